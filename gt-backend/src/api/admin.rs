@@ -1,5 +1,4 @@
 use axum::{extract::State, Extension, Json};
-use migration::Expr;
 use sea_orm::*;
 
 use crate::{db, AppError, AppState, Result};
@@ -13,16 +12,17 @@ pub async fn merge_names(
 ) -> Result<Json<u64>> {
     let delete_name = ExerciseName::find()
         .filter(exercise_name::Column::Name.eq(payload.to_delete.clone()))
+        .filter(exercise_name::Column::OwnerId.is_null())
         .one(&state.conn)
         .await?
         .ok_or(AppError::ResourceNotFound)?;
-    let expand_name_opt = ExerciseName::find()
-        .filter(exercise_name::Column::Name.eq(payload.to_expand.clone()))
-        .one(&state.conn)
-        .await?;
+    let expand_name_opt =
+        db::exercise::find_exercise_name(&payload.to_expand, None, &state.conn).await?;
+
+    let txn = state.conn.begin().await?;
 
     let expand_name_id = if let Some(expand_name) = expand_name_opt {
-        if delete_name.kind != expand_name.kind {
+        if delete_name.kind != expand_name.kind || delete_name.id == expand_name.id {
             return Err(AppError::ValidationError);
         }
         expand_name.id
@@ -32,20 +32,137 @@ pub async fn merge_names(
             kind: ActiveValue::Set(delete_name.kind.into()),
             ..Default::default()
         };
-        let res = ExerciseName::insert(new_name).exec(&state.conn).await?;
-        res.last_insert_id
+        let new_name = new_name.insert(&txn).await?;
+        db::exercise::merge_private_duplicates(&new_name, &txn).await?;
+        new_name.id
     };
 
-    let q = ExerciseSet::update_many()
-        .col_expr(exercise_set::Column::NameId, Expr::value(expand_name_id))
-        .filter(exercise_set::Column::NameId.eq(delete_name.id));
+    let rows_affected =
+        db::exercise::merge_exercise_names(delete_name, expand_name_id, payload.keep_alias, &txn)
+            .await?;
+
+    txn.commit().await?;
+
+    Ok(Json(rows_affected))
+}
+
+pub async fn add_exercise_alias(
+    State(state): State<AppState>,
+    #[allow(unused_variables)] Extension(user): Extension<user_login::Model>,
+    Json(payload): Json<models::ExerciseAlias>,
+) -> Result<Json<()>> {
+    // An alias must not be confused with another exercise.
+    if payload.alias.is_empty()
+        || db::exercise::find_exercise_name(&payload.alias, None, &state.conn)
+            .await?
+            .is_some()
+    {
+        return Err(AppError::ValidationError);
+    }
+
+    let name = db::exercise::find_exercise_name(&payload.name, None, &state.conn)
+        .await?
+        .ok_or(AppError::ResourceNotFound)?;
+
+    let new_alias = exercise_alias::ActiveModel {
+        alias: ActiveValue::Set(payload.alias),
+        name_id: ActiveValue::Set(name.id),
+        ..Default::default()
+    };
+    ExerciseAlias::insert(new_alias).exec(&state.conn).await?;
+
+    Ok(Json(()))
+}
+
+pub async fn delete_exercise_alias(
+    State(state): State<AppState>,
+    #[allow(unused_variables)] Extension(user): Extension<user_login::Model>,
+    Json(payload): Json<models::ExerciseAlias>,
+) -> Result<Json<()>> {
+    let _res = ExerciseAlias::delete_many()
+        .filter(exercise_alias::Column::Alias.eq(payload.alias))
+        .exec(&state.conn)
+        .await?;
+
+    Ok(Json(()))
+}
+
+pub async fn change_exercise_name_metadata(
+    State(state): State<AppState>,
+    #[allow(unused_variables)] Extension(user): Extension<user_login::Model>,
+    Json(payload): Json<models::ExerciseNameMetadata>,
+) -> Result<Json<()>> {
+    let mut name: exercise_name::ActiveModel = ExerciseName::find()
+        .filter(exercise_name::Column::Name.eq(payload.name))
+        .filter(exercise_name::Column::OwnerId.is_null())
+        .one(&state.conn)
+        .await?
+        .ok_or(AppError::ResourceNotFound)?
+        .into();
+    name.primary_muscle = ActiveValue::Set(payload.primary_muscle.map(Into::into));
+    name.secondary_muscle = ActiveValue::Set(payload.secondary_muscle.map(Into::into));
+    name.equipment = ActiveValue::Set(payload.equipment.map(Into::into));
+    name.movement_pattern = ActiveValue::Set(payload.movement_pattern.map(Into::into));
+    name.update(&state.conn).await?;
+
+    Ok(Json(()))
+}
 
-    log::info!("{}", q.build(DbBackend::Postgres).to_string());
+pub async fn get_private_exercise_names(
+    State(state): State<AppState>,
+    #[allow(unused_variables)] Extension(user): Extension<user_login::Model>,
+) -> Result<Json<Vec<models::PrivateExerciseNameQuery>>> {
+    let res = ExerciseName::find()
+        .select_only()
+        .column(exercise_name::Column::Name)
+        .column(exercise_name::Column::Kind)
+        .column_as(user_login::Column::Username, "username")
+        .inner_join(UserLogin)
+        .order_by(exercise_name::Column::Name, Order::Asc)
+        .into_model::<models::PrivateExerciseNameQuery>()
+        .all(&state.conn)
+        .await?;
 
-    let res_update = q.exec(&state.conn).await?;
-    let _res_delete = delete_name.delete(&state.conn).await?;
+    Ok(Json(res))
+}
 
-    Ok(Json(res_update.rows_affected))
+/// Move a private exercise into the global catalog and merge the private exercises of other users
+/// with the same name into it.
+pub async fn promote_exercise_name(
+    State(state): State<AppState>,
+    #[allow(unused_variables)] Extension(user): Extension<user_login::Model>,
+    Json(payload): Json<models::ExerciseNamePromote>,
+) -> Result<Json<()>> {
+    let owner = UserLogin::find()
+        .filter(user_login::Column::Username.eq(payload.username))
+        .one(&state.conn)
+        .await?
+        .ok_or(AppError::ResourceNotFound)?;
+    let name = ExerciseName::find()
+        .filter(exercise_name::Column::Name.eq(payload.name.clone()))
+        .filter(exercise_name::Column::OwnerId.eq(owner.id))
+        .one(&state.conn)
+        .await?
+        .ok_or(AppError::ResourceNotFound)?;
+
+    // A global exercise of the same name should be merged with instead.
+    if db::exercise::find_exercise_name(&payload.name, None, &state.conn)
+        .await?
+        .is_some()
+    {
+        return Err(AppError::ValidationError);
+    }
+
+    let txn = state.conn.begin().await?;
+
+    let mut name: exercise_name::ActiveModel = name.into();
+    name.owner_id = ActiveValue::Set(None);
+    let name = name.update(&txn).await?;
+    db::exercise::merge_private_duplicates(&name, &txn).await?;
+
+    txn.commit().await?;
+
+    Ok(Json(()))
 }
 
 pub async fn reset_password(
@@ -1,5 +1,7 @@
-use axum::extract::Path;
+use axum::extract::Query as QueryParams;
 use axum::{extract::State, Extension, Json};
+use chrono::Utc;
+use http::HeaderMap;
 use migration::{Alias, Expr, NullOrdering, Query, SimpleExpr, SubQueryStatement};
 use sea_orm::*;
 
@@ -7,19 +9,19 @@ use crate::{db, AppError, AppState, Result};
 use gt_core::entities::{prelude::*, *};
 use gt_core::models;
 
+/// Adding an exercise that is already in the catalog, or has an alias of that name, does not create a private copy.
 pub async fn add_exercise_name(
     State(state): State<AppState>,
-    #[allow(unused_variables)] Extension(user): Extension<user_login::Model>,
+    Extension(user): Extension<user_login::Model>,
     Json(payload): Json<models::ExerciseName>,
 ) -> Result<Json<()>> {
-    let exercise_name = exercise_name::ActiveModel {
-        name: ActiveValue::Set(payload.name),
-        ..Default::default()
-    };
-
-    ExerciseName::insert(exercise_name)
-        .exec(&state.conn)
-        .await?;
+    db::exercise::get_or_create_exercise_name(
+        &payload.name,
+        payload.kind,
+        Some(user.id),
+        &state.conn,
+    )
+    .await?;
 
     Ok(Json(()))
 }
@@ -27,11 +29,23 @@ pub async fn add_exercise_name(
 pub async fn get_all_exercise_names(
     State(state): State<AppState>,
     Extension(user): Extension<user_login::Model>,
+    QueryParams(unit_param): QueryParams<models::WeightUnitParam>,
 ) -> Result<Json<Vec<models::ExerciseNameQuery>>> {
+    let unit = db::user::get_weight_unit(user.id, unit_param.unit, &state.conn).await?;
     let q = Query::select()
         .from(exercise_name::Entity)
         .column((exercise_name::Entity, exercise_name::Column::Name))
         .column((exercise_name::Entity, exercise_name::Column::Kind))
+        .column((exercise_name::Entity, exercise_name::Column::PrimaryMuscle))
+        .column((
+            exercise_name::Entity,
+            exercise_name::Column::SecondaryMuscle,
+        ))
+        .column((exercise_name::Entity, exercise_name::Column::Equipment))
+        .column((
+            exercise_name::Entity,
+            exercise_name::Column::MovementPattern,
+        ))
         .expr_as(
             SimpleExpr::SubQuery(
                 None,
@@ -40,6 +54,7 @@ pub async fn get_all_exercise_names(
                         .column((exercise_set::Entity, exercise_set::Column::Weight))
                         .from(exercise_set::Entity)
                         .and_where(exercise_set::Column::UserId.eq(user.id))
+                        .and_where(exercise_set::Column::DeletedAt.is_null())
                         .and_where(
                             Expr::col(exercise_set::Column::NameId)
                                 .equals(exercise_name::Entity, exercise_name::Column::Id),
@@ -60,6 +75,7 @@ pub async fn get_all_exercise_names(
                         .column((exercise_set::Entity, exercise_set::Column::CreatedAt))
                         .from(exercise_set::Entity)
                         .and_where(exercise_set::Column::UserId.eq(user.id))
+                        .and_where(exercise_set::Column::DeletedAt.is_null())
                         .and_where(
                             Expr::col(exercise_set::Column::NameId)
                                 .equals(exercise_name::Entity, exercise_name::Column::Id),
@@ -72,6 +88,24 @@ pub async fn get_all_exercise_names(
             ),
             Alias::new("created_at"),
         )
+        .expr_as(
+            SimpleExpr::SubQuery(
+                None,
+                Box::new(SubQueryStatement::SelectStatement(
+                    Query::select()
+                        .column((exercise_rest::Entity, exercise_rest::Column::Rest))
+                        .from(exercise_rest::Entity)
+                        .and_where(exercise_rest::Column::UserId.eq(user.id))
+                        .and_where(
+                            Expr::col(exercise_rest::Column::NameId)
+                                .equals(exercise_name::Entity, exercise_name::Column::Id),
+                        )
+                        .to_owned(),
+                )),
+            ),
+            Alias::new("rest"),
+        )
+        .cond_where(db::exercise::visible_exercise_names(Some(user.id)))
         .order_by_with_nulls(
             exercise_set::Column::CreatedAt,
             Order::Desc,
@@ -87,79 +121,400 @@ pub async fn get_all_exercise_names(
     .all(&state.conn)
     .await?;
 
+    Ok(Json(unit.convert_from_kg(res)))
+}
+
+pub async fn get_all_exercise_aliases(
+    State(state): State<AppState>,
+    Extension(user): Extension<user_login::Model>,
+) -> Result<Json<Vec<models::ExerciseAlias>>> {
+    let res = ExerciseAlias::find()
+        .select_only()
+        .column(exercise_alias::Column::Alias)
+        .column_as(exercise_name::Column::Name, "name")
+        .inner_join(ExerciseName)
+        .filter(db::exercise::visible_exercise_names(Some(user.id)))
+        .order_by(exercise_alias::Column::Alias, Order::Asc)
+        .into_model::<models::ExerciseAlias>()
+        .all(&state.conn)
+        .await?;
+
     Ok(Json(res))
 }
 
-pub async fn add_exercise_set_for_user(
+pub async fn set_exercise_rest_for_user(
     State(state): State<AppState>,
     Extension(user): Extension<user_login::Model>,
-    Json(payload): Json<models::ExerciseSet>,
+    Json(payload): Json<models::ExerciseRest>,
 ) -> Result<Json<()>> {
-    // get or create exercise name
-    let opt_name = ExerciseName::find()
-        .filter(exercise_name::Column::Name.eq(payload.name()))
-        .one(&state.conn)
+    if !payload.is_valid() {
+        return Err(AppError::ValidationError);
+    }
+
+    let name = db::exercise::find_exercise_name(&payload.name, Some(user.id), &state.conn)
+        .await?
+        .ok_or(AppError::ResourceNotFound)?;
+
+    // replace the previous default, if any
+    let txn = state.conn.begin().await?;
+    ExerciseRest::delete_many()
+        .filter(exercise_rest::Column::UserId.eq(user.id))
+        .filter(exercise_rest::Column::NameId.eq(name.id))
+        .exec(&txn)
         .await?;
 
-    let name_id = if let Some(name) = opt_name {
-        if Ok(payload.kind()) != name.kind.try_into() {
-            return Err(AppError::ValidationError);
-        }
-        name.id
-    } else {
-        let new_name = exercise_name::ActiveModel {
-            name: ActiveValue::Set(payload.name().to_string()),
-            kind: ActiveValue::Set(payload.kind().into()),
+    if let Some(rest) = payload.rest {
+        let exercise_rest = exercise_rest::ActiveModel {
+            user_id: ActiveValue::Set(user.id),
+            name_id: ActiveValue::Set(name.id),
+            rest: ActiveValue::Set(rest),
             ..Default::default()
         };
-        let res = ExerciseName::insert(new_name).exec(&state.conn).await?;
-        res.last_insert_id
+
+        ExerciseRest::insert(exercise_rest).exec(&txn).await?;
+    }
+
+    txn.commit().await?;
+    Ok(Json(()))
+}
+
+/// The idempotency key of a submission, if the client sent one.
+fn get_idempotency_key(headers: &HeaderMap) -> Result<Option<String>> {
+    let key = match headers.get(models::IDEMPOTENCY_KEY_HEADER) {
+        Some(key) => key.to_str().map_err(|_| AppError::ValidationError)?,
+        None => return Ok(None),
     };
 
+    if key.is_empty() || key.len() > models::IDEMPOTENCY_KEY_MAX_LEN {
+        return Err(AppError::ValidationError);
+    }
+    Ok(Some(key.to_string()))
+}
+
+/// The set the user already submitted with this key. Sets in the trash count as well, so that
+/// replaying a submission does not bring back a set that was deleted in the meantime.
+async fn find_submitted_set<C: ConnectionTrait>(
+    user_id: i32,
+    key: &str,
+    conn: &C,
+) -> Result<Option<exercise_set::Model>> {
+    let exs = ExerciseSet::find()
+        .filter(exercise_set::Column::UserId.eq(user_id))
+        .filter(exercise_set::Column::IdempotencyKey.eq(key))
+        .one(conn)
+        .await?;
+    Ok(exs)
+}
+
+/// Whether the user already submitted a set with this key.
+async fn is_duplicate_submission<C: ConnectionTrait>(
+    user_id: i32,
+    key: &str,
+    conn: &C,
+) -> Result<bool> {
+    Ok(find_submitted_set(user_id, key, conn).await?.is_some())
+}
+
+/// The response to a round that was already submitted, whose key is stored on the first set of the round.
+fn submitted_group(exs: exercise_set::Model) -> Result<Json<models::ExerciseSetGroupId>> {
+    let group_id = exs.group_id.ok_or(AppError::ValidationError)?;
+    Ok(Json(models::ExerciseSetGroupId { group_id }))
+}
+
+pub async fn add_exercise_set_for_user(
+    State(state): State<AppState>,
+    Extension(user): Extension<user_login::Model>,
+    QueryParams(unit_param): QueryParams<models::WeightUnitParam>,
+    headers: HeaderMap,
+    Json(payload): Json<models::ExerciseSet>,
+) -> Result<Json<models::NewRecords>> {
+    if !payload.effort_is_valid() {
+        return Err(AppError::ValidationError);
+    }
+
+    let idempotency_key = get_idempotency_key(&headers)?;
+    if let Some(key) = &idempotency_key {
+        if is_duplicate_submission(user.id, key, &state.conn).await? {
+            return Ok(Json(models::NewRecords::default()));
+        }
+    }
+
+    let unit = db::user::get_weight_unit(user.id, unit_param.unit, &state.conn).await?;
+    let payload = unit.convert_to_kg(payload);
+
+    let name_id = db::exercise::get_or_create_exercise_name(
+        payload.name(),
+        payload.kind(),
+        Some(user.id),
+        &state.conn,
+    )
+    .await?;
+
+    // records are computed before inserting so the new set is only compared to earlier ones
+    let formula = db::user::get_e1rm_formula(user.id, &state.conn).await?;
+    let records = db::pr::get_new_records(user.id, name_id, &payload, formula, &state.conn).await?;
+
+    // attach the set to the workout session that is currently in progress
+    let session_id = db::session::get_active_session(user.id, &state.conn)
+        .await?
+        .map(|session| session.id);
+
     let new_exercise_set = exercise_set::ActiveModel {
         user_id: ActiveValue::Set(user.id),
         name_id: ActiveValue::Set(name_id),
+        session_id: ActiveValue::Set(session_id),
+        idempotency_key: ActiveValue::Set(idempotency_key.clone()),
         ..payload.into()
     };
 
-    ExerciseSet::insert(new_exercise_set)
+    // The same submission might have been replayed concurrently, which the unique index rejects.
+    if let Err(e) = ExerciseSet::insert(new_exercise_set)
         .exec(&state.conn)
-        .await?;
+        .await
+    {
+        return match &idempotency_key {
+            Some(key) if is_duplicate_submission(user.id, key, &state.conn).await? => {
+                Ok(Json(models::NewRecords::default()))
+            }
+            _ => Err(e.into()),
+        };
+    }
 
-    Ok(Json(()))
+    Ok(Json(records))
 }
 
-pub async fn get_all_exercise_sets_for_user(
+pub async fn add_exercise_set_group_for_user(
     State(state): State<AppState>,
     Extension(user): Extension<user_login::Model>,
-) -> Result<Json<Vec<models::ExerciseSetQuery>>> {
-    let res = db::exercise::get_exercise_sets(user.id, None, &state.conn).await?;
-    Ok(Json(res))
+    QueryParams(unit_param): QueryParams<models::WeightUnitParam>,
+    headers: HeaderMap,
+    Json(payload): Json<models::ExerciseSetGroup>,
+) -> Result<Json<models::ExerciseSetGroupId>> {
+    if payload.sets.is_empty() || !payload.sets.iter().all(|exs| exs.effort_is_valid()) {
+        return Err(AppError::ValidationError);
+    }
+
+    let idempotency_key = get_idempotency_key(&headers)?;
+    if let Some(key) = &idempotency_key {
+        if let Some(exs) = find_submitted_set(user.id, key, &state.conn).await? {
+            return submitted_group(exs);
+        }
+    }
+
+    let unit = db::user::get_weight_unit(user.id, unit_param.unit, &state.conn).await?;
+    let payload = unit.convert_to_kg(payload);
+
+    // A round that refers to its group by key joins the group the keyed round was added to.
+    // If that round never arrived, e.g. because it was rejected, a new group is started.
+    let mut group_id = payload.group_id;
+    if let (None, Some(group_key)) = (group_id, &payload.group_key) {
+        group_id = find_submitted_set(user.id, group_key, &state.conn)
+            .await?
+            .and_then(|exs| exs.group_id);
+    }
+
+    // only allow adding to groups of the user
+    if let Some(group_id) = group_id {
+        ExerciseSet::find()
+            .filter(exercise_set::Column::UserId.eq(user.id))
+            .filter(exercise_set::Column::GroupId.eq(group_id))
+            .filter(exercise_set::Column::DeletedAt.is_null())
+            .one(&state.conn)
+            .await?
+            .ok_or(AppError::ResourceNotFound)?;
+    }
+
+    let session_id = db::session::get_active_session(user.id, &state.conn)
+        .await?
+        .map(|session| session.id);
+
+    let txn = state.conn.begin().await?;
+    // the key is unique per user, so it is only stored on the first set of the round
+    let mut first_key = idempotency_key.clone();
+
+    for exs in payload.sets {
+        let name_id =
+            db::exercise::get_or_create_exercise_name(exs.name(), exs.kind(), Some(user.id), &txn)
+                .await?;
+
+        let new_exercise_set = exercise_set::ActiveModel {
+            user_id: ActiveValue::Set(user.id),
+            name_id: ActiveValue::Set(name_id),
+            session_id: ActiveValue::Set(session_id),
+            group_id: ActiveValue::Set(group_id),
+            idempotency_key: ActiveValue::Set(first_key.take()),
+            ..exs.into()
+        };
+
+        // The same submission might have been replayed concurrently, which the unique index rejects.
+        let res = match ExerciseSet::insert(new_exercise_set).exec(&txn).await {
+            Ok(res) => res,
+            Err(e) => {
+                txn.rollback().await?;
+                if let Some(key) = &idempotency_key {
+                    if let Some(exs) = find_submitted_set(user.id, key, &state.conn).await? {
+                        return submitted_group(exs);
+                    }
+                }
+                return Err(e.into());
+            }
+        };
+
+        // the first set of a new group gives the group its id
+        if group_id.is_none() {
+            ExerciseSet::update_many()
+                .col_expr(
+                    exercise_set::Column::GroupId,
+                    Expr::value(res.last_insert_id),
+                )
+                .filter(exercise_set::Column::Id.eq(res.last_insert_id))
+                .exec(&txn)
+                .await?;
+            group_id = Some(res.last_insert_id);
+        }
+    }
+
+    txn.commit().await?;
+
+    let group_id = group_id.ok_or(AppError::ValidationError)?;
+    Ok(Json(models::ExerciseSetGroupId { group_id }))
 }
 
-pub async fn get_paged_exercise_sets_for_user(
+pub async fn get_all_exercise_sets_for_user(
     State(state): State<AppState>,
     Extension(user): Extension<user_login::Model>,
-    Path(page_size): Path<u64>,
+    QueryParams(set_type_filter): QueryParams<models::SetTypeFilter>,
+    QueryParams(params): QueryParams<models::ExerciseSetHistoryParams>,
+    QueryParams(utc_offset): QueryParams<models::UtcOffsetParam>,
+    QueryParams(unit_param): QueryParams<models::WeightUnitParam>,
 ) -> Result<Json<Vec<models::ExerciseSetQuery>>> {
-    let res = db::exercise::get_exercise_sets(user.id, Some(page_size), &state.conn).await?;
-    Ok(Json(res))
+    let unit = db::user::get_weight_unit(user.id, unit_param.unit, &state.conn).await?;
+
+    // a name that does not belong to any exercise cannot have sets
+    let name_id = match (params.name_id, params.name) {
+        (Some(name_id), _) => Some(name_id),
+        (None, Some(name)) => {
+            match db::exercise::find_exercise_name(&name, Some(user.id), &state.conn).await? {
+                Some(name) => Some(name.id),
+                None => return Ok(Json(vec![])),
+            }
+        }
+        (None, None) => None,
+    };
+
+    // the cursor needs both parts to be unambiguous
+    let before = match (params.before_created_at, params.before_id) {
+        (Some(created_at), Some(id)) => Some((created_at, id)),
+        (None, None) => None,
+        _ => return Err(AppError::ValidationError),
+    };
+
+    let res = db::exercise::get_exercise_sets(
+        user.id,
+        db::exercise::ExerciseSetFilter {
+            limit: params.limit,
+            name_id,
+            kind: params.kind,
+            from: params.from,
+            to: params.to,
+            utc_offset,
+            before,
+            exclude_warmup: set_type_filter.exclude_warmup,
+            ..Default::default()
+        },
+        &state.conn,
+    )
+    .await?;
+    Ok(Json(unit.convert_from_kg(res)))
 }
 
 pub async fn get_exercise_set_prs_for_user(
     State(state): State<AppState>,
     Extension(user): Extension<user_login::Model>,
+    QueryParams(set_type_filter): QueryParams<models::SetTypeFilter>,
+    QueryParams(unit_param): QueryParams<models::WeightUnitParam>,
 ) -> Result<Json<models::PRQuery>> {
-    let res_weighted = db::pr::get_weighted_exercise_set_prs_for_user(user.id, &state.conn).await?;
+    let unit = db::user::get_weight_unit(user.id, unit_param.unit, &state.conn).await?;
+    let formula = db::user::get_e1rm_formula(user.id, &state.conn).await?;
+    let exclude_warmup = set_type_filter.exclude_warmup;
+    let res_weighted = db::pr::get_weighted_exercise_set_prs_for_user(
+        user.id,
+        exclude_warmup,
+        formula,
+        &state.conn,
+    )
+    .await?;
     let res_bodyweight =
-        db::pr::get_bodyweight_exercise_set_prs_for_user(user.id, &state.conn).await?;
+        db::pr::get_bodyweight_exercise_set_prs_for_user(user.id, exclude_warmup, &state.conn)
+            .await?;
+    let res_timed =
+        db::pr::get_timed_exercise_set_prs_for_user(user.id, exclude_warmup, &state.conn).await?;
+    let res_distance =
+        db::pr::get_distance_exercise_set_prs_for_user(user.id, exclude_warmup, &state.conn)
+            .await?;
+    let res_weighted_bodyweight = db::pr::get_weighted_bodyweight_exercise_set_prs_for_user(
+        user.id,
+        exclude_warmup,
+        formula,
+        &state.conn,
+    )
+    .await?;
 
     let res = models::PRQuery {
         weighted: res_weighted,
         bodyweight: res_bodyweight,
+        timed: res_timed,
+        distance: res_distance,
+        weighted_bodyweight: res_weighted_bodyweight,
     };
 
-    Ok(Json(res))
+    Ok(Json(unit.convert_from_kg(res)))
+}
+
+pub async fn change_exercise_set_for_user(
+    State(state): State<AppState>,
+    Extension(user): Extension<user_login::Model>,
+    QueryParams(unit_param): QueryParams<models::WeightUnitParam>,
+    Json(payload): Json<models::ExerciseSetUpdate>,
+) -> Result<Json<()>> {
+    if !payload.set.effort_is_valid() {
+        return Err(AppError::ValidationError);
+    }
+
+    let unit = db::user::get_weight_unit(user.id, unit_param.unit, &state.conn).await?;
+    let payload = unit.convert_to_kg(payload);
+
+    // only allow changing sets of the user
+    let (old_exercise_set, old_name) = ExerciseSet::find_by_id(payload.id)
+        .filter(exercise_set::Column::UserId.eq(user.id))
+        .filter(exercise_set::Column::DeletedAt.is_null())
+        .find_also_related(ExerciseName)
+        .one(&state.conn)
+        .await?
+        .ok_or(AppError::ResourceNotFound)?;
+    let old_name = old_name.ok_or(AppError::ResourceNotFound)?;
+
+    // a set keeps its kind, otherwise the values of the old kind would stay behind
+    if Ok(payload.set.kind()) != old_name.kind.try_into() {
+        return Err(AppError::ValidationError);
+    }
+
+    let name_id = db::exercise::get_or_create_exercise_name(
+        payload.set.name(),
+        payload.set.kind(),
+        Some(user.id),
+        &state.conn,
+    )
+    .await?;
+
+    let exercise_set = exercise_set::ActiveModel {
+        id: ActiveValue::Unchanged(old_exercise_set.id),
+        name_id: ActiveValue::Set(name_id),
+        ..payload.set.into()
+    };
+    exercise_set.update(&state.conn).await?;
+
+    Ok(Json(()))
 }
 
 pub async fn delete_exercise_set_for_user(
@@ -167,8 +522,15 @@ pub async fn delete_exercise_set_for_user(
     Extension(user): Extension<user_login::Model>,
     Json(payload): Json<models::ExerciseSetDelete>,
 ) -> Result<Json<()>> {
-    let _res = ExerciseSet::delete_by_id(payload.id)
+    // sets are only moved to the trash and purged after the retention period
+    let _res = ExerciseSet::update_many()
+        .col_expr(
+            exercise_set::Column::DeletedAt,
+            Expr::value(Utc::now().naive_utc()),
+        )
+        .filter(exercise_set::Column::Id.eq(payload.id))
         .filter(exercise_set::Column::UserId.eq(user.id))
+        .filter(exercise_set::Column::DeletedAt.is_null())
         .exec(&state.conn)
         .await?;
 
@@ -178,7 +540,31 @@ pub async fn delete_exercise_set_for_user(
 pub async fn get_exercise_graph_for_user(
     State(state): State<AppState>,
     Extension(user): Extension<user_login::Model>,
+    QueryParams(set_type_filter): QueryParams<models::SetTypeFilter>,
+    QueryParams(unit_param): QueryParams<models::WeightUnitParam>,
 ) -> Result<Json<Vec<models::ExerciseGraphQuery>>> {
-    let res = db::exercise::get_exercise_graphs(user.id, &state.conn).await?;
+    let unit = db::user::get_weight_unit(user.id, unit_param.unit, &state.conn).await?;
+    let formula = db::user::get_e1rm_formula(user.id, &state.conn).await?;
+    let res = db::exercise::get_exercise_graphs(
+        user.id,
+        set_type_filter.exclude_warmup,
+        formula,
+        &state.conn,
+    )
+    .await?;
+    Ok(Json(unit.convert_from_kg(res)))
+}
+
+pub async fn get_exercise_pace_graph_for_user(
+    State(state): State<AppState>,
+    Extension(user): Extension<user_login::Model>,
+    QueryParams(set_type_filter): QueryParams<models::SetTypeFilter>,
+) -> Result<Json<Vec<models::ExercisePaceGraphQuery>>> {
+    let res = db::exercise::get_exercise_pace_graphs(
+        user.id,
+        set_type_filter.exclude_warmup,
+        &state.conn,
+    )
+    .await?;
     Ok(Json(res))
 }
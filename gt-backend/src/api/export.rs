@@ -0,0 +1,196 @@
+use std::io;
+
+use axum::{
+    body::StreamBody,
+    extract::{Query as QueryParams, State},
+    http::header,
+    response::IntoResponse,
+    Extension,
+};
+use futures::{channel::mpsc, pin_mut, SinkExt, Stream, StreamExt};
+use http::HeaderMap;
+use itertools::Itertools;
+use sea_orm::*;
+
+use crate::{db, AppState, Result};
+use gt_core::entities::*;
+use gt_core::models;
+
+/// How many lines are buffered before reading from the database waits for the client.
+const CSV_BUFFER_LINES: usize = 64;
+
+const CSV_DATE_FORMAT: &str = "%Y-%m-%d %H:%M";
+
+type CsvLine = std::result::Result<String, io::Error>;
+
+/// Quote a field if it contains a separator, a quote or a line break (RFC 4180).
+fn csv_field(field: String) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field
+    }
+}
+
+fn csv_line(fields: Vec<String>) -> String {
+    let mut line = fields.into_iter().map(csv_field).join(",");
+    line.push_str("\r\n");
+    line
+}
+
+/// Missing values are empty fields.
+fn optional<T: ToString>(value: Option<T>) -> String {
+    value.map(|value| value.to_string()).unwrap_or_default()
+}
+
+/// Send the header and then every row as a line of CSV, until the client stops the download.
+/// A database error ends the response with an error, so the download fails instead of silently missing rows.
+async fn send_csv<S, T, F>(
+    mut tx: mpsc::Sender<CsvLine>,
+    header: Vec<String>,
+    rows: std::result::Result<S, DbErr>,
+    to_fields: F,
+) where
+    S: Stream<Item = std::result::Result<T, DbErr>>,
+    F: Fn(T) -> Vec<String>,
+{
+    let rows = match rows {
+        Ok(rows) => rows,
+        Err(e) => {
+            log::error!("{}", e);
+            let _ = tx.send(Err(io::Error::new(io::ErrorKind::Other, e))).await;
+            return;
+        }
+    };
+    pin_mut!(rows);
+
+    if tx.send(Ok(csv_line(header))).await.is_err() {
+        return;
+    }
+    while let Some(row) = rows.next().await {
+        let line = match row {
+            Ok(row) => Ok(csv_line(to_fields(row))),
+            Err(e) => {
+                log::error!("{}", e);
+                Err(io::Error::new(io::ErrorKind::Other, e))
+            }
+        };
+        let failed = line.is_err();
+        if tx.send(line).await.is_err() || failed {
+            return;
+        }
+    }
+}
+
+fn csv_response(filename: &str, lines: mpsc::Receiver<CsvLine>) -> impl IntoResponse {
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        header::CONTENT_TYPE,
+        "text/csv; charset=utf-8".parse().unwrap(),
+    );
+    headers.insert(
+        header::CONTENT_DISPOSITION,
+        format!("attachment; filename=\"{}\"", filename)
+            .parse()
+            .unwrap(),
+    );
+
+    (headers, StreamBody::new(lines))
+}
+
+/// All exercise sets of the user as CSV. Weights are given in the unit of the user and times in UTC.
+pub async fn export_exercise_sets(
+    State(state): State<AppState>,
+    Extension(user): Extension<user_login::Model>,
+    QueryParams(range): QueryParams<models::ExportRange>,
+    QueryParams(utc_offset): QueryParams<models::UtcOffsetParam>,
+    QueryParams(unit_param): QueryParams<models::WeightUnitParam>,
+) -> Result<impl IntoResponse> {
+    let unit = db::user::get_weight_unit(user.id, unit_param.unit, &state.conn).await?;
+    let header = vec![
+        "id".to_string(),
+        "date (UTC)".to_string(),
+        "exercise".to_string(),
+        "kind".to_string(),
+        "set type".to_string(),
+        "reps".to_string(),
+        format!("weight ({})", unit.label()),
+        "time (s)".to_string(),
+        "distance (m)".to_string(),
+        "rpe".to_string(),
+        "rir".to_string(),
+        "session".to_string(),
+        "group".to_string(),
+        "note".to_string(),
+    ];
+
+    // The rows are read while the response is sent, which needs its own task.
+    let (tx, rx) = mpsc::channel(CSV_BUFFER_LINES);
+    let user_id = user.id;
+    tokio::spawn(async move {
+        let rows = db::export::exercise_sets(user_id, &range, utc_offset)
+            .stream(&state.conn)
+            .await;
+        send_csv(tx, header, rows, |exs: models::ExerciseSetExportQuery| {
+            let exs = unit.convert_from_kg(exs);
+            vec![
+                exs.id.to_string(),
+                exs.created_at.format(CSV_DATE_FORMAT).to_string(),
+                exs.name,
+                exs.kind.label().to_string(),
+                exs.set_type.label().to_string(),
+                optional(exs.reps),
+                optional(exs.weight),
+                optional(exs.time),
+                optional(exs.distance),
+                optional(exs.rpe),
+                optional(exs.rir),
+                optional(exs.session_id),
+                optional(exs.group_id),
+                optional(exs.note),
+            ]
+        })
+        .await
+    });
+
+    Ok(csv_response("sets.csv", rx))
+}
+
+/// All body measurements of the user as CSV. Weights are given in the unit of the user and times in UTC.
+pub async fn export_body_measurements(
+    State(state): State<AppState>,
+    Extension(user): Extension<user_login::Model>,
+    QueryParams(range): QueryParams<models::ExportRange>,
+    QueryParams(utc_offset): QueryParams<models::UtcOffsetParam>,
+    QueryParams(unit_param): QueryParams<models::WeightUnitParam>,
+) -> Result<impl IntoResponse> {
+    let unit = db::user::get_weight_unit(user.id, unit_param.unit, &state.conn).await?;
+    let header = vec![
+        "date (UTC)".to_string(),
+        "height (cm)".to_string(),
+        format!("weight ({})", unit.label()),
+        format!("muscle mass ({})", unit.label()),
+        "body fat (%)".to_string(),
+    ];
+
+    let (tx, rx) = mpsc::channel(CSV_BUFFER_LINES);
+    let user_id = user.id;
+    tokio::spawn(async move {
+        let rows = db::export::body_measurements(user_id, &range, utc_offset)
+            .stream(&state.conn)
+            .await;
+        send_csv(tx, header, rows, |info_ts: user_info_ts::Model| {
+            let info_ts = unit.convert_from_kg(info_ts);
+            vec![
+                info_ts.created_at.format(CSV_DATE_FORMAT).to_string(),
+                optional(info_ts.height),
+                optional(info_ts.weight),
+                optional(info_ts.muscle_mass),
+                optional(info_ts.body_fat),
+            ]
+        })
+        .await
+    });
+
+    Ok(csv_response("body.csv", rx))
+}
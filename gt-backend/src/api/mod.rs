@@ -1,4 +1,10 @@
 pub mod admin;
 pub mod auth;
 pub mod exercise;
+pub mod export;
+pub mod program;
+pub mod routine;
+pub mod session;
+pub mod stats;
+pub mod trash;
 pub mod user;
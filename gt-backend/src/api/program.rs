@@ -0,0 +1,140 @@
+use axum::extract::{Path, Query as QueryParams};
+use axum::{extract::State, Extension, Json};
+use chrono::Utc;
+use sea_orm::*;
+
+use crate::{db, AppError, AppState, Result};
+use gt_core::entities::{prelude::*, *};
+use gt_core::models;
+
+fn validate_program(program: &models::Program) -> Result<()> {
+    if program.name.is_empty()
+        || program.exercises.iter().any(|exercise| {
+            exercise.name.is_empty()
+                || exercise.sets <= 0
+                || exercise.min_reps <= 0
+                || exercise.min_reps > exercise.max_reps
+                || exercise.start_weight < 0.0
+                || exercise.increment < 0.0
+        })
+    {
+        return Err(AppError::ValidationError);
+    }
+    Ok(())
+}
+
+pub async fn get_all_programs(
+    State(state): State<AppState>,
+    Extension(user): Extension<user_login::Model>,
+    QueryParams(unit_param): QueryParams<models::WeightUnitParam>,
+) -> Result<Json<Vec<models::ProgramQuery>>> {
+    let unit = db::user::get_weight_unit(user.id, unit_param.unit, &state.conn).await?;
+    let res = db::program::get_programs(user.id, &state.conn).await?;
+    Ok(Json(unit.convert_from_kg(res)))
+}
+
+pub async fn get_program(
+    State(state): State<AppState>,
+    Extension(user): Extension<user_login::Model>,
+    Path(program_id): Path<i32>,
+    QueryParams(unit_param): QueryParams<models::WeightUnitParam>,
+) -> Result<Json<models::ProgramQuery>> {
+    let unit = db::user::get_weight_unit(user.id, unit_param.unit, &state.conn).await?;
+    let res = db::program::get_program(user.id, program_id, &state.conn)
+        .await?
+        .ok_or(AppError::ResourceNotFound)?;
+    Ok(Json(unit.convert_from_kg(res)))
+}
+
+pub async fn get_program_today(
+    State(state): State<AppState>,
+    Extension(user): Extension<user_login::Model>,
+    Path(program_id): Path<i32>,
+    QueryParams(utc_offset): QueryParams<models::UtcOffsetParam>,
+    QueryParams(unit_param): QueryParams<models::WeightUnitParam>,
+) -> Result<Json<models::PrescribedWorkout>> {
+    let unit = db::user::get_weight_unit(user.id, unit_param.unit, &state.conn).await?;
+    let program = db::program::get_program(user.id, program_id, &state.conn)
+        .await?
+        .ok_or(AppError::ResourceNotFound)?;
+
+    let res = db::program::get_prescribed_workout(user.id, program, unit, utc_offset, &state.conn)
+        .await?;
+    Ok(Json(unit.convert_from_kg(res)))
+}
+
+pub async fn add_program(
+    State(state): State<AppState>,
+    Extension(user): Extension<user_login::Model>,
+    QueryParams(unit_param): QueryParams<models::WeightUnitParam>,
+    Json(payload): Json<models::Program>,
+) -> Result<Json<models::ProgramQuery>> {
+    let unit = db::user::get_weight_unit(user.id, unit_param.unit, &state.conn).await?;
+    let payload = unit.convert_to_kg(payload);
+    validate_program(&payload)?;
+
+    let txn = state.conn.begin().await?;
+
+    let new_program = program::ActiveModel {
+        user_id: ActiveValue::Set(user.id),
+        name: ActiveValue::Set(payload.name),
+        start_date: ActiveValue::Set(Utc::now().naive_utc()),
+        ..Default::default()
+    };
+    let res = Program::insert(new_program).exec(&txn).await?;
+    db::program::set_program_exercises(user.id, res.last_insert_id, payload.exercises, &txn)
+        .await?;
+
+    txn.commit().await?;
+
+    let program = db::program::get_program(user.id, res.last_insert_id, &state.conn)
+        .await?
+        .ok_or(AppError::ResourceNotFound)?;
+    Ok(Json(unit.convert_from_kg(program)))
+}
+
+/// Changing a program keeps its start date, so the progression made so far is not lost.
+pub async fn change_program(
+    State(state): State<AppState>,
+    Extension(user): Extension<user_login::Model>,
+    Path(program_id): Path<i32>,
+    QueryParams(unit_param): QueryParams<models::WeightUnitParam>,
+    Json(payload): Json<models::Program>,
+) -> Result<Json<models::ProgramQuery>> {
+    let unit = db::user::get_weight_unit(user.id, unit_param.unit, &state.conn).await?;
+    let payload = unit.convert_to_kg(payload);
+    validate_program(&payload)?;
+
+    let program = Program::find_by_id(program_id)
+        .filter(program::Column::UserId.eq(user.id))
+        .one(&state.conn)
+        .await?
+        .ok_or(AppError::ResourceNotFound)?;
+
+    let txn = state.conn.begin().await?;
+
+    let mut program: program::ActiveModel = program.into();
+    program.name = ActiveValue::Set(payload.name);
+    program.update(&txn).await?;
+    db::program::set_program_exercises(user.id, program_id, payload.exercises, &txn).await?;
+
+    txn.commit().await?;
+
+    let program = db::program::get_program(user.id, program_id, &state.conn)
+        .await?
+        .ok_or(AppError::ResourceNotFound)?;
+    Ok(Json(unit.convert_from_kg(program)))
+}
+
+pub async fn delete_program(
+    State(state): State<AppState>,
+    Extension(user): Extension<user_login::Model>,
+    Json(payload): Json<models::ProgramDelete>,
+) -> Result<Json<()>> {
+    let _res = Program::delete_by_id(payload.id)
+        .filter(program::Column::UserId.eq(user.id))
+        .exec(&state.conn)
+        .await?;
+
+    Ok(Json(()))
+}
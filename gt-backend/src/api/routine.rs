@@ -0,0 +1,116 @@
+use axum::extract::{Path, Query as QueryParams};
+use axum::{extract::State, Extension, Json};
+use sea_orm::*;
+
+use crate::{db, AppError, AppState, Result};
+use gt_core::entities::{prelude::*, *};
+use gt_core::models;
+
+fn validate_routine(routine: &models::Routine) -> Result<()> {
+    if routine.name.is_empty()
+        || routine
+            .exercises
+            .iter()
+            .any(|exercise| exercise.name.is_empty() || exercise.target_sets <= 0)
+    {
+        return Err(AppError::ValidationError);
+    }
+    Ok(())
+}
+
+pub async fn get_all_routines(
+    State(state): State<AppState>,
+    Extension(user): Extension<user_login::Model>,
+    QueryParams(unit_param): QueryParams<models::WeightUnitParam>,
+) -> Result<Json<Vec<models::RoutineQuery>>> {
+    let unit = db::user::get_weight_unit(user.id, unit_param.unit, &state.conn).await?;
+    let res = db::routine::get_routines(user.id, &state.conn).await?;
+    Ok(Json(unit.convert_from_kg(res)))
+}
+
+pub async fn get_routine(
+    State(state): State<AppState>,
+    Extension(user): Extension<user_login::Model>,
+    Path(routine_id): Path<i32>,
+    QueryParams(unit_param): QueryParams<models::WeightUnitParam>,
+) -> Result<Json<models::RoutineQuery>> {
+    let unit = db::user::get_weight_unit(user.id, unit_param.unit, &state.conn).await?;
+    let res = db::routine::get_routine(user.id, routine_id, &state.conn)
+        .await?
+        .ok_or(AppError::ResourceNotFound)?;
+    Ok(Json(unit.convert_from_kg(res)))
+}
+
+pub async fn add_routine(
+    State(state): State<AppState>,
+    Extension(user): Extension<user_login::Model>,
+    QueryParams(unit_param): QueryParams<models::WeightUnitParam>,
+    Json(payload): Json<models::Routine>,
+) -> Result<Json<models::RoutineQuery>> {
+    let unit = db::user::get_weight_unit(user.id, unit_param.unit, &state.conn).await?;
+    let payload = unit.convert_to_kg(payload);
+    validate_routine(&payload)?;
+
+    let txn = state.conn.begin().await?;
+
+    let new_routine = routine::ActiveModel {
+        user_id: ActiveValue::Set(user.id),
+        name: ActiveValue::Set(payload.name),
+        ..Default::default()
+    };
+    let res = Routine::insert(new_routine).exec(&txn).await?;
+    db::routine::set_routine_exercises(user.id, res.last_insert_id, payload.exercises, &txn)
+        .await?;
+
+    txn.commit().await?;
+
+    let routine = db::routine::get_routine(user.id, res.last_insert_id, &state.conn)
+        .await?
+        .ok_or(AppError::ResourceNotFound)?;
+    Ok(Json(unit.convert_from_kg(routine)))
+}
+
+pub async fn change_routine(
+    State(state): State<AppState>,
+    Extension(user): Extension<user_login::Model>,
+    Path(routine_id): Path<i32>,
+    QueryParams(unit_param): QueryParams<models::WeightUnitParam>,
+    Json(payload): Json<models::Routine>,
+) -> Result<Json<models::RoutineQuery>> {
+    let unit = db::user::get_weight_unit(user.id, unit_param.unit, &state.conn).await?;
+    let payload = unit.convert_to_kg(payload);
+    validate_routine(&payload)?;
+
+    let routine = Routine::find_by_id(routine_id)
+        .filter(routine::Column::UserId.eq(user.id))
+        .one(&state.conn)
+        .await?
+        .ok_or(AppError::ResourceNotFound)?;
+
+    let txn = state.conn.begin().await?;
+
+    let mut routine: routine::ActiveModel = routine.into();
+    routine.name = ActiveValue::Set(payload.name);
+    routine.update(&txn).await?;
+    db::routine::set_routine_exercises(user.id, routine_id, payload.exercises, &txn).await?;
+
+    txn.commit().await?;
+
+    let routine = db::routine::get_routine(user.id, routine_id, &state.conn)
+        .await?
+        .ok_or(AppError::ResourceNotFound)?;
+    Ok(Json(unit.convert_from_kg(routine)))
+}
+
+pub async fn delete_routine(
+    State(state): State<AppState>,
+    Extension(user): Extension<user_login::Model>,
+    Json(payload): Json<models::RoutineDelete>,
+) -> Result<Json<()>> {
+    let _res = Routine::delete_by_id(payload.id)
+        .filter(routine::Column::UserId.eq(user.id))
+        .exec(&state.conn)
+        .await?;
+
+    Ok(Json(()))
+}
@@ -0,0 +1,114 @@
+use axum::extract::{Path, Query as QueryParams};
+use axum::{extract::State, Extension, Json};
+use chrono::Utc;
+use http::StatusCode;
+use sea_orm::*;
+
+use crate::{db, AppError, AppState, Result};
+use gt_core::entities::{prelude::*, *};
+use gt_core::models;
+
+/// Start a new workout session. Exercise sets submitted while it is active are attached to it.
+pub async fn start_session(
+    State(state): State<AppState>,
+    Extension(user): Extension<user_login::Model>,
+    Json(payload): Json<models::WorkoutSessionStart>,
+) -> Result<Json<models::WorkoutSessionQuery>> {
+    if db::session::get_active_session(user.id, &state.conn)
+        .await?
+        .is_some()
+    {
+        return Err(AppError::StatusCode(
+            StatusCode::CONFLICT,
+            "A workout session is already in progress.".to_string(),
+        ));
+    }
+
+    let new_session = workout_session::ActiveModel {
+        user_id: ActiveValue::Set(user.id),
+        title: ActiveValue::Set(payload.title.filter(|title| !title.is_empty())),
+        start_time: ActiveValue::Set(Utc::now().naive_utc()),
+        ..Default::default()
+    };
+    let res = WorkoutSession::insert(new_session)
+        .exec(&state.conn)
+        .await?;
+
+    let session = db::session::get_session(user.id, res.last_insert_id, &state.conn)
+        .await?
+        .ok_or(AppError::ResourceNotFound)?;
+
+    Ok(Json(session))
+}
+
+pub async fn finish_session(
+    State(state): State<AppState>,
+    Extension(user): Extension<user_login::Model>,
+    Json(payload): Json<models::WorkoutSessionFinish>,
+) -> Result<Json<()>> {
+    let session = WorkoutSession::find_by_id(payload.id)
+        .filter(workout_session::Column::UserId.eq(user.id))
+        .one(&state.conn)
+        .await?
+        .ok_or(AppError::ResourceNotFound)?;
+
+    if session.end_time.is_some() {
+        return Err(AppError::ValidationError);
+    }
+
+    let mut session: workout_session::ActiveModel = session.into();
+    session.end_time = ActiveValue::Set(Some(Utc::now().naive_utc()));
+    if payload.notes.is_some() {
+        session.notes = ActiveValue::Set(payload.notes);
+    }
+    session.update(&state.conn).await?;
+
+    Ok(Json(()))
+}
+
+pub async fn get_active_session(
+    State(state): State<AppState>,
+    Extension(user): Extension<user_login::Model>,
+) -> Result<Json<Option<models::WorkoutSessionQuery>>> {
+    let res = match db::session::get_active_session(user.id, &state.conn).await? {
+        Some(session) => db::session::get_session(user.id, session.id, &state.conn).await?,
+        None => None,
+    };
+
+    Ok(Json(res))
+}
+
+pub async fn get_all_sessions(
+    State(state): State<AppState>,
+    Extension(user): Extension<user_login::Model>,
+) -> Result<Json<Vec<models::WorkoutSessionQuery>>> {
+    let res = db::session::get_sessions(user.id, &state.conn).await?;
+    Ok(Json(res))
+}
+
+pub async fn get_session(
+    State(state): State<AppState>,
+    Extension(user): Extension<user_login::Model>,
+    Path(session_id): Path<i32>,
+    QueryParams(unit_param): QueryParams<models::WeightUnitParam>,
+) -> Result<Json<models::WorkoutSessionDetailQuery>> {
+    let unit = db::user::get_weight_unit(user.id, unit_param.unit, &state.conn).await?;
+    let session = db::session::get_session(user.id, session_id, &state.conn)
+        .await?
+        .ok_or(AppError::ResourceNotFound)?;
+
+    let sets = db::exercise::get_exercise_sets(
+        user.id,
+        db::exercise::ExerciseSetFilter {
+            session_id: Some(session.id),
+            ..Default::default()
+        },
+        &state.conn,
+    )
+    .await?;
+
+    Ok(Json(models::WorkoutSessionDetailQuery {
+        session,
+        sets: unit.convert_from_kg(sets),
+    }))
+}
@@ -0,0 +1,36 @@
+use axum::extract::Query as QueryParams;
+use axum::{extract::State, Extension, Json};
+
+use crate::{db, AppState, Result};
+use gt_core::entities::*;
+use gt_core::models;
+
+pub async fn get_stats(
+    State(state): State<AppState>,
+    Extension(user): Extension<user_login::Model>,
+    QueryParams(range): QueryParams<models::StatsRange>,
+    QueryParams(utc_offset): QueryParams<models::UtcOffsetParam>,
+    QueryParams(set_type_filter): QueryParams<models::SetTypeFilter>,
+    QueryParams(unit_param): QueryParams<models::WeightUnitParam>,
+) -> Result<Json<models::StatsQuery>> {
+    let unit = db::user::get_weight_unit(user.id, unit_param.unit, &state.conn).await?;
+    let res = db::stats::get_stats(
+        user.id,
+        &range,
+        utc_offset,
+        set_type_filter.exclude_warmup,
+        &state.conn,
+    )
+    .await?;
+    Ok(Json(unit.convert_from_kg(res)))
+}
+
+pub async fn get_calendar(
+    State(state): State<AppState>,
+    Extension(user): Extension<user_login::Model>,
+    QueryParams(params): QueryParams<models::CalendarParams>,
+    QueryParams(utc_offset): QueryParams<models::UtcOffsetParam>,
+) -> Result<Json<models::CalendarQuery>> {
+    let res = db::stats::get_calendar(user.id, params.year, utc_offset, &state.conn).await?;
+    Ok(Json(res))
+}
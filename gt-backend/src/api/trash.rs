@@ -0,0 +1,25 @@
+use axum::extract::Query as QueryParams;
+use axum::{extract::State, Extension, Json};
+
+use crate::{db, AppState, Result};
+use gt_core::entities::*;
+use gt_core::models;
+
+pub async fn get_trash(
+    State(state): State<AppState>,
+    Extension(user): Extension<user_login::Model>,
+    QueryParams(unit_param): QueryParams<models::WeightUnitParam>,
+) -> Result<Json<models::TrashQuery>> {
+    let unit = db::user::get_weight_unit(user.id, unit_param.unit, &state.conn).await?;
+    let res = db::trash::get_trash(user.id, &state.conn).await?;
+    Ok(Json(unit.convert_from_kg(res)))
+}
+
+pub async fn restore_trash_item(
+    State(state): State<AppState>,
+    Extension(user): Extension<user_login::Model>,
+    Json(payload): Json<models::TrashItem>,
+) -> Result<Json<()>> {
+    db::trash::restore(user.id, payload, &state.conn).await?;
+    Ok(Json(()))
+}
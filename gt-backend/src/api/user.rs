@@ -1,6 +1,6 @@
 use axum::{
     body::Bytes,
-    extract::{Json, State},
+    extract::{Json, Query as QueryParams, State},
     http::header,
     response::IntoResponse,
     Extension,
@@ -8,6 +8,7 @@ use axum::{
 use chrono::Utc;
 use gt_core::models::UserAuth;
 use http::{HeaderMap, StatusCode};
+use migration::Expr;
 use pbkdf2::{
     password_hash::{PasswordHash, PasswordVerifier},
     Pbkdf2,
@@ -95,6 +96,8 @@ pub async fn change_user_info(
         .into();
 
     user_info.display_name = ActiveValue::Set(payload.display_name);
+    user_info.weight_unit = ActiveValue::Set(payload.weight_unit.into());
+    user_info.e1rm_formula = ActiveValue::Set(payload.e1rm_formula.into());
     user_info.update(&state.conn).await?;
 
     Ok(Json(()))
@@ -103,16 +106,23 @@ pub async fn change_user_info(
 pub async fn get_user_info(
     State(state): State<AppState>,
     Extension(user): Extension<user_login::Model>,
+    QueryParams(unit_param): QueryParams<models::WeightUnitParam>,
 ) -> Result<Json<models::UserInfoQuery>> {
     let res = db::user::get_user_info(user, &state.conn).await?;
-    Ok(Json(res))
+    // the saved unit is returned as is, only an explicitly requested unit changes the body metrics
+    let unit = unit_param.unit.unwrap_or(res.weight_unit);
+    Ok(Json(unit.convert_from_kg(res)))
 }
 
 pub async fn add_user_info_ts(
     State(state): State<AppState>,
     Extension(user): Extension<user_login::Model>,
+    QueryParams(unit_param): QueryParams<models::WeightUnitParam>,
     Json(payload): Json<models::UserInfoTs>,
 ) -> Result<Json<()>> {
+    let unit = db::user::get_weight_unit(user.id, unit_param.unit, &state.conn).await?;
+    let payload = unit.convert_to_kg(payload);
+
     let new_user_info_ts = user_info_ts::ActiveModel {
         user_id: ActiveValue::Set(user.id),
         height: ActiveValue::Set(payload.height),
@@ -133,10 +143,36 @@ pub async fn add_user_info_ts(
 pub async fn get_user_info_ts(
     State(state): State<AppState>,
     Extension(user): Extension<user_login::Model>,
+    QueryParams(unit_param): QueryParams<models::WeightUnitParam>,
 ) -> Result<Json<Vec<user_info_ts::Model>>> {
-    let res = user.find_related(UserInfoTs).all(&state.conn).await?;
+    let unit = db::user::get_weight_unit(user.id, unit_param.unit, &state.conn).await?;
+    let res = user
+        .find_related(UserInfoTs)
+        .filter(user_info_ts::Column::DeletedAt.is_null())
+        .all(&state.conn)
+        .await?;
+
+    Ok(Json(unit.convert_from_kg(res)))
+}
+
+pub async fn delete_user_info_ts(
+    State(state): State<AppState>,
+    Extension(user): Extension<user_login::Model>,
+    Json(payload): Json<models::UserInfoTsDelete>,
+) -> Result<Json<()>> {
+    // entries are only moved to the trash and purged after the retention period
+    let _res = UserInfoTs::update_many()
+        .col_expr(
+            user_info_ts::Column::DeletedAt,
+            Expr::value(Utc::now().naive_utc()),
+        )
+        .filter(user_info_ts::Column::Id.eq(payload.id))
+        .filter(user_info_ts::Column::UserId.eq(user.id))
+        .filter(user_info_ts::Column::DeletedAt.is_null())
+        .exec(&state.conn)
+        .await?;
 
-    Ok(Json(res))
+    Ok(Json(()))
 }
 
 pub async fn get_user_picture(
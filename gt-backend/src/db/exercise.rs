@@ -1,43 +1,363 @@
-use chrono::NaiveDate;
+use chrono::{NaiveDate, NaiveDateTime};
 use itertools::Itertools;
-use migration::SimpleExpr;
+use migration::{Expr, Query, SimpleExpr};
 use sea_orm::*;
 use std::collections::HashMap;
 
-use crate::Result;
+use crate::{AppError, Result};
 use gt_core::entities::{prelude::*, *};
 use gt_core::models;
 
+/// The last bodyweight the user recorded at or before the time of an exercise set.
+/// If the user only started recording their bodyweight later on, the earliest recorded bodyweight is used instead.
+pub(crate) const BODYWEIGHT_AT_SET: &str = "COALESCE(\
+    (SELECT user_info_ts.weight FROM user_info_ts \
+        WHERE user_info_ts.user_id = exercise_set.user_id AND user_info_ts.weight IS NOT NULL AND user_info_ts.deleted_at IS NULL \
+        AND user_info_ts.created_at <= exercise_set.created_at \
+        ORDER BY user_info_ts.created_at DESC LIMIT 1), \
+    (SELECT user_info_ts.weight FROM user_info_ts \
+        WHERE user_info_ts.user_id = exercise_set.user_id AND user_info_ts.weight IS NOT NULL AND user_info_ts.deleted_at IS NULL \
+        ORDER BY user_info_ts.created_at ASC LIMIT 1))";
+
+/// Seconds between an exercise set and the set before it in the same workout session, or on the same day
+/// for sets outside of a session. The first set has no rest. Sets are only logged to the minute, so the rest is too.
+const REST_BEFORE_SET: &str = "CAST(EXTRACT(EPOCH FROM exercise_set.created_at - \
+    (SELECT previous.created_at FROM exercise_set AS previous \
+        WHERE previous.user_id = exercise_set.user_id AND previous.deleted_at IS NULL \
+        AND previous.session_id IS NOT DISTINCT FROM exercise_set.session_id \
+        AND (exercise_set.session_id IS NOT NULL \
+            OR date_trunc('day', previous.created_at) = date_trunc('day', exercise_set.created_at)) \
+        AND (previous.created_at, previous.id) < (exercise_set.created_at, exercise_set.id) \
+        ORDER BY previous.created_at DESC, previous.id DESC LIMIT 1)) AS INTEGER)";
+
+/// Restrictions on which exercise sets are returned by [`get_exercise_sets`].
+/// Warm-up sets are always returned, `exclude_warmup` only leaves them out of the running sum of reps.
+/// `before` is a cursor of (created_at, id) and only sets that come after it in the history are returned.
+///
+/// The running sum of reps stays correct with every filter because they either keep or drop a whole
+/// exercise on a day, or only drop newer sets.
+#[derive(Debug, Clone, Default)]
+pub struct ExerciseSetFilter {
+    pub limit: Option<u64>,
+    pub session_id: Option<i32>,
+    pub name_id: Option<i32>,
+    pub kind: Option<models::ExerciseKind>,
+    /// `from` and `to` are days of the user in the time zone given by `utc_offset`.
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+    pub utc_offset: models::UtcOffsetParam,
+    pub before: Option<(NaiveDateTime, i32)>,
+    pub exclude_warmup: bool,
+    /// Only return the sets in the trash instead of all others.
+    pub deleted: bool,
+}
+
+/// Leave out warm-up sets if the user requested it.
+pub(crate) fn filter_warmup(q: Select<ExerciseSet>, exclude_warmup: bool) -> Select<ExerciseSet> {
+    if exclude_warmup {
+        q.filter(exercise_set::Column::SetType.ne(models::SetType::Warmup))
+    } else {
+        q
+    }
+}
+
+/// The exercises a user can see, which are the global catalog and the user's private exercises.
+/// Without a user only the global catalog is visible.
+pub(crate) fn visible_exercise_names(user_id: Option<i32>) -> Condition {
+    let condition = Condition::any().add(exercise_name::Column::OwnerId.is_null());
+    match user_id {
+        Some(user_id) => condition.add(exercise_name::Column::OwnerId.eq(user_id)),
+        None => condition,
+    }
+}
+
+/// Find an exercise visible to the user by its name or by one of its aliases.
+pub async fn find_exercise_name<C: ConnectionTrait>(
+    name: &str,
+    user_id: Option<i32>,
+    conn: &C,
+) -> Result<Option<exercise_name::Model>> {
+    // Creating a global exercise merges private ones of the same name into it, so a name matches at most one exercise.
+    // Should that ever not hold, the user's own exercise comes first because Postgres sorts NULL last in ascending order.
+    let opt_name = ExerciseName::find()
+        .filter(exercise_name::Column::Name.eq(name))
+        .filter(visible_exercise_names(user_id))
+        .order_by(exercise_name::Column::OwnerId, Order::Asc)
+        .one(conn)
+        .await?;
+    if opt_name.is_some() {
+        return Ok(opt_name);
+    }
+
+    let opt_name = ExerciseName::find()
+        .inner_join(ExerciseAlias)
+        .filter(exercise_alias::Column::Alias.eq(name))
+        .filter(visible_exercise_names(user_id))
+        .order_by(exercise_name::Column::OwnerId, Order::Asc)
+        .one(conn)
+        .await?;
+    Ok(opt_name)
+}
+
+/// Look up the id of an exercise name and create it if it does not exist yet.
+/// Fails if the exercise exists with a different kind.
+/// New exercises are private to `user_id`, or part of the global catalog if there is no user.
+/// Sets logged under an alias are stored with the exercise the alias belongs to,
+/// so PRs and graphs are computed for the exercise as a whole.
+pub async fn get_or_create_exercise_name<C: ConnectionTrait>(
+    name: &str,
+    kind: models::ExerciseKind,
+    user_id: Option<i32>,
+    conn: &C,
+) -> Result<i32> {
+    let opt_name = find_exercise_name(name, user_id, conn).await?;
+
+    let name_id = if let Some(name) = opt_name {
+        if Ok(kind.clone()) != name.kind.try_into() {
+            return Err(AppError::ValidationError);
+        }
+        name.id
+    } else {
+        let new_name = exercise_name::ActiveModel {
+            name: ActiveValue::Set(name.to_string()),
+            kind: ActiveValue::Set(kind.into()),
+            owner_id: ActiveValue::Set(user_id),
+            ..Default::default()
+        };
+        let new_name = new_name.insert(conn).await?;
+        if user_id.is_none() {
+            merge_private_duplicates(&new_name, conn).await?;
+        }
+        new_name.id
+    };
+
+    Ok(name_id)
+}
+
+/// Merge the private exercises of other users with the same name into the global exercise `global_name`,
+/// so that nobody sees the name twice. Fails if one of them has a different kind, since its owner
+/// could not tell the two apart anymore.
+pub async fn merge_private_duplicates<C: ConnectionTrait>(
+    global_name: &exercise_name::Model,
+    conn: &C,
+) -> Result<()> {
+    let duplicates = ExerciseName::find()
+        .filter(exercise_name::Column::Name.eq(global_name.name.clone()))
+        .filter(exercise_name::Column::OwnerId.is_not_null())
+        .filter(exercise_name::Column::Id.ne(global_name.id))
+        .all(conn)
+        .await?;
+    if duplicates
+        .iter()
+        .any(|duplicate| duplicate.kind != global_name.kind)
+    {
+        return Err(AppError::ValidationError);
+    }
+
+    for duplicate in duplicates {
+        merge_exercise_names(duplicate, global_name.id, false, conn).await?;
+    }
+
+    Ok(())
+}
+
+/// Move everything that refers to `delete_name` over to the exercise `expand_name_id` and delete `delete_name`.
+/// Returns the number of moved exercise sets.
+pub async fn merge_exercise_names<C: ConnectionTrait>(
+    delete_name: exercise_name::Model,
+    expand_name_id: i32,
+    keep_alias: bool,
+    conn: &C,
+) -> Result<u64> {
+    let q = ExerciseSet::update_many()
+        .col_expr(exercise_set::Column::NameId, Expr::value(expand_name_id))
+        .filter(exercise_set::Column::NameId.eq(delete_name.id));
+
+    log::info!("{}", q.build(DbBackend::Postgres).to_string());
+
+    let res_update = q.exec(conn).await?;
+
+    // Routines, programs, aliases and default rests would otherwise be deleted together with the name.
+    RoutineExercise::update_many()
+        .col_expr(
+            routine_exercise::Column::NameId,
+            Expr::value(expand_name_id),
+        )
+        .filter(routine_exercise::Column::NameId.eq(delete_name.id))
+        .exec(conn)
+        .await?;
+    ProgramExercise::update_many()
+        .col_expr(
+            program_exercise::Column::NameId,
+            Expr::value(expand_name_id),
+        )
+        .filter(program_exercise::Column::NameId.eq(delete_name.id))
+        .exec(conn)
+        .await?;
+    ExerciseAlias::update_many()
+        .col_expr(exercise_alias::Column::NameId, Expr::value(expand_name_id))
+        .filter(exercise_alias::Column::NameId.eq(delete_name.id))
+        .exec(conn)
+        .await?;
+
+    // A user has at most one default rest per exercise, so the one already chosen for `expand_name_id` wins.
+    ExerciseRest::delete_many()
+        .filter(exercise_rest::Column::NameId.eq(delete_name.id))
+        .filter(
+            exercise_rest::Column::UserId.in_subquery(
+                Query::select()
+                    .column(exercise_rest::Column::UserId)
+                    .from(exercise_rest::Entity)
+                    .and_where(exercise_rest::Column::NameId.eq(expand_name_id))
+                    .to_owned(),
+            ),
+        )
+        .exec(conn)
+        .await?;
+    ExerciseRest::update_many()
+        .col_expr(exercise_rest::Column::NameId, Expr::value(expand_name_id))
+        .filter(exercise_rest::Column::NameId.eq(delete_name.id))
+        .exec(conn)
+        .await?;
+
+    let alias = delete_name.name.clone();
+    let _res_delete = delete_name.delete(conn).await?;
+
+    if keep_alias {
+        let new_alias = exercise_alias::ActiveModel {
+            alias: ActiveValue::Set(alias),
+            name_id: ActiveValue::Set(expand_name_id),
+            ..Default::default()
+        };
+        ExerciseAlias::insert(new_alias).exec(conn).await?;
+    }
+
+    Ok(res_update.rows_affected)
+}
+
 pub async fn get_exercise_sets(
     user_id: i32,
-    limit_opt: Option<u64>,
+    filter: ExerciseSetFilter,
     conn: &DatabaseConnection,
 ) -> Result<Vec<models::ExerciseSetQuery>> {
+    query_exercise_sets(user_id, filter, conn)
+        .await?
+        .into_iter()
+        .map(into_exercise_set_query)
+        .collect()
+}
+
+/// The deleted sets of a user, most recently deleted first.
+pub async fn get_trashed_exercise_sets(
+    user_id: i32,
+    conn: &DatabaseConnection,
+) -> Result<Vec<models::TrashedExerciseSet>> {
+    let filter = ExerciseSetFilter {
+        deleted: true,
+        ..Default::default()
+    };
+
+    query_exercise_sets(user_id, filter, conn)
+        .await?
+        .into_iter()
+        .map(|exsj| {
+            let deleted_at = exsj.deleted_at.ok_or(anyhow::anyhow!(
+                "Malformed input. Field `deleted_at` not present."
+            ))?;
+            Ok(models::TrashedExerciseSet {
+                exercise_set: into_exercise_set_query(exsj)?,
+                deleted_at,
+            })
+        })
+        .collect()
+}
+
+async fn query_exercise_sets(
+    user_id: i32,
+    filter: ExerciseSetFilter,
+    conn: &DatabaseConnection,
+) -> Result<Vec<models::ExerciseSetJoinQuery>> {
     /*
+    Sets that belong to a workout session are summed up per session, all others per day.
+    If warm-ups are excluded, `reps` is replaced by `CASE WHEN set_type = 1 THEN 0 ELSE reps END`.
+
     SELECT
         *,
         exercise_name.name AS name,
         exercise_name.kind AS kind,
-        SUM (reps) OVER (PARTITION BY name_id, date_trunc('day', created_at) ORDER BY created_at ASC, exercise_set.id ASC) AS reps_total
+        SUM (reps) OVER (PARTITION BY name_id, session_id, CASE WHEN session_id IS NULL THEN date_trunc('day', created_at) END ORDER BY created_at ASC, exercise_set.id ASC) AS reps_total
     FROM exercise_set
     JOIN exercise_name
     ON exercise_set.name_id = exercise_name.id
     ORDER BY created_at DESC, id DESC
     ;
      */
+    let reps = if filter.exclude_warmup {
+        format!(
+            "CASE WHEN set_type = {} THEN 0 ELSE reps END",
+            i32::from(models::SetType::Warmup)
+        )
+    } else {
+        "reps".to_string()
+    };
+
     let mut q = ExerciseSet::find()
         .filter(exercise_set::Column::UserId.eq(user_id))
         .column_as(exercise_name::Column::Name, "name")
         .column_as(exercise_name::Column::Kind, "kind")
-        .column_as(SimpleExpr::Custom("SUM (reps) OVER (PARTITION BY name_id, date_trunc('day', created_at) ORDER BY created_at ASC, exercise_set.id ASC)".to_string()), "reps_sum")
-        .order_by(exercise_set::Column::CreatedAt, Order::Desc)
-        .order_by(exercise_set::Column::Id, Order::Desc)
+        .column_as(SimpleExpr::Custom(format!("SUM ({}) OVER (PARTITION BY name_id, session_id, CASE WHEN session_id IS NULL THEN date_trunc('day', created_at) END ORDER BY created_at ASC, exercise_set.id ASC)", reps)), "reps_sum")
+        .column_as(SimpleExpr::Custom(BODYWEIGHT_AT_SET.to_string()), "bodyweight")
+        .column_as(SimpleExpr::Custom(REST_BEFORE_SET.to_string()), "rest")
         .join(
             JoinType::InnerJoin,
             exercise_set::Relation::ExerciseName.def(),
         );
 
-    if let Some(limit) = limit_opt {
+    // Trashed sets are listed by the time they were deleted.
+    q = if filter.deleted {
+        q.filter(exercise_set::Column::DeletedAt.is_not_null())
+            .order_by(exercise_set::Column::DeletedAt, Order::Desc)
+    } else {
+        q.filter(exercise_set::Column::DeletedAt.is_null())
+    };
+    q = q
+        .order_by(exercise_set::Column::CreatedAt, Order::Desc)
+        .order_by(exercise_set::Column::Id, Order::Desc);
+
+    if let Some(session_id) = filter.session_id {
+        q = q.filter(exercise_set::Column::SessionId.eq(session_id))
+    }
+
+    if let Some(name_id) = filter.name_id {
+        q = q.filter(exercise_set::Column::NameId.eq(name_id))
+    }
+
+    if let Some(kind) = filter.kind {
+        q = q.filter(exercise_name::Column::Kind.eq(kind))
+    }
+
+    let (from, to) = filter.utc_offset.utc_bounds(filter.from, filter.to);
+    if let Some(from) = from {
+        q = q.filter(exercise_set::Column::CreatedAt.gte(from))
+    }
+
+    if let Some(to) = to {
+        q = q.filter(exercise_set::Column::CreatedAt.lt(to))
+    }
+
+    if let Some((created_at, id)) = filter.before {
+        q = q.filter(
+            Condition::any()
+                .add(exercise_set::Column::CreatedAt.lt(created_at))
+                .add(
+                    Condition::all()
+                        .add(exercise_set::Column::CreatedAt.eq(created_at))
+                        .add(exercise_set::Column::Id.lt(id)),
+                ),
+        )
+    }
+
+    if let Some(limit) = filter.limit {
         q = q.limit(limit)
     }
 
@@ -48,36 +368,59 @@ pub async fn get_exercise_sets(
         .all(conn)
         .await?;
 
-    let res = res
-        .into_iter()
-        .map(|exsj| match exsj.kind {
-            models::ExerciseKind::Weighted => {
-                let exs: models::ExerciseSetWeightedQuery = exsj.try_into()?;
-                Ok(models::ExerciseSetQuery::Weighted(exs))
-            }
-            models::ExerciseKind::Bodyweight => {
-                let exs: models::ExerciseSetBodyweightQuery = exsj.try_into()?;
-                Ok(models::ExerciseSetQuery::Bodyweight(exs))
-            }
-        })
-        .collect::<Result<Vec<_>>>()?;
-
     Ok(res)
 }
 
+fn into_exercise_set_query(exsj: models::ExerciseSetJoinQuery) -> Result<models::ExerciseSetQuery> {
+    match exsj.kind {
+        models::ExerciseKind::Weighted => {
+            let exs: models::ExerciseSetWeightedQuery = exsj.try_into()?;
+            Ok(models::ExerciseSetQuery::Weighted(exs))
+        }
+        models::ExerciseKind::Bodyweight => {
+            let exs: models::ExerciseSetBodyweightQuery = exsj.try_into()?;
+            Ok(models::ExerciseSetQuery::Bodyweight(exs))
+        }
+        models::ExerciseKind::Timed => {
+            let exs: models::ExerciseSetTimedQuery = exsj.try_into()?;
+            Ok(models::ExerciseSetQuery::Timed(exs))
+        }
+        models::ExerciseKind::Distance => {
+            let exs: models::ExerciseSetDistanceQuery = exsj.try_into()?;
+            Ok(models::ExerciseSetQuery::Distance(exs))
+        }
+        models::ExerciseKind::WeightedBodyweight => {
+            let exs: models::ExerciseSetWeightedBodyweightQuery = exsj.try_into()?;
+            Ok(models::ExerciseSetQuery::WeightedBodyweight(exs))
+        }
+    }
+}
+
 pub async fn get_exercise_graphs(
     user_id: i32,
+    exclude_warmup: bool,
+    formula: models::E1rmFormula,
     conn: &DatabaseConnection,
 ) -> Result<Vec<models::ExerciseGraphQuery>> {
     let q = ExerciseSet::find()
         .filter(exercise_set::Column::UserId.eq(user_id))
-        .filter(exercise_name::Column::Kind.eq(models::ExerciseKind::Weighted))
+        .filter(exercise_set::Column::DeletedAt.is_null())
+        .filter(exercise_name::Column::Kind.is_in([
+            models::ExerciseKind::Weighted,
+            models::ExerciseKind::WeightedBodyweight,
+        ]))
         .column_as(exercise_name::Column::Name, "name")
+        .column_as(exercise_name::Column::Kind, "kind")
+        .column_as(
+            SimpleExpr::Custom(BODYWEIGHT_AT_SET.to_string()),
+            "bodyweight",
+        )
         .order_by(exercise_set::Column::NameId, Order::Asc)
         .join(
             JoinType::InnerJoin,
             exercise_set::Relation::ExerciseName.def(),
         );
+    let q = filter_warmup(q, exclude_warmup);
 
     log::info!("{}", q.build(DbBackend::Postgres).to_string());
 
@@ -89,6 +432,15 @@ pub async fn get_exercise_graphs(
     let mut data_per_name: HashMap<String, HashMap<NaiveDate, Vec<(f64, i32)>>> = HashMap::new();
 
     for jq in data {
+        // For weighted bodyweight exercises we graph the effective load, which needs a recorded bodyweight.
+        let weight = match jq.kind {
+            models::ExerciseKind::WeightedBodyweight => match jq.bodyweight {
+                Some(bodyweight) => models::effective_weight(bodyweight, jq.weight),
+                None => continue,
+            },
+            _ => jq.weight,
+        };
+
         // If we have not added data for this exercise, insert a new HashMap for this exercise.
         if !data_per_name.contains_key(&jq.name) {
             data_per_name.insert(jq.name.clone(), HashMap::new());
@@ -102,7 +454,7 @@ pub async fn get_exercise_graphs(
         let data_weights = data_per_date.get_mut(&jq.created_at.date()).unwrap();
 
         // If we have added both before, extend the existing Vector.
-        data_weights.push((jq.weight, jq.reps));
+        data_weights.push((weight, jq.reps));
     }
 
     let res = data_per_name
@@ -112,7 +464,14 @@ pub async fn get_exercise_graphs(
             let per_date = per_date_map
                 .into_iter()
                 .sorted_by(|(date1, _), (date2, _)| date1.cmp(&date2))
-                .map(|(date, weights)| models::ExerciseGraphQueryPerDate { date, weights })
+                .map(|(date, weights)| {
+                    let e1rm = formula.best(&weights);
+                    models::ExerciseGraphQueryPerDate {
+                        date,
+                        weights,
+                        e1rm,
+                    }
+                })
                 .collect();
             models::ExerciseGraphQuery {
                 name: name.to_string(),
@@ -122,3 +481,56 @@ pub async fn get_exercise_graphs(
         .collect();
     Ok(res)
 }
+
+pub async fn get_exercise_pace_graphs(
+    user_id: i32,
+    exclude_warmup: bool,
+    conn: &DatabaseConnection,
+) -> Result<Vec<models::ExercisePaceGraphQuery>> {
+    let q = ExerciseSet::find()
+        .filter(exercise_set::Column::UserId.eq(user_id))
+        .filter(exercise_set::Column::DeletedAt.is_null())
+        .filter(exercise_name::Column::Kind.eq(models::ExerciseKind::Distance))
+        .column_as(exercise_name::Column::Name, "name")
+        .order_by(exercise_set::Column::NameId, Order::Asc)
+        .join(
+            JoinType::InnerJoin,
+            exercise_set::Relation::ExerciseName.def(),
+        );
+    let q = filter_warmup(q, exclude_warmup);
+
+    log::info!("{}", q.build(DbBackend::Postgres).to_string());
+
+    let data = q
+        .into_model::<models::ExercisePaceGraphJoinQuery>()
+        .all(conn)
+        .await?;
+
+    let mut data_per_name: HashMap<String, HashMap<NaiveDate, Vec<(f64, f64)>>> = HashMap::new();
+
+    for jq in data {
+        // Sets without a distance have no pace, so they cannot be drawn.
+        if let Some(pace) = models::pace(jq.distance, jq.time) {
+            data_per_name
+                .entry(jq.name)
+                .or_insert(HashMap::new())
+                .entry(jq.created_at.date())
+                .or_insert(Vec::new())
+                .push((pace, jq.distance));
+        }
+    }
+
+    let res = data_per_name
+        .into_iter()
+        .sorted_by(|(name1, _), (name2, _)| name1.cmp(&name2))
+        .map(|(name, per_date_map)| {
+            let per_date = per_date_map
+                .into_iter()
+                .sorted_by(|(date1, _), (date2, _)| date1.cmp(&date2))
+                .map(|(date, paces)| models::ExercisePaceGraphQueryPerDate { date, paces })
+                .collect();
+            models::ExercisePaceGraphQuery { name, per_date }
+        })
+        .collect();
+    Ok(res)
+}
@@ -0,0 +1,74 @@
+use sea_orm::*;
+
+use gt_core::entities::{prelude::*, *};
+use gt_core::models;
+
+/// The exercise sets of a user in the range together with their exercise, oldest first.
+/// Sets in the trash are not exported.
+pub fn exercise_sets(
+    user_id: i32,
+    range: &models::ExportRange,
+    utc_offset: models::UtcOffsetParam,
+) -> Selector<SelectModel<models::ExerciseSetExportQuery>> {
+    let mut q = ExerciseSet::find()
+        .select_only()
+        .column(exercise_set::Column::Id)
+        .column_as(exercise_name::Column::Name, "name")
+        .column_as(exercise_name::Column::Kind, "kind")
+        .column(exercise_set::Column::Reps)
+        .column(exercise_set::Column::Weight)
+        .column(exercise_set::Column::Time)
+        .column(exercise_set::Column::Distance)
+        .column(exercise_set::Column::Rpe)
+        .column(exercise_set::Column::Rir)
+        .column(exercise_set::Column::SetType)
+        .column(exercise_set::Column::SessionId)
+        .column(exercise_set::Column::GroupId)
+        .column(exercise_set::Column::Note)
+        .column(exercise_set::Column::CreatedAt)
+        .filter(exercise_set::Column::UserId.eq(user_id))
+        .filter(exercise_set::Column::DeletedAt.is_null())
+        .join(
+            JoinType::InnerJoin,
+            exercise_set::Relation::ExerciseName.def(),
+        )
+        .order_by(exercise_set::Column::CreatedAt, Order::Asc)
+        .order_by(exercise_set::Column::Id, Order::Asc);
+
+    let (from, to) = utc_offset.utc_bounds(range.from, range.to);
+    if let Some(from) = from {
+        q = q.filter(exercise_set::Column::CreatedAt.gte(from))
+    }
+    if let Some(to) = to {
+        q = q.filter(exercise_set::Column::CreatedAt.lt(to))
+    }
+
+    log::info!("{}", q.build(DbBackend::Postgres).to_string());
+
+    q.into_model::<models::ExerciseSetExportQuery>()
+}
+
+/// The body measurements of a user in the range, oldest first. Measurements in the trash are not exported.
+pub fn body_measurements(
+    user_id: i32,
+    range: &models::ExportRange,
+    utc_offset: models::UtcOffsetParam,
+) -> Select<UserInfoTs> {
+    let mut q = UserInfoTs::find()
+        .filter(user_info_ts::Column::UserId.eq(user_id))
+        .filter(user_info_ts::Column::DeletedAt.is_null())
+        .order_by(user_info_ts::Column::CreatedAt, Order::Asc)
+        .order_by(user_info_ts::Column::Id, Order::Asc);
+
+    let (from, to) = utc_offset.utc_bounds(range.from, range.to);
+    if let Some(from) = from {
+        q = q.filter(user_info_ts::Column::CreatedAt.gte(from))
+    }
+    if let Some(to) = to {
+        q = q.filter(user_info_ts::Column::CreatedAt.lt(to))
+    }
+
+    log::info!("{}", q.build(DbBackend::Postgres).to_string());
+
+    q
+}
@@ -1,4 +1,10 @@
 pub mod exercise;
+pub mod export;
 pub mod populate;
 pub mod pr;
+pub mod program;
+pub mod routine;
+pub mod session;
+pub mod stats;
+pub mod trash;
 pub mod user;
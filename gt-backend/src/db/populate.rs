@@ -1,6 +1,6 @@
 use sea_orm::*;
 
-use super::user;
+use super::{exercise, user};
 use crate::{AppState, Result};
 use gt_core::entities::{prelude::*, *};
 use gt_core::models;
@@ -18,35 +18,42 @@ pub async fn populate(data: PopulateData, state: &AppState) -> Result<()> {
     Ok(())
 }
 
+/// The default exercises together with their Japanese names, which are added as aliases.
 async fn add_exercise_names(conn: &DatabaseConnection) -> Result<()> {
     let names = vec![
-        "Bench Press",
-        "Deadlift",
-        "Squat",
-        "Leg Extension",
-        "Cable Rows",
-    ];
-    let names_japanese = vec![
-        "ベンチプレス",
-        "デッドリフト",
-        "スクワット",
-        "レッグエクステンション",
-        "シーテッドケーブルロー",
+        ("Bench Press", "ベンチプレス"),
+        ("Deadlift", "デッドリフト"),
+        ("Squat", "スクワット"),
+        ("Leg Extension", "レッグエクステンション"),
+        ("Cable Rows", "シーテッドケーブルロー"),
     ];
 
-    for name in names.into_iter().chain(names_japanese.into_iter()) {
+    for (name, alias) in names {
+        let name_id =
+            exercise::get_or_create_exercise_name(name, models::ExerciseKind::Weighted, None, conn)
+                .await?;
+
+        // Earlier versions added the Japanese names as separate exercises, so we merge them into the English one.
         let res = ExerciseName::find()
-            .filter(exercise_name::Column::Name.eq(name))
+            .filter(exercise_name::Column::Name.eq(alias))
+            .filter(exercise_name::Column::OwnerId.is_null())
             .one(conn)
             .await?;
-        if res.is_none() {
-            let exercise_name = exercise_name::ActiveModel {
-                name: ActiveValue::Set(name.to_owned()),
-                kind: ActiveValue::Set(models::ExerciseKind::Weighted.into()),
+        if let Some(alias_name) = res {
+            let txn = conn.begin().await?;
+            exercise::merge_exercise_names(alias_name, name_id, true, &txn).await?;
+            txn.commit().await?;
+        } else if exercise::find_exercise_name(alias, None, conn)
+            .await?
+            .is_none()
+        {
+            let new_alias = exercise_alias::ActiveModel {
+                alias: ActiveValue::Set(alias.to_owned()),
+                name_id: ActiveValue::Set(name_id),
                 ..Default::default()
             };
 
-            ExerciseName::insert(exercise_name).exec(conn).await?;
+            ExerciseAlias::insert(new_alias).exec(conn).await?;
         }
     }
 
@@ -1,24 +1,36 @@
+use chrono::NaiveDateTime;
 use itertools::Itertools;
+use migration::SimpleExpr;
 use ordered_float::OrderedFloat;
 use sea_orm::*;
 use std::collections::HashMap;
 
+use crate::db::exercise::{filter_warmup, BODYWEIGHT_AT_SET};
 use crate::Result;
 use gt_core::entities::{prelude::*, *};
 use gt_core::models;
 
+/// Distances in meters for which the fastest time is tracked: 1k, 5k, 10k, half and full marathon.
+const PR_DISTANCES: [f64; 5] = [1000.0, 5000.0, 10000.0, 21097.5, 42195.0];
+
+/// The rep maxes from 1 to `REP_MAX_REPS` reps of each weighted exercise, so that rep PRs at lighter
+/// weights are not hidden by the heaviest sets.
 pub async fn get_weighted_exercise_set_prs_for_user(
     user_id: i32,
+    exclude_warmup: bool,
+    formula: models::E1rmFormula,
     conn: &DatabaseConnection,
 ) -> Result<Vec<models::PRWeightedQuery>> {
     let q = ExerciseSet::find()
         .filter(exercise_set::Column::UserId.eq(user_id))
+        .filter(exercise_set::Column::DeletedAt.is_null())
         .column_as(exercise_name::Column::Name, "name")
         .filter(exercise_name::Column::Kind.eq(models::ExerciseKind::Weighted))
         .join(
             JoinType::InnerJoin,
             exercise_set::Relation::ExerciseName.def(),
         );
+    let q = filter_warmup(q, exclude_warmup);
 
     log::info!("{}", q.build(DbBackend::Postgres).to_string());
 
@@ -27,22 +39,51 @@ pub async fn get_weighted_exercise_set_prs_for_user(
         .all(conn)
         .await?;
 
-    let mut data_per_exercise: HashMap<String, Vec<(f64, i32)>> = HashMap::with_capacity(res.len());
+    let mut data_per_exercise: HashMap<String, Vec<models::ExercisePrWeightedQuery>> =
+        HashMap::with_capacity(res.len());
     for exs in res {
-        let prs = data_per_exercise.entry(exs.name).or_insert(Vec::new());
-        prs.push((exs.weight, exs.reps));
+        let prs = data_per_exercise
+            .entry(exs.name.clone())
+            .or_insert(Vec::new());
+        prs.push(exs);
     }
 
     let mut prs = Vec::with_capacity(data_per_exercise.len());
-    for (name, mut data) in data_per_exercise.into_iter().sorted_by_key(|x| x.0.clone()) {
-        data.sort_by(|a, b| b.0.total_cmp(&a.0).then(b.1.cmp(&a.1)));
-        let pr = data
+    for (name, data) in data_per_exercise.into_iter().sorted_by_key(|x| x.0.clone()) {
+        let e1rm = formula.best(
+            &data
+                .iter()
+                .map(|exs| (exs.weight, exs.reps))
+                .collect::<Vec<_>>(),
+        );
+
+        // For each rep count keep the heaviest set, and of those the one that was lifted first.
+        let rep_maxes = data
             .into_iter()
-            .unique_by(|(weight, reps)| (OrderedFloat(*weight), *reps))
-            .take(3)
+            .filter(|exs| exs.reps >= 1 && exs.reps <= models::REP_MAX_REPS)
+            .into_group_map_by(|exs| exs.reps)
+            .into_iter()
+            .filter_map(|(reps, sets)| {
+                sets.into_iter()
+                    .min_by(|a, b| {
+                        b.weight
+                            .total_cmp(&a.weight)
+                            .then(a.created_at.cmp(&b.created_at))
+                    })
+                    .map(|exs| models::RepMax {
+                        reps,
+                        weight: exs.weight,
+                        created_at: exs.created_at,
+                    })
+            })
+            .sorted_by_key(|rep_max| rep_max.reps)
             .collect();
 
-        prs.push(models::PRWeightedQuery { name, pr });
+        prs.push(models::PRWeightedQuery {
+            name,
+            rep_maxes,
+            e1rm,
+        });
     }
 
     Ok(prs)
@@ -50,16 +91,19 @@ pub async fn get_weighted_exercise_set_prs_for_user(
 
 pub async fn get_bodyweight_exercise_set_prs_for_user(
     user_id: i32,
+    exclude_warmup: bool,
     conn: &DatabaseConnection,
 ) -> Result<Vec<models::PRBodyweightQuery>> {
     let q = ExerciseSet::find()
         .filter(exercise_set::Column::UserId.eq(user_id))
+        .filter(exercise_set::Column::DeletedAt.is_null())
         .column_as(exercise_name::Column::Name, "name")
         .filter(exercise_name::Column::Kind.eq(models::ExerciseKind::Bodyweight))
         .join(
             JoinType::InnerJoin,
             exercise_set::Relation::ExerciseName.def(),
         );
+    let q = filter_warmup(q, exclude_warmup);
 
     log::info!("{}", q.build(DbBackend::Postgres).to_string());
 
@@ -84,3 +128,328 @@ pub async fn get_bodyweight_exercise_set_prs_for_user(
 
     Ok(prs)
 }
+
+pub async fn get_timed_exercise_set_prs_for_user(
+    user_id: i32,
+    exclude_warmup: bool,
+    conn: &DatabaseConnection,
+) -> Result<Vec<models::PRTimedQuery>> {
+    let q = ExerciseSet::find()
+        .filter(exercise_set::Column::UserId.eq(user_id))
+        .filter(exercise_set::Column::DeletedAt.is_null())
+        .column_as(exercise_name::Column::Name, "name")
+        .filter(exercise_name::Column::Kind.eq(models::ExerciseKind::Timed))
+        .join(
+            JoinType::InnerJoin,
+            exercise_set::Relation::ExerciseName.def(),
+        );
+    let q = filter_warmup(q, exclude_warmup);
+
+    log::info!("{}", q.build(DbBackend::Postgres).to_string());
+
+    let res = q
+        .into_model::<models::ExercisePrTimedQuery>()
+        .all(conn)
+        .await?;
+
+    let mut data_per_exercise: HashMap<String, Vec<i32>> = HashMap::with_capacity(res.len());
+    for exs in res {
+        let prs = data_per_exercise.entry(exs.name).or_insert(Vec::new());
+        prs.push(exs.time);
+    }
+
+    // The longest holds are the PRs.
+    let mut prs = Vec::with_capacity(data_per_exercise.len());
+    for (name, mut data) in data_per_exercise.into_iter().sorted_by_key(|x| x.0.clone()) {
+        data.sort_by(|a, b| b.cmp(a));
+        let pr = data.into_iter().unique_by(|time| *time).take(3).collect();
+
+        prs.push(models::PRTimedQuery { name, pr });
+    }
+
+    Ok(prs)
+}
+
+pub async fn get_distance_exercise_set_prs_for_user(
+    user_id: i32,
+    exclude_warmup: bool,
+    conn: &DatabaseConnection,
+) -> Result<Vec<models::PRDistanceQuery>> {
+    let q = ExerciseSet::find()
+        .filter(exercise_set::Column::UserId.eq(user_id))
+        .filter(exercise_set::Column::DeletedAt.is_null())
+        .column_as(exercise_name::Column::Name, "name")
+        .filter(exercise_name::Column::Kind.eq(models::ExerciseKind::Distance))
+        .join(
+            JoinType::InnerJoin,
+            exercise_set::Relation::ExerciseName.def(),
+        );
+    let q = filter_warmup(q, exclude_warmup);
+
+    log::info!("{}", q.build(DbBackend::Postgres).to_string());
+
+    let res = q
+        .into_model::<models::ExercisePrDistanceQuery>()
+        .all(conn)
+        .await?;
+
+    let mut data_per_exercise: HashMap<String, Vec<(f64, i32)>> = HashMap::with_capacity(res.len());
+    for exs in res {
+        let prs = data_per_exercise.entry(exs.name).or_insert(Vec::new());
+        prs.push((exs.distance, exs.time));
+    }
+
+    let mut prs = Vec::with_capacity(data_per_exercise.len());
+    for (name, data) in data_per_exercise.into_iter().sorted_by_key(|x| x.0.clone()) {
+        let longest = data
+            .iter()
+            .map(|(distance, _)| OrderedFloat(*distance))
+            .max()
+            .unwrap_or(OrderedFloat(0.0))
+            .0;
+
+        // A set that is longer than a standard distance also counts for it, using the average pace of the set.
+        // Such a time was never actually run, so it is marked as estimated and loses against an equal actual time.
+        let fastest = PR_DISTANCES
+            .iter()
+            .filter_map(|&target| {
+                data.iter()
+                    .filter(|(distance, _)| *distance >= target)
+                    .map(|(distance, time)| {
+                        (
+                            (*time as f64 * target / distance).round() as i32,
+                            *distance > target,
+                        )
+                    })
+                    .min()
+                    .map(|(time, estimated)| (target, time, estimated))
+            })
+            .collect();
+
+        prs.push(models::PRDistanceQuery {
+            name,
+            longest,
+            fastest,
+        });
+    }
+
+    Ok(prs)
+}
+
+pub async fn get_weighted_bodyweight_exercise_set_prs_for_user(
+    user_id: i32,
+    exclude_warmup: bool,
+    formula: models::E1rmFormula,
+    conn: &DatabaseConnection,
+) -> Result<Vec<models::PRWeightedBodyweightQuery>> {
+    let q = ExerciseSet::find()
+        .filter(exercise_set::Column::UserId.eq(user_id))
+        .filter(exercise_set::Column::DeletedAt.is_null())
+        .column_as(exercise_name::Column::Name, "name")
+        .column_as(
+            SimpleExpr::Custom(BODYWEIGHT_AT_SET.to_string()),
+            "bodyweight",
+        )
+        .filter(exercise_name::Column::Kind.eq(models::ExerciseKind::WeightedBodyweight))
+        .join(
+            JoinType::InnerJoin,
+            exercise_set::Relation::ExerciseName.def(),
+        );
+    let q = filter_warmup(q, exclude_warmup);
+
+    log::info!("{}", q.build(DbBackend::Postgres).to_string());
+
+    let res = q
+        .into_model::<models::ExercisePrWeightedBodyweightQuery>()
+        .all(conn)
+        .await?;
+
+    // Sets are compared by effective load, so sets before the user ever recorded their bodyweight are skipped.
+    let mut data_per_exercise: HashMap<String, Vec<(f64, f64, i32)>> =
+        HashMap::with_capacity(res.len());
+    for exs in res {
+        if let Some(bodyweight) = exs.bodyweight {
+            let prs = data_per_exercise.entry(exs.name).or_insert(Vec::new());
+            prs.push((
+                models::effective_weight(bodyweight, exs.weight),
+                exs.weight,
+                exs.reps,
+            ));
+        }
+    }
+
+    let mut prs = Vec::with_capacity(data_per_exercise.len());
+    for (name, mut data) in data_per_exercise.into_iter().sorted_by_key(|x| x.0.clone()) {
+        let e1rm = formula.best(
+            &data
+                .iter()
+                .map(|(load, _, reps)| (*load, *reps))
+                .collect::<Vec<_>>(),
+        );
+        data.sort_by(|a, b| b.0.total_cmp(&a.0).then(b.2.cmp(&a.2)));
+        let pr = data
+            .into_iter()
+            .unique_by(|(load, _, reps)| (OrderedFloat(*load), *reps))
+            .take(3)
+            .collect();
+
+        prs.push(models::PRWeightedBodyweightQuery { name, pr, e1rm });
+    }
+
+    Ok(prs)
+}
+
+/// The bodyweight of the user at `created_at`, which is chosen like `BODYWEIGHT_AT_SET` does for logged sets.
+async fn get_bodyweight_at<C>(
+    user_id: i32,
+    created_at: NaiveDateTime,
+    conn: &C,
+) -> Result<Option<f64>>
+where
+    C: ConnectionTrait,
+{
+    let measurements = UserInfoTs::find()
+        .filter(user_info_ts::Column::UserId.eq(user_id))
+        .filter(user_info_ts::Column::Weight.is_not_null())
+        .filter(user_info_ts::Column::DeletedAt.is_null());
+
+    let latest = measurements
+        .clone()
+        .filter(user_info_ts::Column::CreatedAt.lte(created_at))
+        .order_by(user_info_ts::Column::CreatedAt, Order::Desc)
+        .one(conn)
+        .await?;
+    let info_ts = match latest {
+        Some(info_ts) => Some(info_ts),
+        None => {
+            measurements
+                .order_by(user_info_ts::Column::CreatedAt, Order::Asc)
+                .one(conn)
+                .await?
+        }
+    };
+
+    Ok(info_ts.and_then(|info_ts| info_ts.weight))
+}
+
+/// Compare a set that is about to be logged with the sets of the exercise up to its time, so a backdated set is not compared to later ones.
+/// Sets are only logged to the minute, so sets of the same minute count as earlier.
+/// Warm-up sets neither break records nor count as earlier sets, and the first set of an exercise is not a record.
+pub async fn get_new_records<C>(
+    user_id: i32,
+    name_id: i32,
+    exs: &models::ExerciseSet,
+    formula: models::E1rmFormula,
+    conn: &C,
+) -> Result<models::NewRecords>
+where
+    C: ConnectionTrait,
+{
+    if exs.set_type() == models::SetType::Warmup {
+        return Ok(Default::default());
+    }
+
+    let created_at = exs.created_at();
+    let (weight, reps) = match exs {
+        models::ExerciseSet::Weighted(exs) => (Some(exs.weight), exs.reps),
+        models::ExerciseSet::Bodyweight(exs) => (None, exs.reps),
+        // Like their PRs, sets with added load or assistance are compared by the effective load.
+        models::ExerciseSet::WeightedBodyweight(exs) => {
+            match get_bodyweight_at(user_id, created_at, conn).await? {
+                Some(bodyweight) => (
+                    Some(models::effective_weight(bodyweight, exs.weight)),
+                    exs.reps,
+                ),
+                None => return Ok(Default::default()),
+            }
+        }
+        _ => return Ok(Default::default()),
+    };
+
+    let earlier = ExerciseSet::find()
+        .filter(exercise_set::Column::UserId.eq(user_id))
+        .filter(exercise_set::Column::DeletedAt.is_null())
+        .filter(exercise_set::Column::NameId.eq(name_id))
+        .filter(exercise_set::Column::SetType.ne(models::SetType::Warmup))
+        .filter(exercise_set::Column::CreatedAt.lte(created_at));
+
+    let weight = match weight {
+        Some(weight) => weight,
+        None => {
+            let max_reps = earlier
+                .all(conn)
+                .await?
+                .iter()
+                .filter_map(|exs| exs.reps)
+                .max();
+            return Ok(models::NewRecords {
+                reps: max_reps.map_or(false, |max_reps| reps > max_reps),
+                ..Default::default()
+            });
+        }
+    };
+
+    let earlier: Vec<(f64, i32)> = if let models::ExerciseSet::WeightedBodyweight(_) = exs {
+        earlier
+            .column_as(exercise_name::Column::Name, "name")
+            .column_as(
+                SimpleExpr::Custom(BODYWEIGHT_AT_SET.to_string()),
+                "bodyweight",
+            )
+            .join(
+                JoinType::InnerJoin,
+                exercise_set::Relation::ExerciseName.def(),
+            )
+            .into_model::<models::ExercisePrWeightedBodyweightQuery>()
+            .all(conn)
+            .await?
+            .into_iter()
+            .filter_map(|exs| {
+                Some((
+                    models::effective_weight(exs.bodyweight?, exs.weight),
+                    exs.reps,
+                ))
+            })
+            .collect()
+    } else {
+        earlier
+            .all(conn)
+            .await?
+            .into_iter()
+            .filter_map(|exs| Some((exs.weight?, exs.reps?)))
+            .collect()
+    };
+    if earlier.is_empty() {
+        return Ok(Default::default());
+    }
+
+    let max_weight = earlier
+        .iter()
+        .map(|(weight, _)| OrderedFloat(*weight))
+        .max()
+        .unwrap_or(OrderedFloat(0.0))
+        .0;
+    let max_volume = earlier
+        .iter()
+        .map(|(weight, reps)| OrderedFloat(weight * *reps as f64))
+        .max()
+        .unwrap_or(OrderedFloat(0.0))
+        .0;
+    // Only sets at least as heavy count for a rep PR, otherwise every new weight PR would also be a rep PR.
+    let max_reps = earlier
+        .iter()
+        .filter(|(earlier_weight, _)| *earlier_weight >= weight)
+        .map(|(_, reps)| *reps)
+        .max();
+    let e1rm = match (formula.estimate(weight, reps), formula.best(&earlier)) {
+        (Some(e1rm), Some(max_e1rm)) => e1rm > max_e1rm,
+        _ => false,
+    };
+
+    Ok(models::NewRecords {
+        weight: weight > max_weight,
+        reps: max_reps.map_or(false, |max_reps| reps > max_reps),
+        e1rm,
+        volume: weight * reps as f64 > max_volume,
+    })
+}
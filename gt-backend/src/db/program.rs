@@ -0,0 +1,220 @@
+use chrono::Utc;
+use http::StatusCode;
+use itertools::Itertools;
+use sea_orm::*;
+use std::collections::HashMap;
+
+use crate::progression::{self, TrainingDay};
+use crate::{AppError, Result};
+use gt_core::entities::{prelude::*, *};
+use gt_core::models;
+
+fn select_program_exercises() -> Select<ProgramExercise> {
+    ProgramExercise::find()
+        .column_as(exercise_name::Column::Name, "name")
+        .join(
+            JoinType::InnerJoin,
+            program_exercise::Relation::ExerciseName.def(),
+        )
+        .order_by(program_exercise::Column::Position, Order::Asc)
+}
+
+pub async fn get_programs(
+    user_id: i32,
+    conn: &DatabaseConnection,
+) -> Result<Vec<models::ProgramQuery>> {
+    let programs = Program::find()
+        .filter(program::Column::UserId.eq(user_id))
+        .order_by(program::Column::Name, Order::Asc)
+        .all(conn)
+        .await?;
+
+    let exercises = select_program_exercises()
+        .filter(
+            program_exercise::Column::ProgramId.is_in(programs.iter().map(|program| program.id)),
+        )
+        .into_model::<models::ProgramExerciseQuery>()
+        .all(conn)
+        .await?;
+
+    let mut exercises_per_program: HashMap<i32, Vec<models::ProgramExerciseQuery>> = exercises
+        .into_iter()
+        .into_group_map_by(|exercise| exercise.program_id);
+
+    let res = programs
+        .into_iter()
+        .map(|program| models::ProgramQuery {
+            exercises: exercises_per_program
+                .remove(&program.id)
+                .unwrap_or_default(),
+            id: program.id,
+            name: program.name,
+            start_date: program.start_date,
+        })
+        .collect();
+
+    Ok(res)
+}
+
+pub async fn get_program(
+    user_id: i32,
+    program_id: i32,
+    conn: &DatabaseConnection,
+) -> Result<Option<models::ProgramQuery>> {
+    let program = Program::find_by_id(program_id)
+        .filter(program::Column::UserId.eq(user_id))
+        .one(conn)
+        .await?;
+
+    let program = match program {
+        Some(program) => program,
+        None => return Ok(None),
+    };
+
+    let exercises = select_program_exercises()
+        .filter(program_exercise::Column::ProgramId.eq(program.id))
+        .into_model::<models::ProgramExerciseQuery>()
+        .all(conn)
+        .await?;
+
+    Ok(Some(models::ProgramQuery {
+        id: program.id,
+        name: program.name,
+        start_date: program.start_date,
+        exercises,
+    }))
+}
+
+/// Replace all exercises of a program, keeping the order in which they are given.
+/// Programs prescribe weights, so they only accept weighted exercises and new exercises are created as such.
+pub async fn set_program_exercises<C: ConnectionTrait>(
+    user_id: i32,
+    program_id: i32,
+    exercises: Vec<models::ProgramExercise>,
+    conn: &C,
+) -> Result<()> {
+    ProgramExercise::delete_many()
+        .filter(program_exercise::Column::ProgramId.eq(program_id))
+        .exec(conn)
+        .await?;
+
+    for (position, exercise) in exercises.into_iter().enumerate() {
+        let existing =
+            crate::db::exercise::find_exercise_name(&exercise.name, Some(user_id), conn).await?;
+        if let Some(existing) = existing {
+            if existing.kind != i32::from(models::ExerciseKind::Weighted) {
+                return Err(AppError::StatusCode(
+                    StatusCode::BAD_REQUEST,
+                    format!(
+                        "Programs only support weighted exercises, but \"{}\" is not one.",
+                        exercise.name
+                    ),
+                ));
+            }
+        }
+
+        let name_id = crate::db::exercise::get_or_create_exercise_name(
+            &exercise.name,
+            models::ExerciseKind::Weighted,
+            Some(user_id),
+            conn,
+        )
+        .await?;
+
+        let new_exercise = program_exercise::ActiveModel {
+            program_id: ActiveValue::Set(program_id),
+            name_id: ActiveValue::Set(name_id),
+            position: ActiveValue::Set(position as i32),
+            rule: ActiveValue::Set(exercise.rule.into()),
+            sets: ActiveValue::Set(exercise.sets),
+            min_reps: ActiveValue::Set(exercise.min_reps),
+            max_reps: ActiveValue::Set(exercise.max_reps),
+            start_weight: ActiveValue::Set(exercise.start_weight),
+            increment: ActiveValue::Set(exercise.increment),
+            ..Default::default()
+        };
+        ProgramExercise::insert(new_exercise).exec(conn).await?;
+    }
+
+    Ok(())
+}
+
+/// The working sets of the program's exercises, grouped by exercise name and training day.
+/// Sets logged today are left out so that the prescription does not change in the middle of a workout.
+/// Training days are days of the user.
+async fn get_training_days(
+    user_id: i32,
+    program: &models::ProgramQuery,
+    utc_offset: models::UtcOffsetParam,
+    conn: &DatabaseConnection,
+) -> Result<HashMap<i32, Vec<TrainingDay>>> {
+    let today = utc_offset.local_date(Utc::now().naive_utc());
+
+    let sets = ExerciseSet::find()
+        .filter(exercise_set::Column::UserId.eq(user_id))
+        .filter(exercise_set::Column::DeletedAt.is_null())
+        .filter(
+            exercise_set::Column::NameId
+                .is_in(program.exercises.iter().map(|exercise| exercise.name_id)),
+        )
+        .filter(exercise_set::Column::CreatedAt.gte(program.start_date))
+        .filter(exercise_set::Column::SetType.ne(i32::from(models::SetType::Warmup)))
+        .order_by(exercise_set::Column::CreatedAt, Order::Asc)
+        .all(conn)
+        .await?;
+
+    let sets_per_name = sets
+        .into_iter()
+        .filter(|set| utc_offset.local_date(set.created_at) < today)
+        .into_group_map_by(|set| set.name_id);
+
+    let days_per_name = sets_per_name
+        .into_iter()
+        .map(|(name_id, sets)| {
+            let days = sets
+                .into_iter()
+                .group_by(|set| utc_offset.local_date(set.created_at))
+                .into_iter()
+                .map(|(_, day)| {
+                    day.filter_map(|set| Some((set.weight?, set.reps?)))
+                        .collect()
+                })
+                .collect();
+            (name_id, days)
+        })
+        .collect();
+
+    Ok(days_per_name)
+}
+
+pub async fn get_prescribed_workout(
+    user_id: i32,
+    program: models::ProgramQuery,
+    unit: models::WeightUnit,
+    utc_offset: models::UtcOffsetParam,
+    conn: &DatabaseConnection,
+) -> Result<models::PrescribedWorkout> {
+    let days_per_name = get_training_days(user_id, &program, utc_offset, conn).await?;
+
+    let exercises = program
+        .exercises
+        .into_iter()
+        .map(|exercise| {
+            let days = days_per_name
+                .get(&exercise.name_id)
+                .map(Vec::as_slice)
+                .unwrap_or_default();
+            models::PrescribedExercise {
+                sets: progression::prescribe(&exercise, days, unit),
+                name: exercise.name,
+                rule: exercise.rule,
+            }
+        })
+        .collect();
+
+    Ok(models::PrescribedWorkout {
+        program_id: program.id,
+        name: program.name,
+        exercises,
+    })
+}
@@ -0,0 +1,135 @@
+use itertools::Itertools;
+use migration::SimpleExpr;
+use sea_orm::*;
+use std::collections::HashMap;
+
+use crate::Result;
+use gt_core::entities::{prelude::*, *};
+use gt_core::models;
+
+/// The value of `column` in the last set the user logged for the exercise of a routine.
+fn last_value(column: &str, user_id: i32) -> SimpleExpr {
+    SimpleExpr::Custom(format!(
+        "(SELECT exercise_set.{} FROM exercise_set \
+            WHERE exercise_set.user_id = {} AND exercise_set.name_id = routine_exercise.name_id \
+            AND exercise_set.deleted_at IS NULL \
+            ORDER BY exercise_set.created_at DESC, exercise_set.id DESC LIMIT 1)",
+        column, user_id
+    ))
+}
+
+fn select_routine_exercises(user_id: i32) -> Select<RoutineExercise> {
+    RoutineExercise::find()
+        .column_as(exercise_name::Column::Name, "name")
+        .column_as(exercise_name::Column::Kind, "kind")
+        .column_as(last_value("weight", user_id), "last_weight")
+        .column_as(last_value("reps", user_id), "last_reps")
+        .join(
+            JoinType::InnerJoin,
+            routine_exercise::Relation::ExerciseName.def(),
+        )
+        .order_by(routine_exercise::Column::Position, Order::Asc)
+}
+
+pub async fn get_routines(
+    user_id: i32,
+    conn: &DatabaseConnection,
+) -> Result<Vec<models::RoutineQuery>> {
+    let routines = Routine::find()
+        .filter(routine::Column::UserId.eq(user_id))
+        .order_by(routine::Column::Name, Order::Asc)
+        .all(conn)
+        .await?;
+
+    let q = select_routine_exercises(user_id).filter(
+        routine_exercise::Column::RoutineId.is_in(routines.iter().map(|routine| routine.id)),
+    );
+
+    log::info!("{}", q.build(DbBackend::Postgres).to_string());
+
+    let exercises = q
+        .into_model::<models::RoutineExerciseQuery>()
+        .all(conn)
+        .await?;
+
+    let mut exercises_per_routine: HashMap<i32, Vec<models::RoutineExerciseQuery>> = exercises
+        .into_iter()
+        .into_group_map_by(|exercise| exercise.routine_id);
+
+    let res = routines
+        .into_iter()
+        .map(|routine| models::RoutineQuery {
+            exercises: exercises_per_routine
+                .remove(&routine.id)
+                .unwrap_or_default(),
+            id: routine.id,
+            name: routine.name,
+        })
+        .collect();
+
+    Ok(res)
+}
+
+pub async fn get_routine(
+    user_id: i32,
+    routine_id: i32,
+    conn: &DatabaseConnection,
+) -> Result<Option<models::RoutineQuery>> {
+    let routine = Routine::find_by_id(routine_id)
+        .filter(routine::Column::UserId.eq(user_id))
+        .one(conn)
+        .await?;
+
+    let routine = match routine {
+        Some(routine) => routine,
+        None => return Ok(None),
+    };
+
+    let exercises = select_routine_exercises(user_id)
+        .filter(routine_exercise::Column::RoutineId.eq(routine.id))
+        .into_model::<models::RoutineExerciseQuery>()
+        .all(conn)
+        .await?;
+
+    Ok(Some(models::RoutineQuery {
+        id: routine.id,
+        name: routine.name,
+        exercises,
+    }))
+}
+
+/// Replace all exercises of a routine, keeping the order in which they are given.
+pub async fn set_routine_exercises<C: ConnectionTrait>(
+    user_id: i32,
+    routine_id: i32,
+    exercises: Vec<models::RoutineExercise>,
+    conn: &C,
+) -> Result<()> {
+    RoutineExercise::delete_many()
+        .filter(routine_exercise::Column::RoutineId.eq(routine_id))
+        .exec(conn)
+        .await?;
+
+    for (position, exercise) in exercises.into_iter().enumerate() {
+        let name_id = crate::db::exercise::get_or_create_exercise_name(
+            &exercise.name,
+            exercise.kind,
+            Some(user_id),
+            conn,
+        )
+        .await?;
+
+        let new_exercise = routine_exercise::ActiveModel {
+            routine_id: ActiveValue::Set(routine_id),
+            name_id: ActiveValue::Set(name_id),
+            position: ActiveValue::Set(position as i32),
+            target_sets: ActiveValue::Set(exercise.target_sets),
+            target_reps: ActiveValue::Set(exercise.target_reps),
+            target_weight: ActiveValue::Set(exercise.target_weight),
+            ..Default::default()
+        };
+        RoutineExercise::insert(new_exercise).exec(conn).await?;
+    }
+
+    Ok(())
+}
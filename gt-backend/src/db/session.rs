@@ -0,0 +1,67 @@
+use migration::SimpleExpr;
+use sea_orm::*;
+
+use crate::Result;
+use gt_core::entities::{prelude::*, *};
+use gt_core::models;
+
+/// Select all columns of a workout session together with the number of sets that were logged during it.
+fn select_sessions(user_id: i32) -> Select<WorkoutSession> {
+    WorkoutSession::find()
+        .filter(workout_session::Column::UserId.eq(user_id))
+        .column_as(
+            SimpleExpr::Custom(
+                "(SELECT COUNT(*) FROM exercise_set WHERE exercise_set.session_id = workout_session.id AND exercise_set.deleted_at IS NULL)"
+                    .to_string(),
+            ),
+            "set_count",
+        )
+}
+
+pub async fn get_sessions(
+    user_id: i32,
+    conn: &DatabaseConnection,
+) -> Result<Vec<models::WorkoutSessionQuery>> {
+    let q = select_sessions(user_id)
+        .order_by(workout_session::Column::StartTime, Order::Desc)
+        .order_by(workout_session::Column::Id, Order::Desc);
+
+    log::info!("{}", q.build(DbBackend::Postgres).to_string());
+
+    let res = q
+        .into_model::<models::WorkoutSessionQuery>()
+        .all(conn)
+        .await?;
+
+    Ok(res)
+}
+
+pub async fn get_session(
+    user_id: i32,
+    session_id: i32,
+    conn: &DatabaseConnection,
+) -> Result<Option<models::WorkoutSessionQuery>> {
+    let res = select_sessions(user_id)
+        .filter(workout_session::Column::Id.eq(session_id))
+        .into_model::<models::WorkoutSessionQuery>()
+        .one(conn)
+        .await?;
+
+    Ok(res)
+}
+
+/// The active session is the one that has been started but not finished yet.
+/// There is at most one of those per user.
+pub async fn get_active_session(
+    user_id: i32,
+    conn: &DatabaseConnection,
+) -> Result<Option<workout_session::Model>> {
+    let res = WorkoutSession::find()
+        .filter(workout_session::Column::UserId.eq(user_id))
+        .filter(workout_session::Column::EndTime.is_null())
+        .order_by(workout_session::Column::StartTime, Order::Desc)
+        .one(conn)
+        .await?;
+
+    Ok(res)
+}
@@ -0,0 +1,227 @@
+use chrono::{Datelike, Duration, NaiveDate, Utc};
+use itertools::Itertools;
+use migration::SimpleExpr;
+use sea_orm::*;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+use crate::db::exercise::{filter_warmup, BODYWEIGHT_AT_SET};
+use crate::Result;
+use gt_core::entities::{prelude::*, *};
+use gt_core::models;
+
+/// How many of the most trained exercises are returned.
+const TOP_EXERCISES: usize = 5;
+
+/// The day of the user on which a set was logged.
+fn set_date(utc_offset: models::UtcOffsetParam) -> SimpleExpr {
+    SimpleExpr::Custom(format!(
+        "CAST(exercise_set.created_at + INTERVAL '{} seconds' AS DATE)",
+        utc_offset.utc_offset
+    ))
+}
+
+fn week_start(date: NaiveDate) -> NaiveDate {
+    date - Duration::days(date.weekday().num_days_from_monday() as i64)
+}
+
+fn month_start(date: NaiveDate) -> NaiveDate {
+    date.with_day(1).unwrap_or(date)
+}
+
+#[derive(Default)]
+struct Totals {
+    sets: i64,
+    reps: i64,
+    tonnage: f64,
+    days: HashSet<NaiveDate>,
+}
+
+impl Totals {
+    /// `date` is the day of the user on which the set was logged.
+    fn add(&mut self, set: &models::StatsJoinQuery, date: NaiveDate) {
+        let reps = set.reps.unwrap_or(0) as i64;
+        self.sets += 1;
+        self.reps += reps;
+        self.tonnage += set.load().unwrap_or(0.0) * reps as f64;
+        self.days.insert(date);
+    }
+
+    fn into_period(self, start: NaiveDate) -> models::StatsPeriod {
+        models::StatsPeriod {
+            start,
+            sets: self.sets,
+            reps: self.reps,
+            tonnage: self.tonnage,
+            training_days: self.days.len() as i64,
+        }
+    }
+}
+
+pub async fn get_stats(
+    user_id: i32,
+    range: &models::StatsRange,
+    utc_offset: models::UtcOffsetParam,
+    exclude_warmup: bool,
+    conn: &DatabaseConnection,
+) -> Result<models::StatsQuery> {
+    let mut q = ExerciseSet::find()
+        .select_only()
+        .column(exercise_set::Column::Reps)
+        .column(exercise_set::Column::Weight)
+        .column(exercise_set::Column::CreatedAt)
+        .column_as(exercise_name::Column::Name, "name")
+        .column_as(exercise_name::Column::Kind, "kind")
+        .column_as(
+            SimpleExpr::Custom(BODYWEIGHT_AT_SET.to_string()),
+            "bodyweight",
+        )
+        .filter(exercise_set::Column::UserId.eq(user_id))
+        .filter(exercise_set::Column::DeletedAt.is_null())
+        .join(
+            JoinType::InnerJoin,
+            exercise_set::Relation::ExerciseName.def(),
+        );
+    let mut sessions = WorkoutSession::find()
+        .filter(workout_session::Column::UserId.eq(user_id))
+        .filter(workout_session::Column::EndTime.is_not_null());
+
+    let (from, to) = utc_offset.utc_bounds(range.from, range.to);
+    if let Some(from) = from {
+        q = q.filter(exercise_set::Column::CreatedAt.gte(from));
+        sessions = sessions.filter(workout_session::Column::StartTime.gte(from));
+    }
+    if let Some(to) = to {
+        q = q.filter(exercise_set::Column::CreatedAt.lt(to));
+        sessions = sessions.filter(workout_session::Column::StartTime.lt(to));
+    }
+    let q = filter_warmup(q, exclude_warmup);
+
+    log::info!("{}", q.build(DbBackend::Postgres).to_string());
+
+    let sets = q.into_model::<models::StatsJoinQuery>().all(conn).await?;
+    let sessions = sessions.all(conn).await?;
+
+    let mut total = Totals::default();
+    let mut per_week: BTreeMap<NaiveDate, Totals> = BTreeMap::new();
+    let mut per_month: BTreeMap<NaiveDate, Totals> = BTreeMap::new();
+    let mut per_exercise: HashMap<String, Totals> = HashMap::new();
+
+    for set in sets.iter() {
+        let date = utc_offset.local_date(set.created_at);
+        total.add(set, date);
+        per_week.entry(week_start(date)).or_default().add(set, date);
+        per_month
+            .entry(month_start(date))
+            .or_default()
+            .add(set, date);
+        per_exercise
+            .entry(set.name.clone())
+            .or_default()
+            .add(set, date);
+    }
+
+    let top_exercises = per_exercise
+        .into_iter()
+        .sorted_by(|(name1, totals1), (name2, totals2)| {
+            totals2.sets.cmp(&totals1.sets).then(name1.cmp(name2))
+        })
+        .take(TOP_EXERCISES)
+        .map(|(name, totals)| models::StatsExercise {
+            name,
+            sets: totals.sets,
+            reps: totals.reps,
+            tonnage: totals.tonnage,
+        })
+        .collect();
+
+    let session_minutes = sessions
+        .iter()
+        .filter_map(|session| {
+            session
+                .end_time
+                .map(|end| (end - session.start_time).num_minutes())
+        })
+        .collect::<Vec<_>>();
+    let average_session_minutes = if session_minutes.is_empty() {
+        None
+    } else {
+        Some(session_minutes.iter().sum::<i64>() as f64 / session_minutes.len() as f64)
+    };
+
+    Ok(models::StatsQuery {
+        sets: total.sets,
+        reps: total.reps,
+        tonnage: total.tonnage,
+        training_days: total.days.len() as i64,
+        per_week: per_week
+            .into_iter()
+            .map(|(start, totals)| totals.into_period(start))
+            .collect(),
+        per_month: per_month
+            .into_iter()
+            .map(|(start, totals)| totals.into_period(start))
+            .collect(),
+        top_exercises,
+        average_session_minutes,
+    })
+}
+
+pub async fn get_calendar(
+    user_id: i32,
+    year: Option<i32>,
+    utc_offset: models::UtcOffsetParam,
+    conn: &DatabaseConnection,
+) -> Result<models::CalendarQuery> {
+    let today = utc_offset.local_date(Utc::now().naive_utc());
+    let year = year.unwrap_or(today.year());
+
+    // The streaks reach across years so all days are needed, not only the ones of the requested year.
+    let days = ExerciseSet::find()
+        .select_only()
+        .column_as(set_date(utc_offset), "date")
+        .column_as(exercise_set::Column::Id.count(), "sets")
+        .filter(exercise_set::Column::UserId.eq(user_id))
+        .filter(exercise_set::Column::DeletedAt.is_null())
+        .group_by(set_date(utc_offset))
+        .order_by_asc(set_date(utc_offset))
+        .into_model::<models::CalendarDay>()
+        .all(conn)
+        .await?;
+
+    let weeks = days
+        .iter()
+        .map(|day| week_start(day.date))
+        .collect::<BTreeSet<_>>();
+
+    let mut longest_streak = 0;
+    let mut streak = 0;
+    let mut last_week: Option<NaiveDate> = None;
+    for week in weeks.iter() {
+        streak = match last_week {
+            Some(last_week) if *week - last_week == Duration::weeks(1) => streak + 1,
+            _ => 1,
+        };
+        longest_streak = longest_streak.max(streak);
+        last_week = Some(*week);
+    }
+
+    let mut week = week_start(today);
+    if !weeks.contains(&week) {
+        week = week - Duration::weeks(1);
+    }
+    let mut current_streak = 0;
+    while weeks.contains(&week) {
+        current_streak += 1;
+        week = week - Duration::weeks(1);
+    }
+
+    Ok(models::CalendarQuery {
+        year,
+        days: days
+            .into_iter()
+            .filter(|day| day.date.year() == year)
+            .collect(),
+        current_streak,
+        longest_streak,
+    })
+}
@@ -0,0 +1,98 @@
+use chrono::{Duration, Utc};
+use migration::Expr;
+use sea_orm::*;
+
+use crate::db::exercise::get_trashed_exercise_sets;
+use crate::{AppError, AppState, Result};
+use gt_core::entities::{prelude::*, *};
+use gt_core::models;
+
+/// How often the trash is checked for items older than the retention period.
+const PURGE_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60 * 60);
+
+pub async fn get_trash(user_id: i32, conn: &DatabaseConnection) -> Result<models::TrashQuery> {
+    let exercise_sets = get_trashed_exercise_sets(user_id, conn).await?;
+
+    let user_info_ts = UserInfoTs::find()
+        .filter(user_info_ts::Column::UserId.eq(user_id))
+        .filter(user_info_ts::Column::DeletedAt.is_not_null())
+        .order_by(user_info_ts::Column::DeletedAt, Order::Desc)
+        .into_model::<models::TrashedUserInfoTs>()
+        .all(conn)
+        .await?;
+
+    Ok(models::TrashQuery {
+        exercise_sets,
+        user_info_ts,
+    })
+}
+
+/// Take an item of the user out of the trash again.
+pub async fn restore(
+    user_id: i32,
+    item: models::TrashItem,
+    conn: &DatabaseConnection,
+) -> Result<()> {
+    let rows_affected = match item {
+        models::TrashItem::ExerciseSet(id) => {
+            ExerciseSet::update_many()
+                .col_expr(
+                    exercise_set::Column::DeletedAt,
+                    Expr::value(Option::<chrono::NaiveDateTime>::None),
+                )
+                .filter(exercise_set::Column::Id.eq(id))
+                .filter(exercise_set::Column::UserId.eq(user_id))
+                .filter(exercise_set::Column::DeletedAt.is_not_null())
+                .exec(conn)
+                .await?
+                .rows_affected
+        }
+        models::TrashItem::UserInfoTs(id) => {
+            UserInfoTs::update_many()
+                .col_expr(
+                    user_info_ts::Column::DeletedAt,
+                    Expr::value(Option::<chrono::NaiveDateTime>::None),
+                )
+                .filter(user_info_ts::Column::Id.eq(id))
+                .filter(user_info_ts::Column::UserId.eq(user_id))
+                .filter(user_info_ts::Column::DeletedAt.is_not_null())
+                .exec(conn)
+                .await?
+                .rows_affected
+        }
+    };
+
+    if rows_affected == 0 {
+        return Err(AppError::ResourceNotFound);
+    }
+    Ok(())
+}
+
+/// Delete everything that has been in the trash for longer than [`models::TRASH_RETENTION_DAYS`].
+/// Returns the number of purged rows.
+pub async fn purge(conn: &DatabaseConnection) -> Result<u64> {
+    let cutoff = Utc::now().naive_utc() - Duration::days(models::TRASH_RETENTION_DAYS);
+
+    let res_sets = ExerciseSet::delete_many()
+        .filter(exercise_set::Column::DeletedAt.lt(cutoff))
+        .exec(conn)
+        .await?;
+    let res_info = UserInfoTs::delete_many()
+        .filter(user_info_ts::Column::DeletedAt.lt(cutoff))
+        .exec(conn)
+        .await?;
+
+    Ok(res_sets.rows_affected + res_info.rows_affected)
+}
+
+/// Purge the trash in regular intervals for as long as the server runs.
+pub async fn purge_periodically(state: AppState) {
+    let mut interval = tokio::time::interval(PURGE_INTERVAL);
+    loop {
+        interval.tick().await;
+        match purge(&state.conn).await {
+            Ok(purged) => log::info!("Purged {} items from the trash.", purged),
+            Err(e) => log::error!("Purging the trash failed: {}", e),
+        }
+    }
+}
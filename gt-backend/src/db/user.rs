@@ -57,6 +57,42 @@ pub async fn create_user(
     Ok(new_user.last_insert_id)
 }
 
+/// The unit in which weights are exchanged with the user. A unit given in the request takes precedence
+/// over the one saved in the user info.
+pub async fn get_weight_unit<C>(
+    user_id: i32,
+    requested: Option<models::WeightUnit>,
+    conn: &C,
+) -> Result<models::WeightUnit>
+where
+    C: ConnectionTrait,
+{
+    if let Some(unit) = requested {
+        return Ok(unit);
+    }
+
+    let user_info = UserInfo::find()
+        .filter(user_info::Column::UserId.eq(user_id))
+        .one(conn)
+        .await?
+        .ok_or(AppError::ResourceNotFound)?;
+
+    Ok(models::WeightUnit::try_from(user_info.weight_unit).unwrap_or_default())
+}
+
+pub async fn get_e1rm_formula<C>(user_id: i32, conn: &C) -> Result<models::E1rmFormula>
+where
+    C: ConnectionTrait,
+{
+    let user_info = UserInfo::find()
+        .filter(user_info::Column::UserId.eq(user_id))
+        .one(conn)
+        .await?
+        .ok_or(AppError::ResourceNotFound)?;
+
+    Ok(models::E1rmFormula::try_from(user_info.e1rm_formula).unwrap_or_default())
+}
+
 pub async fn get_user_info(
     user: user_login::Model,
     conn: &DatabaseConnection,
@@ -86,6 +122,14 @@ pub async fn get_user_info(
             ),
             Alias::new("display_name"),
         )
+        .expr_as(
+            Expr::value(user_info.weight_unit),
+            Alias::new("weight_unit"),
+        )
+        .expr_as(
+            Expr::value(user_info.e1rm_formula),
+            Alias::new("e1rm_formula"),
+        )
         .expr_as(
             SimpleExpr::SubQuery(
                 None,
@@ -94,6 +138,7 @@ pub async fn get_user_info(
                         .column((user_info_ts::Entity, user_info_ts::Column::Height))
                         .from(user_info_ts::Entity)
                         .and_where(user_info_ts::Column::UserId.eq(user.id))
+                        .and_where(user_info_ts::Column::DeletedAt.is_null())
                         .and_where(user_info_ts::Column::Height.is_not_null())
                         .order_by(user_info_ts::Column::CreatedAt, Order::Desc)
                         .limit(1)
@@ -110,6 +155,7 @@ pub async fn get_user_info(
                         .column((user_info_ts::Entity, user_info_ts::Column::Weight))
                         .from(user_info_ts::Entity)
                         .and_where(user_info_ts::Column::UserId.eq(user.id))
+                        .and_where(user_info_ts::Column::DeletedAt.is_null())
                         .and_where(user_info_ts::Column::Weight.is_not_null())
                         .order_by(user_info_ts::Column::CreatedAt, Order::Desc)
                         .limit(1)
@@ -126,6 +172,7 @@ pub async fn get_user_info(
                         .column((user_info_ts::Entity, user_info_ts::Column::MuscleMass))
                         .from(user_info_ts::Entity)
                         .and_where(user_info_ts::Column::UserId.eq(user.id))
+                        .and_where(user_info_ts::Column::DeletedAt.is_null())
                         .and_where(user_info_ts::Column::MuscleMass.is_not_null())
                         .order_by(user_info_ts::Column::CreatedAt, Order::Desc)
                         .limit(1)
@@ -142,6 +189,7 @@ pub async fn get_user_info(
                         .column((user_info_ts::Entity, user_info_ts::Column::BodyFat))
                         .from(user_info_ts::Entity)
                         .and_where(user_info_ts::Column::UserId.eq(user.id))
+                        .and_where(user_info_ts::Column::DeletedAt.is_null())
                         .and_where(user_info_ts::Column::BodyFat.is_not_null())
                         .order_by(user_info_ts::Column::CreatedAt, Order::Desc)
                         .limit(1)
@@ -7,6 +7,7 @@ use thiserror::Error;
 
 pub mod api;
 pub mod db;
+pub mod progression;
 
 #[derive(Clone)]
 pub struct InnerAppState {
@@ -3,7 +3,7 @@ use axum::{
     http::StatusCode,
     middleware,
     response::Redirect,
-    routing::{get, get_service, post},
+    routing::{get, get_service, post, put},
     Router, Server,
 };
 use gt_core::APP_BASE;
@@ -42,6 +42,8 @@ async fn main() -> anyhow::Result<()> {
     Migrator::up(&state.conn, None).await?;
     db::populate::populate(populate_data, &state).await?;
 
+    tokio::spawn(db::trash::purge_periodically(state.clone()));
+
     let unauth_api_routes = Router::new()
         .route("/user/login", post(api::user::login))
         .route("/user/register", post(api::user::register));
@@ -55,29 +57,93 @@ async fn main() -> anyhow::Result<()> {
     let auth_api_routes = Router::new()
         .route("/admin/merge-names", post(api::admin::merge_names))
         .route("/admin/reset-password", post(api::admin::reset_password))
+        .route(
+            "/admin/exercise-name",
+            put(api::admin::change_exercise_name_metadata),
+        )
+        .route(
+            "/admin/exercise-name/private",
+            get(api::admin::get_private_exercise_names),
+        )
+        .route(
+            "/admin/exercise-name/promote",
+            post(api::admin::promote_exercise_name),
+        )
+        .route(
+            "/admin/exercise-alias",
+            post(api::admin::add_exercise_alias).delete(api::admin::delete_exercise_alias),
+        )
         .layer(superuser_auth)
         .route(
             "/exercise/name",
             get(api::exercise::get_all_exercise_names).post(api::exercise::add_exercise_name),
         )
+        .route(
+            "/exercise/alias",
+            get(api::exercise::get_all_exercise_aliases),
+        )
+        .route(
+            "/exercise/rest",
+            put(api::exercise::set_exercise_rest_for_user),
+        )
         .route(
             "/exercise/graph",
             get(api::exercise::get_exercise_graph_for_user),
         )
+        .route(
+            "/exercise/graph/pace",
+            get(api::exercise::get_exercise_pace_graph_for_user),
+        )
         .route(
             "/exercise/set",
             get(api::exercise::get_all_exercise_sets_for_user)
                 .post(api::exercise::add_exercise_set_for_user)
+                .put(api::exercise::change_exercise_set_for_user)
                 .delete(api::exercise::delete_exercise_set_for_user),
         )
         .route(
-            "/exercise/set/:page_size",
-            get(api::exercise::get_paged_exercise_sets_for_user),
+            "/exercise/set/group",
+            post(api::exercise::add_exercise_set_group_for_user),
         )
         .route(
             "/exercise/pr",
             get(api::exercise::get_exercise_set_prs_for_user),
         )
+        .route("/export/sets.csv", get(api::export::export_exercise_sets))
+        .route(
+            "/export/body.csv",
+            get(api::export::export_body_measurements),
+        )
+        .route(
+            "/program",
+            get(api::program::get_all_programs)
+                .post(api::program::add_program)
+                .delete(api::program::delete_program),
+        )
+        .route(
+            "/program/:id",
+            get(api::program::get_program).put(api::program::change_program),
+        )
+        .route("/program/:id/today", get(api::program::get_program_today))
+        .route(
+            "/routine",
+            get(api::routine::get_all_routines)
+                .post(api::routine::add_routine)
+                .delete(api::routine::delete_routine),
+        )
+        .route(
+            "/routine/:id",
+            get(api::routine::get_routine).put(api::routine::change_routine),
+        )
+        .route("/session", get(api::session::get_all_sessions))
+        .route("/session/active", get(api::session::get_active_session))
+        .route("/session/start", post(api::session::start_session))
+        .route("/session/finish", post(api::session::finish_session))
+        .route("/session/:id", get(api::session::get_session))
+        .route("/stats", get(api::stats::get_stats))
+        .route("/stats/calendar", get(api::stats::get_calendar))
+        .route("/trash", get(api::trash::get_trash))
+        .route("/trash/restore", post(api::trash::restore_trash_item))
         .route(
             "/user/info",
             get(api::user::get_user_info).post(api::user::change_user_info),
@@ -90,7 +156,9 @@ async fn main() -> anyhow::Result<()> {
         )
         .route(
             "/user/info-ts",
-            get(api::user::get_user_info_ts).post(api::user::add_user_info_ts),
+            get(api::user::get_user_info_ts)
+                .post(api::user::add_user_info_ts)
+                .delete(api::user::delete_user_info_ts),
         )
         .route("/user/logout", post(api::user::logout))
         .route("/auth/check", post(api::auth::check_token))
@@ -0,0 +1,159 @@
+use gt_core::models::{self, ProgressionRule, SetType, WeightUnit};
+
+/// The working sets of one exercise on one training day as `(weight, reps)`.
+pub type TrainingDay = Vec<(f64, i32)>;
+
+/// Smallest weight step that can be loaded with common plates, in kg and lb.
+const WEIGHT_STEP_KG: f64 = 2.5;
+const WEIGHT_STEP_LB: f64 = 5.0;
+
+/// Linear progression takes off 10% after failing this many training days in a row at the same weight.
+const LINEAR_STALLS_BEFORE_DELOAD: usize = 3;
+
+/// Percentage of the training max and reps of each set, for the four weeks of a 5/3/1 cycle.
+/// The last week is a deload.
+const FIVE_THREE_ONE: [[(f64, i32); 3]; 4] = [
+    [(0.65, 5), (0.75, 5), (0.85, 5)],
+    [(0.70, 3), (0.80, 3), (0.90, 3)],
+    [(0.75, 5), (0.85, 3), (0.95, 1)],
+    [(0.40, 5), (0.50, 5), (0.60, 5)],
+];
+
+/// Round a weight in kg to what can be loaded in the unit of the user, and return it in kg again.
+fn round_weight(weight: f64, unit: WeightUnit) -> f64 {
+    let step = match unit {
+        WeightUnit::Kg => WEIGHT_STEP_KG,
+        WeightUnit::Lb => WEIGHT_STEP_LB,
+    };
+    unit.to_kg((unit.from_kg(weight) / step).round() * step)
+}
+
+fn top_weight(day: &TrainingDay) -> f64 {
+    day.iter()
+        .map(|(weight, _)| *weight)
+        .fold(f64::MIN, f64::max)
+}
+
+/// The reps of all sets that were done with the top weight of the day.
+fn top_reps(day: &TrainingDay) -> Vec<i32> {
+    let weight = top_weight(day);
+    day.iter()
+        .filter(|(w, _)| *w >= weight)
+        .map(|(_, reps)| *reps)
+        .collect()
+}
+
+fn straight_sets(weight: f64, reps: i32, sets: i32) -> Vec<models::PrescribedSet> {
+    (0..sets)
+        .map(|_| models::PrescribedSet {
+            weight,
+            reps,
+            set_type: SetType::Working,
+        })
+        .collect()
+}
+
+fn all_sets_done(exercise: &models::ProgramExerciseQuery, day: &TrainingDay, reps: i32) -> bool {
+    let top_reps = top_reps(day);
+    top_reps.len() as i32 >= exercise.sets && top_reps.iter().all(|r| *r >= reps)
+}
+
+fn linear(
+    exercise: &models::ProgramExerciseQuery,
+    days: &[TrainingDay],
+    unit: WeightUnit,
+) -> Vec<models::PrescribedSet> {
+    let last_day = match days.last() {
+        Some(day) => day,
+        None => return straight_sets(exercise.start_weight, exercise.min_reps, exercise.sets),
+    };
+
+    let weight = top_weight(last_day);
+    let stalled = days.len() >= LINEAR_STALLS_BEFORE_DELOAD
+        && days
+            .iter()
+            .rev()
+            .take(LINEAR_STALLS_BEFORE_DELOAD)
+            .all(|day| {
+                top_weight(day) == weight && !all_sets_done(exercise, day, exercise.min_reps)
+            });
+
+    let weight = if all_sets_done(exercise, last_day, exercise.min_reps) {
+        weight + exercise.increment
+    } else if stalled {
+        round_weight(weight * 0.9, unit)
+    } else {
+        weight
+    };
+
+    straight_sets(weight, exercise.min_reps, exercise.sets)
+}
+
+fn double_progression(
+    exercise: &models::ProgramExerciseQuery,
+    days: &[TrainingDay],
+) -> Vec<models::PrescribedSet> {
+    let last_day = match days.last() {
+        Some(day) => day,
+        None => return straight_sets(exercise.start_weight, exercise.min_reps, exercise.sets),
+    };
+
+    let weight = top_weight(last_day);
+    if all_sets_done(exercise, last_day, exercise.max_reps) {
+        return straight_sets(
+            weight + exercise.increment,
+            exercise.min_reps,
+            exercise.sets,
+        );
+    }
+
+    // Try to get one more rep on the weakest set.
+    let reps = top_reps(last_day)
+        .into_iter()
+        .min()
+        .map(|reps| reps + 1)
+        .unwrap_or(exercise.min_reps)
+        .clamp(exercise.min_reps, exercise.max_reps);
+
+    straight_sets(weight, reps, exercise.sets)
+}
+
+/// Every training day of the exercise is one week of the cycle. The last set of each week except for the deload is an AMRAP set.
+fn five_three_one(
+    exercise: &models::ProgramExerciseQuery,
+    days: &[TrainingDay],
+    unit: WeightUnit,
+) -> Vec<models::PrescribedSet> {
+    let week = days.len() % FIVE_THREE_ONE.len();
+    let cycle = days.len() / FIVE_THREE_ONE.len();
+    let training_max = exercise.start_weight + cycle as f64 * exercise.increment;
+    let is_deload = week == FIVE_THREE_ONE.len() - 1;
+
+    FIVE_THREE_ONE[week]
+        .iter()
+        .enumerate()
+        .map(|(i, (percentage, reps))| models::PrescribedSet {
+            weight: round_weight(training_max * percentage, unit),
+            reps: *reps,
+            set_type: if i == FIVE_THREE_ONE[week].len() - 1 && !is_deload {
+                SetType::Amrap
+            } else {
+                SetType::Working
+            },
+        })
+        .collect()
+}
+
+/// Compute the sets of the next training day of a program exercise from the training days since the program started.
+/// Weights are in kg, but computed weights are rounded to plates of the user's unit.
+pub fn prescribe(
+    exercise: &models::ProgramExerciseQuery,
+    days: &[TrainingDay],
+    unit: WeightUnit,
+) -> Vec<models::PrescribedSet> {
+    match exercise.rule {
+        ProgressionRule::Linear => linear(exercise, days, unit),
+        ProgressionRule::FiveThreeOne => five_three_one(exercise, days, unit),
+        ProgressionRule::DoubleProgression => double_progression(exercise, days),
+    }
+}
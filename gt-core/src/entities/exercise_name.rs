@@ -8,15 +8,47 @@ use serde::{Deserialize, Serialize};
 pub struct Model {
     #[sea_orm(primary_key)]
     pub id: i32,
-    #[sea_orm(unique)]
     pub name: String,
     pub kind: i32,
+    pub primary_muscle: Option<i32>,
+    pub secondary_muscle: Option<i32>,
+    pub equipment: Option<i32>,
+    pub movement_pattern: Option<i32>,
+    pub owner_id: Option<i32>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(has_many = "super::exercise_alias::Entity")]
+    ExerciseAlias,
+    #[sea_orm(has_many = "super::exercise_rest::Entity")]
+    ExerciseRest,
     #[sea_orm(has_many = "super::exercise_set::Entity")]
     ExerciseSet,
+    #[sea_orm(has_many = "super::program_exercise::Entity")]
+    ProgramExercise,
+    #[sea_orm(has_many = "super::routine_exercise::Entity")]
+    RoutineExercise,
+    #[sea_orm(
+        belongs_to = "super::user_login::Entity",
+        from = "Column::OwnerId",
+        to = "super::user_login::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    UserLogin,
+}
+
+impl Related<super::exercise_alias::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ExerciseAlias.def()
+    }
+}
+
+impl Related<super::exercise_rest::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ExerciseRest.def()
+    }
 }
 
 impl Related<super::exercise_set::Entity> for Entity {
@@ -25,4 +57,22 @@ impl Related<super::exercise_set::Entity> for Entity {
     }
 }
 
+impl Related<super::program_exercise::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ProgramExercise.def()
+    }
+}
+
+impl Related<super::routine_exercise::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::RoutineExercise.def()
+    }
+}
+
+impl Related<super::user_login::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::UserLogin.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
@@ -14,6 +14,16 @@ pub struct Model {
     pub weight: Option<f64>,
     pub time: Option<i32>,
     pub created_at: DateTime,
+    pub session_id: Option<i32>,
+    pub distance: Option<f64>,
+    pub set_type: i32,
+    pub rpe: Option<f64>,
+    pub rir: Option<i32>,
+    pub group_id: Option<i32>,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub note: Option<String>,
+    pub deleted_at: Option<DateTime>,
+    pub idempotency_key: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -34,6 +44,14 @@ pub enum Relation {
         on_delete = "Cascade"
     )]
     UserLogin,
+    #[sea_orm(
+        belongs_to = "super::workout_session::Entity",
+        from = "Column::SessionId",
+        to = "super::workout_session::Column::Id",
+        on_update = "Cascade",
+        on_delete = "SetNull"
+    )]
+    WorkoutSession,
 }
 
 impl Related<super::exercise_name::Entity> for Entity {
@@ -48,4 +66,10 @@ impl Related<super::user_login::Entity> for Entity {
     }
 }
 
+impl Related<super::workout_session::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::WorkoutSession.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
@@ -2,8 +2,15 @@
 
 pub mod prelude;
 
+pub mod exercise_alias;
 pub mod exercise_name;
+pub mod exercise_rest;
 pub mod exercise_set;
+pub mod program;
+pub mod program_exercise;
+pub mod routine;
+pub mod routine_exercise;
 pub mod user_info;
 pub mod user_info_ts;
 pub mod user_login;
+pub mod workout_session;
@@ -1,7 +1,14 @@
 //! `SeaORM` Entity. Generated by sea-orm-codegen 0.10.6
 
+pub use super::exercise_alias::Entity as ExerciseAlias;
 pub use super::exercise_name::Entity as ExerciseName;
+pub use super::exercise_rest::Entity as ExerciseRest;
 pub use super::exercise_set::Entity as ExerciseSet;
+pub use super::program::Entity as Program;
+pub use super::program_exercise::Entity as ProgramExercise;
+pub use super::routine::Entity as Routine;
+pub use super::routine_exercise::Entity as RoutineExercise;
 pub use super::user_info::Entity as UserInfo;
 pub use super::user_info_ts::Entity as UserInfoTs;
 pub use super::user_login::Entity as UserLogin;
+pub use super::workout_session::Entity as WorkoutSession;
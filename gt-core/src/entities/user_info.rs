@@ -12,6 +12,8 @@ pub struct Model {
     pub user_id: i32,
     pub display_name: String,
     pub photo: Option<Vec<u8>>,
+    pub weight_unit: i32,
+    pub e1rm_formula: i32,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
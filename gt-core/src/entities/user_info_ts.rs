@@ -14,6 +14,7 @@ pub struct Model {
     pub muscle_mass: Option<f64>,
     pub body_fat: Option<f64>,
     pub created_at: DateTime,
+    pub deleted_at: Option<DateTime>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -19,12 +19,34 @@ pub struct Model {
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(has_many = "super::exercise_name::Entity")]
+    ExerciseName,
+    #[sea_orm(has_many = "super::exercise_rest::Entity")]
+    ExerciseRest,
     #[sea_orm(has_many = "super::exercise_set::Entity")]
     ExerciseSet,
+    #[sea_orm(has_many = "super::program::Entity")]
+    Program,
+    #[sea_orm(has_many = "super::routine::Entity")]
+    Routine,
     #[sea_orm(has_one = "super::user_info::Entity")]
     UserInfo,
     #[sea_orm(has_many = "super::user_info_ts::Entity")]
     UserInfoTs,
+    #[sea_orm(has_many = "super::workout_session::Entity")]
+    WorkoutSession,
+}
+
+impl Related<super::exercise_name::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ExerciseName.def()
+    }
+}
+
+impl Related<super::exercise_rest::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ExerciseRest.def()
+    }
 }
 
 impl Related<super::exercise_set::Entity> for Entity {
@@ -33,6 +55,18 @@ impl Related<super::exercise_set::Entity> for Entity {
     }
 }
 
+impl Related<super::program::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Program.def()
+    }
+}
+
+impl Related<super::routine::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Routine.def()
+    }
+}
+
 impl Related<super::user_info::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::UserInfo.def()
@@ -45,4 +79,10 @@ impl Related<super::user_info_ts::Entity> for Entity {
     }
 }
 
+impl Related<super::workout_session::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::WorkoutSession.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
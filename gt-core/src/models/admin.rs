@@ -1,10 +1,25 @@
 use derive_more::From;
+#[cfg(not(target_arch = "wasm32"))]
+use sea_orm::FromQueryResult;
 use serde::{Deserialize, Serialize};
 
+use crate::models::ExerciseKind;
+
 #[derive(Debug, Clone, Deserialize, Serialize, From, PartialEq)]
 pub struct MergeNames {
     pub to_delete: String,
     pub to_expand: String,
+    /// Keep the deleted name as an alias, so that it can still be used to log sets.
+    #[serde(default)]
+    pub keep_alias: bool,
+}
+
+/// Another name under which the exercise `name` can be logged, e.g. a translation.
+#[derive(Debug, Clone, Deserialize, Serialize, From, PartialEq)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(FromQueryResult))]
+pub struct ExerciseAlias {
+    pub alias: String,
+    pub name: String,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, From, PartialEq)]
@@ -12,3 +27,18 @@ pub struct AdminResetPassword {
     pub username: String,
     pub password: String,
 }
+
+/// A private exercise together with the user it belongs to.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(FromQueryResult))]
+pub struct PrivateExerciseNameQuery {
+    pub name: String,
+    pub kind: ExerciseKind,
+    pub username: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct ExerciseNamePromote {
+    pub name: String,
+    pub username: String,
+}
@@ -0,0 +1,236 @@
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+#[cfg(not(target_arch = "wasm32"))]
+use sea_orm::{DeriveActiveEnum, EnumIter};
+use serde::{Deserialize, Serialize};
+
+use crate::models::ExerciseNameQuery;
+
+/// A property of an exercise name that is picked from a fixed list of values.
+pub trait CatalogValue: Copy + PartialEq + Into<i32> + TryFrom<i32> + 'static {
+    const ALL: &'static [Self];
+
+    fn label(&self) -> &'static str;
+}
+
+#[derive(
+    Debug, Clone, Copy, Deserialize, Serialize, TryFromPrimitive, IntoPrimitive, PartialEq,
+)]
+#[repr(i32)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(EnumIter, DeriveActiveEnum))]
+#[cfg_attr(
+    not(target_arch = "wasm32"),
+    sea_orm(rs_type = "i32", db_type = "Integer")
+)]
+pub enum MuscleGroup {
+    #[cfg_attr(not(target_arch = "wasm32"), sea_orm(num_value = 0))]
+    Chest = 0,
+    #[cfg_attr(not(target_arch = "wasm32"), sea_orm(num_value = 1))]
+    Back = 1,
+    #[cfg_attr(not(target_arch = "wasm32"), sea_orm(num_value = 2))]
+    Shoulders = 2,
+    #[cfg_attr(not(target_arch = "wasm32"), sea_orm(num_value = 3))]
+    Biceps = 3,
+    #[cfg_attr(not(target_arch = "wasm32"), sea_orm(num_value = 4))]
+    Triceps = 4,
+    #[cfg_attr(not(target_arch = "wasm32"), sea_orm(num_value = 5))]
+    Forearms = 5,
+    #[cfg_attr(not(target_arch = "wasm32"), sea_orm(num_value = 6))]
+    Core = 6,
+    #[cfg_attr(not(target_arch = "wasm32"), sea_orm(num_value = 7))]
+    Quads = 7,
+    #[cfg_attr(not(target_arch = "wasm32"), sea_orm(num_value = 8))]
+    Hamstrings = 8,
+    #[cfg_attr(not(target_arch = "wasm32"), sea_orm(num_value = 9))]
+    Glutes = 9,
+    #[cfg_attr(not(target_arch = "wasm32"), sea_orm(num_value = 10))]
+    Calves = 10,
+    #[cfg_attr(not(target_arch = "wasm32"), sea_orm(num_value = 11))]
+    FullBody = 11,
+}
+
+impl CatalogValue for MuscleGroup {
+    const ALL: &'static [Self] = &[
+        MuscleGroup::Chest,
+        MuscleGroup::Back,
+        MuscleGroup::Shoulders,
+        MuscleGroup::Biceps,
+        MuscleGroup::Triceps,
+        MuscleGroup::Forearms,
+        MuscleGroup::Core,
+        MuscleGroup::Quads,
+        MuscleGroup::Hamstrings,
+        MuscleGroup::Glutes,
+        MuscleGroup::Calves,
+        MuscleGroup::FullBody,
+    ];
+
+    fn label(&self) -> &'static str {
+        match self {
+            MuscleGroup::Chest => "Chest",
+            MuscleGroup::Back => "Back",
+            MuscleGroup::Shoulders => "Shoulders",
+            MuscleGroup::Biceps => "Biceps",
+            MuscleGroup::Triceps => "Triceps",
+            MuscleGroup::Forearms => "Forearms",
+            MuscleGroup::Core => "Core",
+            MuscleGroup::Quads => "Quads",
+            MuscleGroup::Hamstrings => "Hamstrings",
+            MuscleGroup::Glutes => "Glutes",
+            MuscleGroup::Calves => "Calves",
+            MuscleGroup::FullBody => "Full body",
+        }
+    }
+}
+
+#[derive(
+    Debug, Clone, Copy, Deserialize, Serialize, TryFromPrimitive, IntoPrimitive, PartialEq,
+)]
+#[repr(i32)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(EnumIter, DeriveActiveEnum))]
+#[cfg_attr(
+    not(target_arch = "wasm32"),
+    sea_orm(rs_type = "i32", db_type = "Integer")
+)]
+pub enum Equipment {
+    #[cfg_attr(not(target_arch = "wasm32"), sea_orm(num_value = 0))]
+    Barbell = 0,
+    #[cfg_attr(not(target_arch = "wasm32"), sea_orm(num_value = 1))]
+    Dumbbell = 1,
+    #[cfg_attr(not(target_arch = "wasm32"), sea_orm(num_value = 2))]
+    Kettlebell = 2,
+    #[cfg_attr(not(target_arch = "wasm32"), sea_orm(num_value = 3))]
+    Machine = 3,
+    #[cfg_attr(not(target_arch = "wasm32"), sea_orm(num_value = 4))]
+    Cable = 4,
+    #[cfg_attr(not(target_arch = "wasm32"), sea_orm(num_value = 5))]
+    Bodyweight = 5,
+    #[cfg_attr(not(target_arch = "wasm32"), sea_orm(num_value = 6))]
+    Band = 6,
+    #[cfg_attr(not(target_arch = "wasm32"), sea_orm(num_value = 7))]
+    Other = 7,
+}
+
+impl CatalogValue for Equipment {
+    const ALL: &'static [Self] = &[
+        Equipment::Barbell,
+        Equipment::Dumbbell,
+        Equipment::Kettlebell,
+        Equipment::Machine,
+        Equipment::Cable,
+        Equipment::Bodyweight,
+        Equipment::Band,
+        Equipment::Other,
+    ];
+
+    fn label(&self) -> &'static str {
+        match self {
+            Equipment::Barbell => "Barbell",
+            Equipment::Dumbbell => "Dumbbell",
+            Equipment::Kettlebell => "Kettlebell",
+            Equipment::Machine => "Machine",
+            Equipment::Cable => "Cable",
+            Equipment::Bodyweight => "Bodyweight",
+            Equipment::Band => "Band",
+            Equipment::Other => "Other",
+        }
+    }
+}
+
+#[derive(
+    Debug, Clone, Copy, Deserialize, Serialize, TryFromPrimitive, IntoPrimitive, PartialEq,
+)]
+#[repr(i32)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(EnumIter, DeriveActiveEnum))]
+#[cfg_attr(
+    not(target_arch = "wasm32"),
+    sea_orm(rs_type = "i32", db_type = "Integer")
+)]
+pub enum MovementPattern {
+    #[cfg_attr(not(target_arch = "wasm32"), sea_orm(num_value = 0))]
+    HorizontalPush = 0,
+    #[cfg_attr(not(target_arch = "wasm32"), sea_orm(num_value = 1))]
+    VerticalPush = 1,
+    #[cfg_attr(not(target_arch = "wasm32"), sea_orm(num_value = 2))]
+    HorizontalPull = 2,
+    #[cfg_attr(not(target_arch = "wasm32"), sea_orm(num_value = 3))]
+    VerticalPull = 3,
+    #[cfg_attr(not(target_arch = "wasm32"), sea_orm(num_value = 4))]
+    Squat = 4,
+    #[cfg_attr(not(target_arch = "wasm32"), sea_orm(num_value = 5))]
+    Hinge = 5,
+    #[cfg_attr(not(target_arch = "wasm32"), sea_orm(num_value = 6))]
+    Lunge = 6,
+    #[cfg_attr(not(target_arch = "wasm32"), sea_orm(num_value = 7))]
+    Carry = 7,
+    #[cfg_attr(not(target_arch = "wasm32"), sea_orm(num_value = 8))]
+    Isolation = 8,
+}
+
+impl CatalogValue for MovementPattern {
+    const ALL: &'static [Self] = &[
+        MovementPattern::HorizontalPush,
+        MovementPattern::VerticalPush,
+        MovementPattern::HorizontalPull,
+        MovementPattern::VerticalPull,
+        MovementPattern::Squat,
+        MovementPattern::Hinge,
+        MovementPattern::Lunge,
+        MovementPattern::Carry,
+        MovementPattern::Isolation,
+    ];
+
+    fn label(&self) -> &'static str {
+        match self {
+            MovementPattern::HorizontalPush => "Horizontal push",
+            MovementPattern::VerticalPush => "Vertical push",
+            MovementPattern::HorizontalPull => "Horizontal pull",
+            MovementPattern::VerticalPull => "Vertical pull",
+            MovementPattern::Squat => "Squat",
+            MovementPattern::Hinge => "Hinge",
+            MovementPattern::Lunge => "Lunge",
+            MovementPattern::Carry => "Carry",
+            MovementPattern::Isolation => "Isolation",
+        }
+    }
+}
+
+/// Sent by an admin to change the catalog information of an exercise name.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct ExerciseNameMetadata {
+    pub name: String,
+    pub primary_muscle: Option<MuscleGroup>,
+    pub secondary_muscle: Option<MuscleGroup>,
+    pub equipment: Option<Equipment>,
+    pub movement_pattern: Option<MovementPattern>,
+}
+
+/// Restricts the exercises shown on the PR and graph pages. A field that is `None` matches everything.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct ExerciseNameFilter {
+    pub muscle: Option<MuscleGroup>,
+    pub equipment: Option<Equipment>,
+    pub movement_pattern: Option<MovementPattern>,
+}
+
+impl ExerciseNameFilter {
+    pub fn is_empty(&self) -> bool {
+        self.muscle.is_none() && self.equipment.is_none() && self.movement_pattern.is_none()
+    }
+
+    /// The muscle group matches both the primary and the secondary muscle group of an exercise.
+    pub fn matches(&self, name: &ExerciseNameQuery) -> bool {
+        let muscle_matches = self.muscle.is_none()
+            || self.muscle == name.primary_muscle
+            || self.muscle == name.secondary_muscle;
+        let equipment_matches = self.equipment.is_none() || self.equipment == name.equipment;
+        let movement_pattern_matches =
+            self.movement_pattern.is_none() || self.movement_pattern == name.movement_pattern;
+
+        muscle_matches && equipment_matches && movement_pattern_matches
+    }
+
+    /// Look up the exercise called `name` in `names`. Unknown exercises only match an empty filter.
+    pub fn matches_name(&self, name: &str, names: &[ExerciseNameQuery]) -> bool {
+        self.is_empty() || names.iter().any(|exn| exn.name == name && self.matches(exn))
+    }
+}
@@ -0,0 +1,39 @@
+use chrono::{Duration, NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+
+/// Query parameter with the time zone of the user in seconds east of UTC, so that dates are taken as
+/// the days of the user instead of UTC days. Times are always stored in UTC.
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq)]
+pub struct UtcOffsetParam {
+    #[serde(default)]
+    pub utc_offset: i32,
+}
+
+impl UtcOffsetParam {
+    fn offset(&self) -> Duration {
+        Duration::seconds(self.utc_offset as i64)
+    }
+
+    /// The start of the day `date` of the user, in UTC.
+    pub fn start_of_day(&self, date: NaiveDate) -> NaiveDateTime {
+        date.and_hms_opt(0, 0, 0).unwrap() - self.offset()
+    }
+
+    /// The range from the start of the day `from` to the end of the day `to` of the user in UTC,
+    /// with the start inclusive and the end exclusive. A missing date leaves the range open.
+    pub fn utc_bounds(
+        &self,
+        from: Option<NaiveDate>,
+        to: Option<NaiveDate>,
+    ) -> (Option<NaiveDateTime>, Option<NaiveDateTime>) {
+        (
+            from.map(|from| self.start_of_day(from)),
+            to.map(|to| self.start_of_day(to + Duration::days(1))),
+        )
+    }
+
+    /// The day of the user at the UTC time `time`.
+    pub fn local_date(&self, time: NaiveDateTime) -> NaiveDate {
+        (time + self.offset()).date()
+    }
+}
@@ -6,15 +6,50 @@ use crate::models::exercise::ExerciseSet;
 impl From<ExerciseSet> for exercise_set::ActiveModel {
     fn from(exs: ExerciseSet) -> Self {
         let created_at = exs.created_at();
+        let set_type = exs.set_type();
+        let note = exs.note().map(|note| note.to_string());
         match exs {
             ExerciseSet::Weighted(exs) => exercise_set::ActiveModel {
                 reps: ActiveValue::Set(Some(exs.reps)),
                 weight: ActiveValue::Set(Some(exs.weight)),
+                rpe: ActiveValue::Set(exs.rpe),
+                rir: ActiveValue::Set(exs.rir),
+                set_type: ActiveValue::Set(set_type.into()),
+                note: ActiveValue::Set(note),
                 created_at: ActiveValue::Set(created_at),
                 ..Default::default()
             },
             ExerciseSet::Bodyweight(exs) => exercise_set::ActiveModel {
                 reps: ActiveValue::Set(Some(exs.reps)),
+                rpe: ActiveValue::Set(exs.rpe),
+                rir: ActiveValue::Set(exs.rir),
+                set_type: ActiveValue::Set(set_type.into()),
+                note: ActiveValue::Set(note),
+                created_at: ActiveValue::Set(created_at),
+                ..Default::default()
+            },
+            ExerciseSet::Timed(exs) => exercise_set::ActiveModel {
+                time: ActiveValue::Set(Some(exs.time)),
+                set_type: ActiveValue::Set(set_type.into()),
+                note: ActiveValue::Set(note),
+                created_at: ActiveValue::Set(created_at),
+                ..Default::default()
+            },
+            ExerciseSet::Distance(exs) => exercise_set::ActiveModel {
+                distance: ActiveValue::Set(Some(exs.distance)),
+                time: ActiveValue::Set(Some(exs.time)),
+                set_type: ActiveValue::Set(set_type.into()),
+                note: ActiveValue::Set(note),
+                created_at: ActiveValue::Set(created_at),
+                ..Default::default()
+            },
+            ExerciseSet::WeightedBodyweight(exs) => exercise_set::ActiveModel {
+                reps: ActiveValue::Set(Some(exs.reps)),
+                weight: ActiveValue::Set(Some(exs.weight)),
+                rpe: ActiveValue::Set(exs.rpe),
+                rir: ActiveValue::Set(exs.rir),
+                set_type: ActiveValue::Set(set_type.into()),
+                note: ActiveValue::Set(note),
                 created_at: ActiveValue::Set(created_at),
                 ..Default::default()
             },
@@ -1,5 +1,5 @@
 use anyhow::anyhow;
-use chrono::{NaiveDateTime, Utc};
+use chrono::{NaiveDate, NaiveDateTime, Utc};
 use derive_more::From;
 use log::info;
 use num_enum::{IntoPrimitive, TryFromPrimitive};
@@ -7,6 +7,8 @@ use num_enum::{IntoPrimitive, TryFromPrimitive};
 use sea_orm::{DeriveActiveEnum, EnumIter, FromQueryResult};
 use serde::{Deserialize, Serialize};
 
+use crate::models::{Equipment, MovementPattern, MuscleGroup};
+
 #[derive(Debug, Clone, Deserialize, Serialize, TryFromPrimitive, IntoPrimitive, PartialEq)]
 #[repr(i32)]
 #[cfg_attr(not(target_arch = "wasm32"), derive(EnumIter, DeriveActiveEnum))]
@@ -19,6 +21,108 @@ pub enum ExerciseKind {
     Weighted = 0,
     #[cfg_attr(not(target_arch = "wasm32"), sea_orm(num_value = 1))]
     Bodyweight = 1,
+    #[cfg_attr(not(target_arch = "wasm32"), sea_orm(num_value = 2))]
+    Timed = 2,
+    #[cfg_attr(not(target_arch = "wasm32"), sea_orm(num_value = 3))]
+    Distance = 3,
+    #[cfg_attr(not(target_arch = "wasm32"), sea_orm(num_value = 4))]
+    WeightedBodyweight = 4,
+}
+
+impl ExerciseKind {
+    pub const ALL: [ExerciseKind; 5] = [
+        ExerciseKind::Weighted,
+        ExerciseKind::Bodyweight,
+        ExerciseKind::Timed,
+        ExerciseKind::Distance,
+        ExerciseKind::WeightedBodyweight,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ExerciseKind::Weighted => "Weighted",
+            ExerciseKind::Bodyweight => "Bodyweight",
+            ExerciseKind::Timed => "Timed",
+            ExerciseKind::Distance => "Distance",
+            ExerciseKind::WeightedBodyweight => "Bodyweight + Load",
+        }
+    }
+}
+
+/// How a set was performed. Warm-up sets can be excluded from PRs, graphs and volume.
+#[derive(
+    Debug, Clone, Copy, Default, Deserialize, Serialize, TryFromPrimitive, IntoPrimitive, PartialEq,
+)]
+#[repr(i32)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(EnumIter, DeriveActiveEnum))]
+#[cfg_attr(
+    not(target_arch = "wasm32"),
+    sea_orm(rs_type = "i32", db_type = "Integer")
+)]
+pub enum SetType {
+    #[default]
+    #[cfg_attr(not(target_arch = "wasm32"), sea_orm(num_value = 0))]
+    Working = 0,
+    #[cfg_attr(not(target_arch = "wasm32"), sea_orm(num_value = 1))]
+    Warmup = 1,
+    #[cfg_attr(not(target_arch = "wasm32"), sea_orm(num_value = 2))]
+    Drop = 2,
+    #[cfg_attr(not(target_arch = "wasm32"), sea_orm(num_value = 3))]
+    Failure = 3,
+    #[cfg_attr(not(target_arch = "wasm32"), sea_orm(num_value = 4))]
+    Amrap = 4,
+}
+
+impl SetType {
+    pub const ALL: [SetType; 5] = [
+        SetType::Working,
+        SetType::Warmup,
+        SetType::Drop,
+        SetType::Failure,
+        SetType::Amrap,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SetType::Working => "Working",
+            SetType::Warmup => "Warm-up",
+            SetType::Drop => "Drop set",
+            SetType::Failure => "Failure",
+            SetType::Amrap => "AMRAP",
+        }
+    }
+}
+
+/// Query parameters for endpoints that compute PRs, graphs or volume.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct SetTypeFilter {
+    #[serde(default)]
+    pub exclude_warmup: bool,
+}
+
+/// Query parameters to filter the exercise set history, which is sorted newest first.
+/// The exercise is given either by its name (or one of its aliases) or by its id, and both dates are inclusive
+/// days of the user, see [`UtcOffsetParam`](crate::models::UtcOffsetParam).
+/// `before_created_at` and `before_id` are the cursor to the next page, which are taken from the last set of
+/// the previous page. Without a `limit` all matching sets are returned.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct ExerciseSetHistoryParams {
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub name_id: Option<i32>,
+    #[serde(default)]
+    pub kind: Option<ExerciseKind>,
+    #[serde(default)]
+    pub from: Option<NaiveDate>,
+    #[serde(default)]
+    pub to: Option<NaiveDate>,
+    #[serde(default)]
+    pub before_created_at: Option<NaiveDateTime>,
+    #[serde(default)]
+    pub before_id: Option<i32>,
+    #[serde(default)]
+    pub limit: Option<u64>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
@@ -28,19 +132,39 @@ pub struct ExerciseName {
     pub kind: ExerciseKind,
 }
 
+/// `rest` is the default rest in seconds the user chose for the exercise, if any.
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
 #[cfg_attr(not(target_arch = "wasm32"), derive(FromQueryResult))]
 pub struct ExerciseNameQuery {
     pub name: String,
     pub kind: ExerciseKind,
     pub last_weight: Option<f64>,
+    pub rest: Option<i32>,
+    pub primary_muscle: Option<MuscleGroup>,
+    pub secondary_muscle: Option<MuscleGroup>,
+    pub equipment: Option<Equipment>,
+    pub movement_pattern: Option<MovementPattern>,
 }
 
+/// Request header with a key the client chooses for a new exercise set or round of a group. A submission that is
+/// sent again with the same key is only stored once, so submissions that might have arrived already can be safely replayed.
+pub const IDEMPOTENCY_KEY_HEADER: &str = "Idempotency-Key";
+
+pub const IDEMPOTENCY_KEY_MAX_LEN: usize = 64;
+
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
 pub struct ExerciseSetWeighted {
     pub name: String,
     pub reps: i32,
     pub weight: f64,
+    #[serde(default)]
+    pub rpe: Option<f64>,
+    #[serde(default)]
+    pub rir: Option<i32>,
+    #[serde(default)]
+    pub set_type: SetType,
+    #[serde(default)]
+    pub note: Option<String>,
     pub created_at: String,
 }
 
@@ -48,6 +172,59 @@ pub struct ExerciseSetWeighted {
 pub struct ExerciseSetBodyweight {
     pub name: String,
     pub reps: i32,
+    #[serde(default)]
+    pub rpe: Option<f64>,
+    #[serde(default)]
+    pub rir: Option<i32>,
+    #[serde(default)]
+    pub set_type: SetType,
+    #[serde(default)]
+    pub note: Option<String>,
+    pub created_at: String,
+}
+
+/// A set that is measured by how long a position is held, e.g. planks or dead hangs.
+/// `time` is given in seconds.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct ExerciseSetTimed {
+    pub name: String,
+    pub time: i32,
+    #[serde(default)]
+    pub set_type: SetType,
+    #[serde(default)]
+    pub note: Option<String>,
+    pub created_at: String,
+}
+
+/// A set that covers a distance like a run or a row.
+/// `distance` is given in meters and `time` in seconds.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct ExerciseSetDistance {
+    pub name: String,
+    pub distance: f64,
+    pub time: i32,
+    #[serde(default)]
+    pub set_type: SetType,
+    #[serde(default)]
+    pub note: Option<String>,
+    pub created_at: String,
+}
+
+/// A bodyweight set with additional load like weighted pull-ups, or with assistance like band-assisted pull-ups.
+/// `weight` is the added load in kg, which is negative for assistance.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct ExerciseSetWeightedBodyweight {
+    pub name: String,
+    pub reps: i32,
+    pub weight: f64,
+    #[serde(default)]
+    pub rpe: Option<f64>,
+    #[serde(default)]
+    pub rir: Option<i32>,
+    #[serde(default)]
+    pub set_type: SetType,
+    #[serde(default)]
+    pub note: Option<String>,
     pub created_at: String,
 }
 
@@ -55,6 +232,26 @@ pub struct ExerciseSetBodyweight {
 pub enum ExerciseSet {
     Weighted(ExerciseSetWeighted),
     Bodyweight(ExerciseSetBodyweight),
+    Timed(ExerciseSetTimed),
+    Distance(ExerciseSetDistance),
+    WeightedBodyweight(ExerciseSetWeightedBodyweight),
+}
+
+/// One round of a superset or circuit. Without a `group_id` a new group is started,
+/// otherwise the sets are added to the existing group.
+/// A round that was logged before the id of its group was known instead refers to the group by the
+/// idempotency key of the round that started it, in `group_key`.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct ExerciseSetGroup {
+    pub group_id: Option<i32>,
+    #[serde(default)]
+    pub group_key: Option<String>,
+    pub sets: Vec<ExerciseSet>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct ExerciseSetGroupId {
+    pub group_id: i32,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, From, PartialEq)]
@@ -62,6 +259,13 @@ pub struct ExerciseSetDelete {
     pub id: i32,
 }
 
+/// Replaces the set with id `id`. The kind of a set cannot be changed.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct ExerciseSetUpdate {
+    pub id: i32,
+    pub set: ExerciseSet,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
 #[cfg_attr(not(target_arch = "wasm32"), derive(FromQueryResult))]
 pub struct ExerciseSetJoinQuery {
@@ -73,7 +277,17 @@ pub struct ExerciseSetJoinQuery {
     pub reps: Option<i32>,
     pub reps_sum: Option<i64>,
     pub weight: Option<f64>,
+    pub time: Option<i32>,
+    pub distance: Option<f64>,
+    pub bodyweight: Option<f64>,
+    pub rpe: Option<f64>,
+    pub rir: Option<i32>,
+    pub group_id: Option<i32>,
+    pub set_type: SetType,
+    pub note: Option<String>,
+    pub rest: Option<i32>,
     pub created_at: chrono::NaiveDateTime,
+    pub deleted_at: Option<chrono::NaiveDateTime>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
@@ -86,6 +300,12 @@ pub struct ExerciseSetWeightedQuery {
     pub reps: i32,
     pub reps_sum: i64,
     pub weight: f64,
+    pub rpe: Option<f64>,
+    pub rir: Option<i32>,
+    pub group_id: Option<i32>,
+    pub set_type: SetType,
+    pub note: Option<String>,
+    pub rest: Option<i32>,
     pub created_at: chrono::NaiveDateTime,
 }
 
@@ -98,6 +318,64 @@ pub struct ExerciseSetBodyweightQuery {
     pub name: String,
     pub reps: i32,
     pub reps_sum: i64,
+    pub rpe: Option<f64>,
+    pub rir: Option<i32>,
+    pub group_id: Option<i32>,
+    pub set_type: SetType,
+    pub note: Option<String>,
+    pub rest: Option<i32>,
+    pub created_at: chrono::NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(FromQueryResult))]
+pub struct ExerciseSetTimedQuery {
+    pub id: i32,
+    pub user_id: i32,
+    pub name_id: i32,
+    pub name: String,
+    pub time: i32,
+    pub group_id: Option<i32>,
+    pub set_type: SetType,
+    pub note: Option<String>,
+    pub rest: Option<i32>,
+    pub created_at: chrono::NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(FromQueryResult))]
+pub struct ExerciseSetDistanceQuery {
+    pub id: i32,
+    pub user_id: i32,
+    pub name_id: i32,
+    pub name: String,
+    pub distance: f64,
+    pub time: i32,
+    pub group_id: Option<i32>,
+    pub set_type: SetType,
+    pub note: Option<String>,
+    pub rest: Option<i32>,
+    pub created_at: chrono::NaiveDateTime,
+}
+
+/// `bodyweight` is the last bodyweight the user recorded before the set, if any.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(FromQueryResult))]
+pub struct ExerciseSetWeightedBodyweightQuery {
+    pub id: i32,
+    pub user_id: i32,
+    pub name_id: i32,
+    pub name: String,
+    pub reps: i32,
+    pub reps_sum: i64,
+    pub weight: f64,
+    pub bodyweight: Option<f64>,
+    pub rpe: Option<f64>,
+    pub rir: Option<i32>,
+    pub group_id: Option<i32>,
+    pub set_type: SetType,
+    pub note: Option<String>,
+    pub rest: Option<i32>,
     pub created_at: chrono::NaiveDateTime,
 }
 
@@ -105,6 +383,9 @@ pub struct ExerciseSetBodyweightQuery {
 pub enum ExerciseSetQuery {
     Weighted(ExerciseSetWeightedQuery),
     Bodyweight(ExerciseSetBodyweightQuery),
+    Timed(ExerciseSetTimedQuery),
+    Distance(ExerciseSetDistanceQuery),
+    WeightedBodyweight(ExerciseSetWeightedBodyweightQuery),
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
@@ -130,12 +411,50 @@ pub struct ExercisePrBodyweightQuery {
     pub created_at: chrono::NaiveDateTime,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(FromQueryResult))]
+pub struct ExercisePrTimedQuery {
+    pub id: i32,
+    pub user_id: i32,
+    pub name_id: i32,
+    pub name: String,
+    pub time: i32,
+    pub created_at: chrono::NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(FromQueryResult))]
+pub struct ExercisePrDistanceQuery {
+    pub id: i32,
+    pub user_id: i32,
+    pub name_id: i32,
+    pub name: String,
+    pub distance: f64,
+    pub time: i32,
+    pub created_at: chrono::NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(FromQueryResult))]
+pub struct ExercisePrWeightedBodyweightQuery {
+    pub id: i32,
+    pub user_id: i32,
+    pub name_id: i32,
+    pub name: String,
+    pub reps: i32,
+    pub weight: f64,
+    pub bodyweight: Option<f64>,
+    pub created_at: chrono::NaiveDateTime,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
 #[cfg_attr(not(target_arch = "wasm32"), derive(FromQueryResult))]
 pub struct ExerciseGraphJoinQuery {
     pub name: String,
+    pub kind: ExerciseKind,
     pub reps: i32,
     pub weight: f64,
+    pub bodyweight: Option<f64>,
     pub created_at: chrono::NaiveDateTime,
 }
 
@@ -143,6 +462,7 @@ pub struct ExerciseGraphJoinQuery {
 pub struct ExerciseGraphQueryPerDate {
     pub date: chrono::NaiveDate,
     pub weights: Vec<(f64, i32)>,
+    pub e1rm: Option<f64>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, From, PartialEq)]
@@ -151,10 +471,46 @@ pub struct ExerciseGraphQuery {
     pub per_date: Vec<ExerciseGraphQueryPerDate>,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(FromQueryResult))]
+pub struct ExercisePaceGraphJoinQuery {
+    pub name: String,
+    pub distance: f64,
+    pub time: i32,
+    pub created_at: chrono::NaiveDateTime,
+}
+
+/// `paces` contains a pair of (pace in seconds per kilometer, distance in meters) for each set of the day.
+#[derive(Debug, Clone, Deserialize, Serialize, From, PartialEq)]
+pub struct ExercisePaceGraphQueryPerDate {
+    pub date: chrono::NaiveDate,
+    pub paces: Vec<(f64, f64)>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, From, PartialEq)]
+pub struct ExercisePaceGraphQuery {
+    pub name: String,
+    pub per_date: Vec<ExercisePaceGraphQueryPerDate>,
+}
+
+/// Rep maxes are tracked for 1 up to this many reps.
+pub const REP_MAX_REPS: i32 = 15;
+
+/// The heaviest weight lifted for exactly `reps` reps and when it was first lifted.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct RepMax {
+    pub reps: i32,
+    pub weight: f64,
+    pub created_at: chrono::NaiveDateTime,
+}
+
+/// `rep_maxes` is sorted by reps and only contains rep counts that were logged.
+/// `e1rm` is the best estimated one-rep max over all sets of the exercise.
 #[derive(Debug, Clone, Deserialize, Serialize, From, PartialEq)]
 pub struct PRWeightedQuery {
     pub name: String,
-    pub pr: Vec<(f64, i32)>,
+    pub rep_maxes: Vec<RepMax>,
+    pub e1rm: Option<f64>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, From, PartialEq)]
@@ -163,10 +519,67 @@ pub struct PRBodyweightQuery {
     pub pr: Vec<i32>,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize, From, PartialEq)]
+pub struct PRTimedQuery {
+    pub name: String,
+    pub pr: Vec<i32>,
+}
+
+/// `fastest` contains a triple of (distance in meters, best time in seconds, estimated) for each standard distance
+/// that was covered in a single set. A time is estimated if it comes from the average pace of a longer set.
+#[derive(Debug, Clone, Deserialize, Serialize, From, PartialEq)]
+pub struct PRDistanceQuery {
+    pub name: String,
+    pub longest: f64,
+    pub fastest: Vec<(f64, i32, bool)>,
+}
+
+/// `pr` contains a triple of (effective load in kg, added weight in kg, reps).
+/// `e1rm` is estimated from the effective load.
+#[derive(Debug, Clone, Deserialize, Serialize, From, PartialEq)]
+pub struct PRWeightedBodyweightQuery {
+    pub name: String,
+    pub pr: Vec<(f64, f64, i32)>,
+    pub e1rm: Option<f64>,
+}
+
+/// The records a newly logged set broke, compared to the earlier sets of the exercise.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct NewRecords {
+    /// The heaviest weight.
+    pub weight: bool,
+    /// The most reps at this weight or heavier, or the most reps of a bodyweight exercise.
+    pub reps: bool,
+    pub e1rm: bool,
+    /// The highest volume (weight × reps) of a single set.
+    pub volume: bool,
+}
+
+impl NewRecords {
+    pub fn is_empty(&self) -> bool {
+        !(self.weight || self.reps || self.e1rm || self.volume)
+    }
+
+    pub fn labels(&self) -> Vec<&'static str> {
+        [
+            (self.weight, "weight PR"),
+            (self.reps, "rep PR"),
+            (self.e1rm, "e1RM PR"),
+            (self.volume, "volume PR"),
+        ]
+        .into_iter()
+        .filter_map(|(broken, label)| broken.then_some(label))
+        .collect()
+    }
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, From, PartialEq)]
 pub struct PRQuery {
     pub weighted: Vec<PRWeightedQuery>,
     pub bodyweight: Vec<PRBodyweightQuery>,
+    pub timed: Vec<PRTimedQuery>,
+    pub distance: Vec<PRDistanceQuery>,
+    pub weighted_bodyweight: Vec<PRWeightedBodyweightQuery>,
 }
 
 impl ExerciseSetQuery {
@@ -174,6 +587,9 @@ impl ExerciseSetQuery {
         match self {
             ExerciseSetQuery::Weighted(exs) => &exs.name,
             ExerciseSetQuery::Bodyweight(exs) => &exs.name,
+            ExerciseSetQuery::Timed(exs) => &exs.name,
+            ExerciseSetQuery::Distance(exs) => &exs.name,
+            ExerciseSetQuery::WeightedBodyweight(exs) => &exs.name,
         }
     }
 
@@ -181,8 +597,176 @@ impl ExerciseSetQuery {
         match self {
             ExerciseSetQuery::Weighted(exs) => exs.id,
             ExerciseSetQuery::Bodyweight(exs) => exs.id,
+            ExerciseSetQuery::Timed(exs) => exs.id,
+            ExerciseSetQuery::Distance(exs) => exs.id,
+            ExerciseSetQuery::WeightedBodyweight(exs) => exs.id,
+        }
+    }
+
+    pub fn group_id(&self) -> Option<i32> {
+        match self {
+            ExerciseSetQuery::Weighted(exs) => exs.group_id,
+            ExerciseSetQuery::Bodyweight(exs) => exs.group_id,
+            ExerciseSetQuery::Timed(exs) => exs.group_id,
+            ExerciseSetQuery::Distance(exs) => exs.group_id,
+            ExerciseSetQuery::WeightedBodyweight(exs) => exs.group_id,
+        }
+    }
+
+    pub fn set_type(&self) -> SetType {
+        match self {
+            ExerciseSetQuery::Weighted(exs) => exs.set_type,
+            ExerciseSetQuery::Bodyweight(exs) => exs.set_type,
+            ExerciseSetQuery::Timed(exs) => exs.set_type,
+            ExerciseSetQuery::Distance(exs) => exs.set_type,
+            ExerciseSetQuery::WeightedBodyweight(exs) => exs.set_type,
+        }
+    }
+
+    pub fn created_at(&self) -> NaiveDateTime {
+        match self {
+            ExerciseSetQuery::Weighted(exs) => exs.created_at,
+            ExerciseSetQuery::Bodyweight(exs) => exs.created_at,
+            ExerciseSetQuery::Timed(exs) => exs.created_at,
+            ExerciseSetQuery::Distance(exs) => exs.created_at,
+            ExerciseSetQuery::WeightedBodyweight(exs) => exs.created_at,
+        }
+    }
+
+    pub fn note(&self) -> Option<&str> {
+        match self {
+            ExerciseSetQuery::Weighted(exs) => exs.note.as_deref(),
+            ExerciseSetQuery::Bodyweight(exs) => exs.note.as_deref(),
+            ExerciseSetQuery::Timed(exs) => exs.note.as_deref(),
+            ExerciseSetQuery::Distance(exs) => exs.note.as_deref(),
+            ExerciseSetQuery::WeightedBodyweight(exs) => exs.note.as_deref(),
+        }
+    }
+
+    /// Seconds since the previous set of the same workout session, or of the same day for sets outside of a session.
+    pub fn rest(&self) -> Option<i32> {
+        match self {
+            ExerciseSetQuery::Weighted(exs) => exs.rest,
+            ExerciseSetQuery::Bodyweight(exs) => exs.rest,
+            ExerciseSetQuery::Timed(exs) => exs.rest,
+            ExerciseSetQuery::Distance(exs) => exs.rest,
+            ExerciseSetQuery::WeightedBodyweight(exs) => exs.rest,
+        }
+    }
+}
+
+/// Pace in seconds per kilometer for covering `distance` meters in `time` seconds.
+pub fn pace(distance: f64, time: i32) -> Option<f64> {
+    if distance > 0.0 {
+        Some(time as f64 / (distance / 1000.0))
+    } else {
+        None
+    }
+}
+
+/// Speed in kilometers per hour for covering `distance` meters in `time` seconds.
+pub fn speed(distance: f64, time: i32) -> Option<f64> {
+    if time > 0 {
+        Some((distance / 1000.0) / (time as f64 / 3600.0))
+    } else {
+        None
+    }
+}
+
+/// RPE values that can be recorded, from 6 to 10 in half steps.
+pub const RPE_VALUES: [f64; 9] = [6.0, 6.5, 7.0, 7.5, 8.0, 8.5, 9.0, 9.5, 10.0];
+
+pub fn effort_is_valid(rpe: Option<f64>, rir: Option<i32>) -> bool {
+    match (rpe, rir) {
+        (Some(_), Some(_)) => false,
+        (Some(rpe), None) => RPE_VALUES.contains(&rpe),
+        (None, Some(rir)) => rir >= 0,
+        (None, None) => true,
+    }
+}
+
+/// The load that is actually moved in a weighted bodyweight set.
+pub fn effective_weight(bodyweight: f64, weight: f64) -> f64 {
+    bodyweight + weight
+}
+
+/// Formula to estimate the one-rep max (e1RM) from a set with more reps.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    Deserialize,
+    Serialize,
+    TryFromPrimitive,
+    IntoPrimitive,
+    PartialEq,
+    Eq,
+)]
+#[repr(i32)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(EnumIter, DeriveActiveEnum))]
+#[cfg_attr(
+    not(target_arch = "wasm32"),
+    sea_orm(rs_type = "i32", db_type = "Integer")
+)]
+pub enum E1rmFormula {
+    #[default]
+    #[cfg_attr(not(target_arch = "wasm32"), sea_orm(num_value = 0))]
+    Epley = 0,
+    #[cfg_attr(not(target_arch = "wasm32"), sea_orm(num_value = 1))]
+    Brzycki = 1,
+}
+
+impl E1rmFormula {
+    pub const ALL: [E1rmFormula; 2] = [E1rmFormula::Epley, E1rmFormula::Brzycki];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            E1rmFormula::Epley => "Epley",
+            E1rmFormula::Brzycki => "Brzycki",
         }
     }
+
+    /// The estimated one-rep max for lifting `weight` for `reps` reps. A single rep is its own one-rep max
+    /// and Brzycki is not defined for 37 reps or more.
+    pub fn estimate(&self, weight: f64, reps: i32) -> Option<f64> {
+        if reps <= 0 {
+            return None;
+        }
+        if reps == 1 {
+            return Some(weight);
+        }
+
+        match self {
+            E1rmFormula::Epley => Some(weight * (1.0 + reps as f64 / 30.0)),
+            E1rmFormula::Brzycki if reps < 37 => Some(weight * 36.0 / (37.0 - reps as f64)),
+            E1rmFormula::Brzycki => None,
+        }
+    }
+
+    /// The best estimated one-rep max of (weight, reps) pairs.
+    pub fn best<'a>(&self, sets: impl IntoIterator<Item = &'a (f64, i32)>) -> Option<f64> {
+        sets.into_iter()
+            .filter_map(|(weight, reps)| self.estimate(*weight, *reps))
+            .max_by(|a, b| a.total_cmp(b))
+    }
+}
+
+impl ExerciseSetWeightedBodyweightQuery {
+    pub fn effective_weight(&self) -> Option<f64> {
+        self.bodyweight
+            .map(|bodyweight| effective_weight(bodyweight, self.weight))
+    }
+}
+
+impl ExerciseSetDistanceQuery {
+    pub fn pace(&self) -> Option<f64> {
+        pace(self.distance, self.time)
+    }
+
+    pub fn speed(&self) -> Option<f64> {
+        speed(self.distance, self.time)
+    }
 }
 
 impl ExerciseSet {
@@ -190,6 +774,9 @@ impl ExerciseSet {
         match self {
             ExerciseSet::Weighted(exs) => &exs.name,
             ExerciseSet::Bodyweight(exs) => &exs.name,
+            ExerciseSet::Timed(exs) => &exs.name,
+            ExerciseSet::Distance(exs) => &exs.name,
+            ExerciseSet::WeightedBodyweight(exs) => &exs.name,
         }
     }
 
@@ -197,6 +784,43 @@ impl ExerciseSet {
         match self {
             ExerciseSet::Weighted(_) => ExerciseKind::Weighted,
             ExerciseSet::Bodyweight(_) => ExerciseKind::Bodyweight,
+            ExerciseSet::Timed(_) => ExerciseKind::Timed,
+            ExerciseSet::Distance(_) => ExerciseKind::Distance,
+            ExerciseSet::WeightedBodyweight(_) => ExerciseKind::WeightedBodyweight,
+        }
+    }
+
+    pub fn set_type(&self) -> SetType {
+        match self {
+            ExerciseSet::Weighted(exs) => exs.set_type,
+            ExerciseSet::Bodyweight(exs) => exs.set_type,
+            ExerciseSet::Timed(exs) => exs.set_type,
+            ExerciseSet::Distance(exs) => exs.set_type,
+            ExerciseSet::WeightedBodyweight(exs) => exs.set_type,
+        }
+    }
+
+    /// The note of the set, where a blank note counts as no note.
+    pub fn note(&self) -> Option<&str> {
+        let note = match self {
+            ExerciseSet::Weighted(exs) => &exs.note,
+            ExerciseSet::Bodyweight(exs) => &exs.note,
+            ExerciseSet::Timed(exs) => &exs.note,
+            ExerciseSet::Distance(exs) => &exs.note,
+            ExerciseSet::WeightedBodyweight(exs) => &exs.note,
+        };
+        note.as_deref()
+            .map(|note| note.trim())
+            .filter(|note| !note.is_empty())
+    }
+
+    /// Sets can record their effort either as RPE or as reps in reserve, but not both.
+    pub fn effort_is_valid(&self) -> bool {
+        match self {
+            ExerciseSet::Weighted(exs) => effort_is_valid(exs.rpe, exs.rir),
+            ExerciseSet::Bodyweight(exs) => effort_is_valid(exs.rpe, exs.rir),
+            ExerciseSet::WeightedBodyweight(exs) => effort_is_valid(exs.rpe, exs.rir),
+            ExerciseSet::Timed(_) | ExerciseSet::Distance(_) => true,
         }
     }
 
@@ -204,6 +828,9 @@ impl ExerciseSet {
         let created_at = match self {
             ExerciseSet::Weighted(exs) => &exs.created_at,
             ExerciseSet::Bodyweight(exs) => &exs.created_at,
+            ExerciseSet::Timed(exs) => &exs.created_at,
+            ExerciseSet::Distance(exs) => &exs.created_at,
+            ExerciseSet::WeightedBodyweight(exs) => &exs.created_at,
         };
         let x = NaiveDateTime::parse_from_str(created_at.as_str(), "%Y-%m-%dT%H:%M");
         info!("{:?}", x);
@@ -229,6 +856,12 @@ impl TryFrom<ExerciseSetJoinQuery> for ExerciseSetBodyweightQuery {
             name: value.name,
             reps,
             reps_sum: reps_sum,
+            rpe: value.rpe,
+            rir: value.rir,
+            group_id: value.group_id,
+            set_type: value.set_type,
+            note: value.note,
+            rest: value.rest,
             created_at: value.created_at,
         })
     }
@@ -256,6 +889,96 @@ impl TryFrom<ExerciseSetJoinQuery> for ExerciseSetWeightedQuery {
             reps,
             reps_sum: reps_sum,
             weight,
+            rpe: value.rpe,
+            rir: value.rir,
+            group_id: value.group_id,
+            set_type: value.set_type,
+            note: value.note,
+            rest: value.rest,
+            created_at: value.created_at,
+        })
+    }
+}
+
+impl TryFrom<ExerciseSetJoinQuery> for ExerciseSetTimedQuery {
+    type Error = anyhow::Error;
+
+    fn try_from(value: ExerciseSetJoinQuery) -> Result<Self, Self::Error> {
+        let time = value
+            .time
+            .ok_or(anyhow!("Malformed input. Field `time` not present."))?;
+
+        Ok(Self {
+            id: value.id,
+            user_id: value.user_id,
+            name_id: value.name_id,
+            name: value.name,
+            time,
+            group_id: value.group_id,
+            set_type: value.set_type,
+            note: value.note,
+            rest: value.rest,
+            created_at: value.created_at,
+        })
+    }
+}
+
+impl TryFrom<ExerciseSetJoinQuery> for ExerciseSetDistanceQuery {
+    type Error = anyhow::Error;
+
+    fn try_from(value: ExerciseSetJoinQuery) -> Result<Self, Self::Error> {
+        let distance = value
+            .distance
+            .ok_or(anyhow!("Malformed input. Field `distance` not present."))?;
+        let time = value
+            .time
+            .ok_or(anyhow!("Malformed input. Field `time` not present."))?;
+
+        Ok(Self {
+            id: value.id,
+            user_id: value.user_id,
+            name_id: value.name_id,
+            name: value.name,
+            distance,
+            time,
+            group_id: value.group_id,
+            set_type: value.set_type,
+            note: value.note,
+            rest: value.rest,
+            created_at: value.created_at,
+        })
+    }
+}
+
+impl TryFrom<ExerciseSetJoinQuery> for ExerciseSetWeightedBodyweightQuery {
+    type Error = anyhow::Error;
+
+    fn try_from(value: ExerciseSetJoinQuery) -> Result<Self, Self::Error> {
+        let reps = value
+            .reps
+            .ok_or(anyhow!("Malformed input. Field `reps` not present."))?;
+        let reps_sum = value
+            .reps_sum
+            .ok_or(anyhow!("Malformed input. Field `reps_sum` not present."))?;
+        let weight = value
+            .weight
+            .ok_or(anyhow!("Malformed input. Field `weight` not present."))?;
+
+        Ok(Self {
+            id: value.id,
+            user_id: value.user_id,
+            name_id: value.name_id,
+            name: value.name,
+            reps,
+            reps_sum,
+            weight,
+            bodyweight: value.bodyweight,
+            rpe: value.rpe,
+            rir: value.rir,
+            group_id: value.group_id,
+            set_type: value.set_type,
+            note: value.note,
+            rest: value.rest,
             created_at: value.created_at,
         })
     }
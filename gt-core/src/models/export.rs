@@ -0,0 +1,36 @@
+use chrono::{NaiveDate, NaiveDateTime};
+#[cfg(not(target_arch = "wasm32"))]
+use sea_orm::FromQueryResult;
+use serde::{Deserialize, Serialize};
+
+use crate::models::{ExerciseKind, SetType};
+
+/// Query parameters for the CSV exports. Both dates are inclusive and a missing date leaves the range open.
+/// The dates are days of the user, see [`UtcOffsetParam`](crate::models::UtcOffsetParam).
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct ExportRange {
+    #[serde(default)]
+    pub from: Option<NaiveDate>,
+    #[serde(default)]
+    pub to: Option<NaiveDate>,
+}
+
+/// An exercise set as it is exported, together with the exercise it belongs to.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(FromQueryResult))]
+pub struct ExerciseSetExportQuery {
+    pub id: i32,
+    pub name: String,
+    pub kind: ExerciseKind,
+    pub reps: Option<i32>,
+    pub weight: Option<f64>,
+    pub time: Option<i32>,
+    pub distance: Option<f64>,
+    pub rpe: Option<f64>,
+    pub rir: Option<i32>,
+    pub set_type: SetType,
+    pub session_id: Option<i32>,
+    pub group_id: Option<i32>,
+    pub note: Option<String>,
+    pub created_at: NaiveDateTime,
+}
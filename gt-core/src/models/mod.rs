@@ -1,9 +1,29 @@
 pub mod admin;
+pub mod catalog;
+pub mod date;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod db;
 pub mod exercise;
+pub mod export;
+pub mod program;
+pub mod rest;
+pub mod routine;
+pub mod session;
+pub mod stats;
+pub mod trash;
+pub mod unit;
 pub mod user;
 
 pub use admin::*;
+pub use catalog::*;
+pub use date::*;
 pub use exercise::*;
+pub use export::*;
+pub use program::*;
+pub use rest::*;
+pub use routine::*;
+pub use session::*;
+pub use stats::*;
+pub use trash::*;
+pub use unit::*;
 pub use user::*;
@@ -0,0 +1,116 @@
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+#[cfg(not(target_arch = "wasm32"))]
+use sea_orm::{DeriveActiveEnum, EnumIter, FromQueryResult};
+use serde::{Deserialize, Serialize};
+
+use crate::models::SetType;
+
+/// How the target weight of a program exercise changes from one training day to the next.
+#[derive(
+    Debug, Clone, Copy, Default, Deserialize, Serialize, TryFromPrimitive, IntoPrimitive, PartialEq,
+)]
+#[repr(i32)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(EnumIter, DeriveActiveEnum))]
+#[cfg_attr(
+    not(target_arch = "wasm32"),
+    sea_orm(rs_type = "i32", db_type = "Integer")
+)]
+pub enum ProgressionRule {
+    /// Add `increment` after all sets were completed with `min_reps`.
+    #[default]
+    #[cfg_attr(not(target_arch = "wasm32"), sea_orm(num_value = 0))]
+    Linear = 0,
+    /// Four week cycles based on a training max of `start_weight`, which grows by `increment` per cycle.
+    #[cfg_attr(not(target_arch = "wasm32"), sea_orm(num_value = 1))]
+    FiveThreeOne = 1,
+    /// Work up from `min_reps` to `max_reps`, then add `increment` and start over.
+    #[cfg_attr(not(target_arch = "wasm32"), sea_orm(num_value = 2))]
+    DoubleProgression = 2,
+}
+
+impl ProgressionRule {
+    pub const ALL: [ProgressionRule; 3] = [
+        ProgressionRule::Linear,
+        ProgressionRule::FiveThreeOne,
+        ProgressionRule::DoubleProgression,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ProgressionRule::Linear => "Linear",
+            ProgressionRule::FiveThreeOne => "5/3/1",
+            ProgressionRule::DoubleProgression => "Double progression",
+        }
+    }
+}
+
+/// An exercise of a program as it is submitted when creating or changing a program.
+/// Programs only contain weighted exercises, which are created if they do not exist yet.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct ProgramExercise {
+    pub name: String,
+    pub rule: ProgressionRule,
+    pub sets: i32,
+    pub min_reps: i32,
+    pub max_reps: i32,
+    pub start_weight: f64,
+    pub increment: f64,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct Program {
+    pub name: String,
+    pub exercises: Vec<ProgramExercise>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct ProgramDelete {
+    pub id: i32,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(FromQueryResult))]
+pub struct ProgramExerciseQuery {
+    pub id: i32,
+    pub program_id: i32,
+    pub name_id: i32,
+    pub name: String,
+    pub position: i32,
+    pub rule: ProgressionRule,
+    pub sets: i32,
+    pub min_reps: i32,
+    pub max_reps: i32,
+    pub start_weight: f64,
+    pub increment: f64,
+}
+
+/// Only sets logged after `start_date` count towards the progression of a program.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct ProgramQuery {
+    pub id: i32,
+    pub name: String,
+    pub start_date: chrono::NaiveDateTime,
+    pub exercises: Vec<ProgramExerciseQuery>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct PrescribedSet {
+    pub weight: f64,
+    pub reps: i32,
+    pub set_type: SetType,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct PrescribedExercise {
+    pub name: String,
+    pub rule: ProgressionRule,
+    pub sets: Vec<PrescribedSet>,
+}
+
+/// The sets a program prescribes for today, computed from the sets logged on earlier days.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct PrescribedWorkout {
+    pub program_id: i32,
+    pub name: String,
+    pub exercises: Vec<PrescribedExercise>,
+}
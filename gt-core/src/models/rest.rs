@@ -0,0 +1,33 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::ExerciseNameQuery;
+
+/// The rest in seconds after a set of an exercise for which the user did not choose a default.
+pub const DEFAULT_REST_SECONDS: i32 = 90;
+
+/// The longest rest in seconds that can be chosen as a default.
+pub const MAX_REST_SECONDS: i32 = 60 * 60;
+
+/// Change the default rest after sets of the exercise `name`. Without `rest` the default is removed again.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct ExerciseRest {
+    pub name: String,
+    #[serde(default)]
+    pub rest: Option<i32>,
+}
+
+impl ExerciseRest {
+    pub fn is_valid(&self) -> bool {
+        self.rest
+            .map_or(true, |rest| 0 < rest && rest <= MAX_REST_SECONDS)
+    }
+}
+
+/// The rest after a set of the exercise `name`, which is the default of the user or [`DEFAULT_REST_SECONDS`].
+pub fn rest_for_exercise(name: &str, names: &[ExerciseNameQuery]) -> i32 {
+    names
+        .iter()
+        .find(|exn| exn.name == name)
+        .and_then(|exn| exn.rest)
+        .unwrap_or(DEFAULT_REST_SECONDS)
+}
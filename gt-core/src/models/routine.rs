@@ -0,0 +1,64 @@
+#[cfg(not(target_arch = "wasm32"))]
+use sea_orm::FromQueryResult;
+use serde::{Deserialize, Serialize};
+
+use crate::models::ExerciseKind;
+
+/// An exercise of a routine as it is submitted when creating or changing a routine.
+/// Like for exercise sets, the exercise name is created if it does not exist yet.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct RoutineExercise {
+    pub name: String,
+    pub kind: ExerciseKind,
+    pub target_sets: i32,
+    pub target_reps: Option<i32>,
+    pub target_weight: Option<f64>,
+}
+
+/// The exercises are stored in the order in which they are given.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct Routine {
+    pub name: String,
+    pub exercises: Vec<RoutineExercise>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct RoutineDelete {
+    pub id: i32,
+}
+
+/// `last_weight` and `last_reps` are taken from the last set of the exercise the user logged,
+/// so that a workout started from the routine continues where the last one stopped.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(FromQueryResult))]
+pub struct RoutineExerciseQuery {
+    pub id: i32,
+    pub routine_id: i32,
+    pub name: String,
+    pub kind: ExerciseKind,
+    pub position: i32,
+    pub target_sets: i32,
+    pub target_reps: Option<i32>,
+    pub target_weight: Option<f64>,
+    pub last_weight: Option<f64>,
+    pub last_reps: Option<i32>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct RoutineQuery {
+    pub id: i32,
+    pub name: String,
+    pub exercises: Vec<RoutineExerciseQuery>,
+}
+
+impl RoutineExerciseQuery {
+    /// The weight to pre-fill when logging the exercise.
+    pub fn prefill_weight(&self) -> Option<f64> {
+        self.last_weight.or(self.target_weight)
+    }
+
+    /// The reps to pre-fill when logging the exercise.
+    pub fn prefill_reps(&self) -> Option<i32> {
+        self.last_reps.or(self.target_reps)
+    }
+}
@@ -0,0 +1,40 @@
+use derive_more::From;
+#[cfg(not(target_arch = "wasm32"))]
+use sea_orm::FromQueryResult;
+use serde::{Deserialize, Serialize};
+
+use crate::models::ExerciseSetQuery;
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct WorkoutSessionStart {
+    pub title: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct WorkoutSessionFinish {
+    pub id: i32,
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(FromQueryResult))]
+pub struct WorkoutSessionQuery {
+    pub id: i32,
+    pub title: Option<String>,
+    pub notes: Option<String>,
+    pub start_time: chrono::NaiveDateTime,
+    pub end_time: Option<chrono::NaiveDateTime>,
+    pub set_count: i64,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, From, PartialEq)]
+pub struct WorkoutSessionDetailQuery {
+    pub session: WorkoutSessionQuery,
+    pub sets: Vec<ExerciseSetQuery>,
+}
+
+impl WorkoutSessionQuery {
+    pub fn is_active(&self) -> bool {
+        self.end_time.is_none()
+    }
+}
@@ -0,0 +1,101 @@
+use chrono::NaiveDate;
+#[cfg(not(target_arch = "wasm32"))]
+use sea_orm::FromQueryResult;
+use serde::{Deserialize, Serialize};
+
+use crate::models::{effective_weight, ExerciseKind};
+
+/// Query parameters for the statistics. Both dates are inclusive and a missing date leaves the range open.
+/// The dates are days of the user, see [`UtcOffsetParam`](crate::models::UtcOffsetParam).
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct StatsRange {
+    #[serde(default)]
+    pub from: Option<NaiveDate>,
+    #[serde(default)]
+    pub to: Option<NaiveDate>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(FromQueryResult))]
+pub struct StatsJoinQuery {
+    pub name: String,
+    pub kind: ExerciseKind,
+    pub reps: Option<i32>,
+    pub weight: Option<f64>,
+    pub bodyweight: Option<f64>,
+    pub created_at: chrono::NaiveDateTime,
+}
+
+impl StatsJoinQuery {
+    /// The weight moved per rep, which for sets with added load or assistance is the effective load.
+    /// Other sets without a weight do not count for the tonnage.
+    pub fn load(&self) -> Option<f64> {
+        match self.kind {
+            ExerciseKind::Weighted => self.weight,
+            ExerciseKind::WeightedBodyweight => {
+                Some(effective_weight(self.bodyweight?, self.weight?))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Totals of a week or month, which is identified by its first day.
+/// Weeks start on Monday.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct StatsPeriod {
+    pub start: NaiveDate,
+    pub sets: i64,
+    pub reps: i64,
+    pub tonnage: f64,
+    pub training_days: i64,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct StatsExercise {
+    pub name: String,
+    pub sets: i64,
+    pub reps: i64,
+    pub tonnage: f64,
+}
+
+/// Tonnage is the sum of weight × reps of all weighted sets, where sets with added load or assistance
+/// count with their effective load. Bodyweight sets without added load do not count.
+/// `top_exercises` are the exercises with the most sets and `average_session_minutes` only
+/// counts finished sessions.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct StatsQuery {
+    pub sets: i64,
+    pub reps: i64,
+    pub tonnage: f64,
+    pub training_days: i64,
+    pub per_week: Vec<StatsPeriod>,
+    pub per_month: Vec<StatsPeriod>,
+    pub top_exercises: Vec<StatsExercise>,
+    pub average_session_minutes: Option<f64>,
+}
+
+/// Query parameter for the training calendar. Defaults to the current year of the user.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct CalendarParams {
+    #[serde(default)]
+    pub year: Option<i32>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(FromQueryResult))]
+pub struct CalendarDay {
+    pub date: NaiveDate,
+    pub sets: i64,
+}
+
+/// Number of sets per day of a year, only containing days on which the user trained.
+/// Streaks count consecutive weeks with at least one set, where weeks start on Monday.
+/// The current streak is not broken by a running week without training yet.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct CalendarQuery {
+    pub year: i32,
+    pub days: Vec<CalendarDay>,
+    pub current_streak: i64,
+    pub longest_streak: i64,
+}
@@ -0,0 +1,41 @@
+use chrono::NaiveDateTime;
+#[cfg(not(target_arch = "wasm32"))]
+use sea_orm::FromQueryResult;
+use serde::{Deserialize, Serialize};
+
+use crate::models::ExerciseSetQuery;
+
+/// Deleted items stay in the trash for this many days before they are purged for good.
+pub const TRASH_RETENTION_DAYS: i64 = 30;
+
+/// An item in the trash, identified by its id.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub enum TrashItem {
+    ExerciseSet(i32),
+    UserInfoTs(i32),
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct TrashedExerciseSet {
+    pub exercise_set: ExerciseSetQuery,
+    pub deleted_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(FromQueryResult))]
+pub struct TrashedUserInfoTs {
+    pub id: i32,
+    pub height: Option<f64>,
+    pub weight: Option<f64>,
+    pub muscle_mass: Option<f64>,
+    pub body_fat: Option<f64>,
+    pub created_at: NaiveDateTime,
+    pub deleted_at: NaiveDateTime,
+}
+
+/// The deleted items of a user, most recently deleted first.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct TrashQuery {
+    pub exercise_sets: Vec<TrashedExerciseSet>,
+    pub user_info_ts: Vec<TrashedUserInfoTs>,
+}
@@ -0,0 +1,280 @@
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+#[cfg(not(target_arch = "wasm32"))]
+use sea_orm::{DeriveActiveEnum, EnumIter};
+use serde::{Deserialize, Serialize};
+
+use crate::models::{
+    ExerciseGraphQuery, ExerciseNameQuery, ExerciseSet, ExerciseSetExportQuery, ExerciseSetGroup,
+    ExerciseSetQuery, ExerciseSetUpdate, PRQuery, PrescribedWorkout, Program, ProgramQuery,
+    Routine, RoutineQuery, StatsQuery, TrashQuery, UserInfoQuery, UserInfoTs,
+};
+
+pub const KG_PER_LB: f64 = 0.45359237;
+
+/// The unit in which a user enters and reads weights. Weights are always stored in kg
+/// and only converted when they pass the API.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    Deserialize,
+    Serialize,
+    TryFromPrimitive,
+    IntoPrimitive,
+    PartialEq,
+    Eq,
+)]
+#[repr(i32)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(EnumIter, DeriveActiveEnum))]
+#[cfg_attr(
+    not(target_arch = "wasm32"),
+    sea_orm(rs_type = "i32", db_type = "Integer")
+)]
+pub enum WeightUnit {
+    #[default]
+    #[cfg_attr(not(target_arch = "wasm32"), sea_orm(num_value = 0))]
+    Kg = 0,
+    #[cfg_attr(not(target_arch = "wasm32"), sea_orm(num_value = 1))]
+    Lb = 1,
+}
+
+impl WeightUnit {
+    pub const ALL: [WeightUnit; 2] = [WeightUnit::Kg, WeightUnit::Lb];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            WeightUnit::Kg => "kg",
+            WeightUnit::Lb => "lb",
+        }
+    }
+
+    pub fn to_kg(&self, weight: f64) -> f64 {
+        match self {
+            WeightUnit::Kg => weight,
+            WeightUnit::Lb => weight * KG_PER_LB,
+        }
+    }
+
+    /// Rounded to two decimals so that a weight entered in pounds reads the same when it comes back.
+    pub fn from_kg(&self, weight: f64) -> f64 {
+        match self {
+            WeightUnit::Kg => weight,
+            WeightUnit::Lb => (weight / KG_PER_LB * 100.0).round() / 100.0,
+        }
+    }
+
+    pub fn convert_to_kg<T: ConvertWeight>(&self, mut value: T) -> T {
+        value.convert_weight(&|weight| self.to_kg(weight));
+        value
+    }
+
+    pub fn convert_from_kg<T: ConvertWeight>(&self, mut value: T) -> T {
+        value.convert_weight(&|weight| self.from_kg(weight));
+        value
+    }
+}
+
+/// Query parameter to read or write weights in a unit other than the one the user prefers.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct WeightUnitParam {
+    #[serde(default)]
+    pub unit: Option<WeightUnit>,
+}
+
+/// Applies a conversion to every weight contained in a value.
+pub trait ConvertWeight {
+    fn convert_weight(&mut self, f: &dyn Fn(f64) -> f64);
+}
+
+impl<T: ConvertWeight> ConvertWeight for Vec<T> {
+    fn convert_weight(&mut self, f: &dyn Fn(f64) -> f64) {
+        for value in self.iter_mut() {
+            value.convert_weight(f);
+        }
+    }
+}
+
+impl<T: ConvertWeight> ConvertWeight for Option<T> {
+    fn convert_weight(&mut self, f: &dyn Fn(f64) -> f64) {
+        if let Some(value) = self {
+            value.convert_weight(f);
+        }
+    }
+}
+
+impl ConvertWeight for f64 {
+    fn convert_weight(&mut self, f: &dyn Fn(f64) -> f64) {
+        *self = f(*self);
+    }
+}
+
+impl ConvertWeight for ExerciseSet {
+    fn convert_weight(&mut self, f: &dyn Fn(f64) -> f64) {
+        match self {
+            ExerciseSet::Weighted(exs) => exs.weight.convert_weight(f),
+            ExerciseSet::WeightedBodyweight(exs) => exs.weight.convert_weight(f),
+            ExerciseSet::Bodyweight(_) | ExerciseSet::Timed(_) | ExerciseSet::Distance(_) => {}
+        }
+    }
+}
+
+impl ConvertWeight for ExerciseSetGroup {
+    fn convert_weight(&mut self, f: &dyn Fn(f64) -> f64) {
+        self.sets.convert_weight(f);
+    }
+}
+
+impl ConvertWeight for ExerciseSetUpdate {
+    fn convert_weight(&mut self, f: &dyn Fn(f64) -> f64) {
+        self.set.convert_weight(f);
+    }
+}
+
+impl ConvertWeight for ExerciseSetQuery {
+    fn convert_weight(&mut self, f: &dyn Fn(f64) -> f64) {
+        match self {
+            ExerciseSetQuery::Weighted(exs) => exs.weight.convert_weight(f),
+            ExerciseSetQuery::WeightedBodyweight(exs) => {
+                exs.weight.convert_weight(f);
+                exs.bodyweight.convert_weight(f);
+            }
+            ExerciseSetQuery::Bodyweight(_)
+            | ExerciseSetQuery::Timed(_)
+            | ExerciseSetQuery::Distance(_) => {}
+        }
+    }
+}
+
+impl ConvertWeight for ExerciseNameQuery {
+    fn convert_weight(&mut self, f: &dyn Fn(f64) -> f64) {
+        self.last_weight.convert_weight(f);
+    }
+}
+
+impl ConvertWeight for ExerciseSetExportQuery {
+    fn convert_weight(&mut self, f: &dyn Fn(f64) -> f64) {
+        self.weight.convert_weight(f);
+    }
+}
+
+impl ConvertWeight for PRQuery {
+    fn convert_weight(&mut self, f: &dyn Fn(f64) -> f64) {
+        for pr in self.weighted.iter_mut() {
+            for rep_max in pr.rep_maxes.iter_mut() {
+                rep_max.weight.convert_weight(f);
+            }
+            pr.e1rm.convert_weight(f);
+        }
+        for pr in self.weighted_bodyweight.iter_mut() {
+            for (load, weight, _) in pr.pr.iter_mut() {
+                load.convert_weight(f);
+                weight.convert_weight(f);
+            }
+            pr.e1rm.convert_weight(f);
+        }
+    }
+}
+
+impl ConvertWeight for ExerciseGraphQuery {
+    fn convert_weight(&mut self, f: &dyn Fn(f64) -> f64) {
+        for per_date in self.per_date.iter_mut() {
+            for (weight, _) in per_date.weights.iter_mut() {
+                weight.convert_weight(f);
+            }
+            per_date.e1rm.convert_weight(f);
+        }
+    }
+}
+
+impl ConvertWeight for Routine {
+    fn convert_weight(&mut self, f: &dyn Fn(f64) -> f64) {
+        for exercise in self.exercises.iter_mut() {
+            exercise.target_weight.convert_weight(f);
+        }
+    }
+}
+
+impl ConvertWeight for RoutineQuery {
+    fn convert_weight(&mut self, f: &dyn Fn(f64) -> f64) {
+        for exercise in self.exercises.iter_mut() {
+            exercise.target_weight.convert_weight(f);
+            exercise.last_weight.convert_weight(f);
+        }
+    }
+}
+
+impl ConvertWeight for Program {
+    fn convert_weight(&mut self, f: &dyn Fn(f64) -> f64) {
+        for exercise in self.exercises.iter_mut() {
+            exercise.start_weight.convert_weight(f);
+            exercise.increment.convert_weight(f);
+        }
+    }
+}
+
+impl ConvertWeight for ProgramQuery {
+    fn convert_weight(&mut self, f: &dyn Fn(f64) -> f64) {
+        for exercise in self.exercises.iter_mut() {
+            exercise.start_weight.convert_weight(f);
+            exercise.increment.convert_weight(f);
+        }
+    }
+}
+
+impl ConvertWeight for PrescribedWorkout {
+    fn convert_weight(&mut self, f: &dyn Fn(f64) -> f64) {
+        for exercise in self.exercises.iter_mut() {
+            for set in exercise.sets.iter_mut() {
+                set.weight.convert_weight(f);
+            }
+        }
+    }
+}
+
+impl ConvertWeight for StatsQuery {
+    fn convert_weight(&mut self, f: &dyn Fn(f64) -> f64) {
+        self.tonnage.convert_weight(f);
+        for period in self.per_week.iter_mut().chain(self.per_month.iter_mut()) {
+            period.tonnage.convert_weight(f);
+        }
+        for exercise in self.top_exercises.iter_mut() {
+            exercise.tonnage.convert_weight(f);
+        }
+    }
+}
+
+impl ConvertWeight for TrashQuery {
+    fn convert_weight(&mut self, f: &dyn Fn(f64) -> f64) {
+        for trashed in self.exercise_sets.iter_mut() {
+            trashed.exercise_set.convert_weight(f);
+        }
+        for trashed in self.user_info_ts.iter_mut() {
+            trashed.weight.convert_weight(f);
+            trashed.muscle_mass.convert_weight(f);
+        }
+    }
+}
+
+/// Height and body fat are not weights and are left as they are.
+impl ConvertWeight for UserInfoQuery {
+    fn convert_weight(&mut self, f: &dyn Fn(f64) -> f64) {
+        self.weight.convert_weight(f);
+        self.muscle_mass.convert_weight(f);
+    }
+}
+
+impl ConvertWeight for UserInfoTs {
+    fn convert_weight(&mut self, f: &dyn Fn(f64) -> f64) {
+        self.weight.convert_weight(f);
+        self.muscle_mass.convert_weight(f);
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl ConvertWeight for crate::entities::user_info_ts::Model {
+    fn convert_weight(&mut self, f: &dyn Fn(f64) -> f64) {
+        self.weight.convert_weight(f);
+        self.muscle_mass.convert_weight(f);
+    }
+}
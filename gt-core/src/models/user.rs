@@ -5,6 +5,8 @@ use derive_more::{Deref, From};
 use sea_orm::FromQueryResult;
 use serde::{Deserialize, Serialize};
 
+use crate::models::{E1rmFormula, WeightUnit};
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct UserLogin {
     pub username: String,
@@ -44,6 +46,10 @@ pub struct UserAuth {
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct UserInfo {
     pub display_name: String,
+    #[serde(default)]
+    pub weight_unit: WeightUnit,
+    #[serde(default)]
+    pub e1rm_formula: E1rmFormula,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -54,10 +60,17 @@ pub struct UserInfoTs {
     pub body_fat: Option<f64>,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct UserInfoTsDelete {
+    pub id: i32,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
 #[cfg_attr(not(target_arch = "wasm32"), derive(FromQueryResult))]
 pub struct UserInfoQuery {
     pub display_name: String,
+    pub weight_unit: WeightUnit,
+    pub e1rm_formula: E1rmFormula,
     pub height: Option<f64>,
     pub weight: Option<f64>,
     pub muscle_mass: Option<f64>,
@@ -8,9 +8,25 @@ fn api_url(endpoint: &str) -> String {
 lazy_static! {
     pub static ref EXERCISE_NAME_WEIGHT: String = api_url("/exercise/name/weight");
     pub static ref EXERCISE_NAME: String = api_url("/exercise/name");
+    pub static ref EXERCISE_ALIAS: String = api_url("/exercise/alias");
+    pub static ref EXERCISE_REST: String = api_url("/exercise/rest");
     pub static ref EXERCISE_SET: String = api_url("/exercise/set");
+    pub static ref EXERCISE_SET_GROUP: String = api_url("/exercise/set/group");
     pub static ref EXERCISE_GRAPH: String = api_url("/exercise/graph");
+    pub static ref EXERCISE_GRAPH_PACE: String = api_url("/exercise/graph/pace");
     pub static ref EXERCISE_PR: String = api_url("/exercise/pr");
+    pub static ref EXPORT_SETS: String = api_url("/export/sets.csv");
+    pub static ref EXPORT_BODY: String = api_url("/export/body.csv");
+    pub static ref SESSION: String = api_url("/session");
+    pub static ref SESSION_ACTIVE: String = api_url("/session/active");
+    pub static ref SESSION_START: String = api_url("/session/start");
+    pub static ref SESSION_FINISH: String = api_url("/session/finish");
+    pub static ref STATS: String = api_url("/stats");
+    pub static ref STATS_CALENDAR: String = api_url("/stats/calendar");
+    pub static ref TRASH: String = api_url("/trash");
+    pub static ref TRASH_RESTORE: String = api_url("/trash/restore");
+    pub static ref ROUTINE: String = api_url("/routine");
+    pub static ref PROGRAM: String = api_url("/program");
     pub static ref USER_LOGIN: String = api_url("/user/login");
     pub static ref USER_REGISTER: String = api_url("/user/register");
     pub static ref USER_INFO: String = api_url("/user/info");
@@ -19,4 +35,8 @@ lazy_static! {
     pub static ref AUTH_CHECK: String = api_url("/auth/check");
     pub static ref MERGE_NAMES: String = api_url("/admin/merge-names");
     pub static ref RESET_PASSWORD: String = api_url("/admin/reset-password");
+    pub static ref EXERCISE_NAME_METADATA: String = api_url("/admin/exercise-name");
+    pub static ref ADMIN_EXERCISE_ALIAS: String = api_url("/admin/exercise-alias");
+    pub static ref PRIVATE_EXERCISE_NAMES: String = api_url("/admin/exercise-name/private");
+    pub static ref PROMOTE_EXERCISE_NAME: String = api_url("/admin/exercise-name/promote");
 }
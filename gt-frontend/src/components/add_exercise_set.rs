@@ -5,11 +5,12 @@ use derive_more::Deref;
 use fermi::{use_read, use_atom_state, Atom};
 
 use crate::{
-    api,
     auth::ACTIVE_AUTH_TOKEN,
     messages::UIMessage,
     components as c,
-    request_ext::RequestExt,
+    components::nav::WEIGHT_UNIT,
+    offline_queue::{new_idempotency_key, submit_exercise_set, submit_exercise_set_group, Submission},
+    util::{format_added_weight, format_distance, format_time, format_weight},
 };
 use gt_core::models;
 
@@ -19,10 +20,22 @@ use gt_core::models;
 struct Wrapper1<T>(T);
 #[derive(Deref)]
 struct Wrapper2<T>(T);
+#[derive(Deref)]
+struct Wrapper3<T>(T);
+#[derive(Deref)]
+struct Wrapper4<T>(T);
+#[derive(Deref)]
+struct Wrapper5<T>(T);
+#[derive(Deref)]
+struct Wrapper6<T>(T);
 
 static W_EXERCISE_SET_NAME: Atom<Wrapper1<String>> = |_| Wrapper1("".to_string());
 static W_EXERCISE_SET_WEIGHT: Atom<f64> = |_| 1.0;
 static B_EXERCISE_SET_NAME: Atom<Wrapper2<String>> = |_| Wrapper2("".to_string());
+static T_EXERCISE_SET_NAME: Atom<Wrapper3<String>> = |_| Wrapper3("".to_string());
+static D_EXERCISE_SET_NAME: Atom<Wrapper4<String>> = |_| Wrapper4("".to_string());
+static WB_EXERCISE_SET_NAME: Atom<Wrapper5<String>> = |_| Wrapper5("".to_string());
+static WB_EXERCISE_SET_WEIGHT: Atom<Wrapper6<f64>> = |_| Wrapper6(0.0);
 
 #[derive(Props)]
 pub struct AddExerciseProps<'a> {
@@ -33,9 +46,15 @@ pub struct AddExerciseProps<'a> {
 
 pub fn AddExerciseSetWeighted<'a>(cx: Scope<'a, AddExerciseProps<'a>>) -> Element<'a> {
     let auth_token = use_read(&cx, ACTIVE_AUTH_TOKEN);
+    let unit = *use_read(&cx, WEIGHT_UNIT);
     let w_exercise_set_name = use_atom_state(&cx, W_EXERCISE_SET_NAME);
+    let rest_timer = use_atom_state(&cx, c::REST_TIMER);
     let w_exercise_set_weight = use_atom_state(&cx, W_EXERCISE_SET_WEIGHT);
     let w_exercise_set_reps = use_state(&cx, || 0);
+    let w_exercise_set_rpe = use_state(&cx, || None::<f64>);
+    let w_exercise_set_rir = use_state(&cx, || None::<i32>);
+    let w_exercise_set_type = use_state(&cx, || models::SetType::Working);
+    let w_exercise_set_note = use_state(&cx, || "".to_string());
     let w_exercise_set_date = use_state(&cx, || Local::now().naive_local().format("%Y-%m-%dT%H:%M").to_string());
 
     let names_datalist = cx.props.exercise_names.iter()
@@ -97,7 +116,7 @@ pub fn AddExerciseSetWeighted<'a>(cx: Scope<'a, AddExerciseProps<'a>>) -> Elemen
                     class: "form-group col-12 col-sm-2",
                     label {
                         r#for: "w-exercise-set-weight",
-                        "Weight (kg)"
+                        format!("Weight ({})", unit.label())
                     }
                     input {
                         class: "form-control",
@@ -131,6 +150,19 @@ pub fn AddExerciseSetWeighted<'a>(cx: Scope<'a, AddExerciseProps<'a>>) -> Elemen
                         }
                     }
                 }
+                c::SetTypeSelect {
+                    id: "w-exercise-set-type",
+                    set_type: w_exercise_set_type
+                }
+                c::NoteInput {
+                    id: "w-exercise-set-note",
+                    note: w_exercise_set_note
+                }
+                c::EffortInput {
+                    id: "w-exercise-set",
+                    rpe: w_exercise_set_rpe,
+                    rir: w_exercise_set_rir
+                }
                 div { class: "w-100" }
                 div {
                     class: "form-group col col-sm-auto",
@@ -165,13 +197,12 @@ pub fn AddExerciseSetWeighted<'a>(cx: Scope<'a, AddExerciseProps<'a>>) -> Elemen
                         r#type: "button",
                         class: "col-3 col-sm-1 btn btn-sm btn-outline-success",
                         onclick: move |_| cx.spawn({
-                            to_owned![w_exercise_set_name, w_exercise_set_reps, w_exercise_set_weight, w_exercise_set_date, auth_token];
+                            to_owned![w_exercise_set_name, w_exercise_set_type, w_exercise_set_note, w_exercise_set_rpe, w_exercise_set_rir, w_exercise_set_reps, w_exercise_set_weight, w_exercise_set_date, rest_timer, auth_token];
                             let fetch_names = cx.props.fetch_names.clone();
+                            let exercise_names = cx.props.exercise_names.clone();
                             let display_message = cx.props.display_message.clone();
                             
                             async move {
-                                let client = reqwest::Client::new();
-                                
                                 if !w_exercise_set_name.current().is_empty()
                                 && *w_exercise_set_reps.current() > 0 {
                                     // convert the datetime-local into a utc datetime string
@@ -183,21 +214,26 @@ pub fn AddExerciseSetWeighted<'a>(cx: Scope<'a, AddExerciseProps<'a>>) -> Elemen
                                         name: w_exercise_set_name.current().0.clone(),
                                         reps: *w_exercise_set_reps.current(),
                                         weight: *w_exercise_set_weight.current(),
+                                        rpe: *w_exercise_set_rpe.current(),
+                                        rir: *w_exercise_set_rir.current(),
+                                        set_type: *w_exercise_set_type.current(),
+                                        note: Some(w_exercise_set_note.current().as_ref().clone()),
                                         created_at: created_at.naive_utc().format("%Y-%m-%dT%H:%M").to_string(),
                                     }).into();
 
-                                    let res = client.post(api::EXERCISE_SET.as_str())
-                                        .json(&exs).bearer_auth(auth_token.unwrap_or("".into()))
-                                        .send().await
-                                        .handle_result(UIMessage::error("Submitting exercise failed.".to_string())).await;
+                                    let res = submit_exercise_set(auth_token, exs, unit).await;
 
                                     match res {
-                                        Ok(()) => {
+                                        Ok(submission) => {
+                                            let name = w_exercise_set_name.current().0.clone();
+                                            let rest = models::rest_for_exercise(&name, &exercise_names);
+                                            c::set_rest_timer(&rest_timer, Some(c::RestTimerState::start(name, rest)));
+                                            w_exercise_set_note.set("".to_string());
                                             fetch_names.send(c::main_page::FetchNames);
-                                            display_message.send(UIMessage::info(format!("Added exercise \"{}\" x{} ({}kg)",
+                                            display_message.send(UIMessage::submitted_set(&submission, &w_exercise_set_name.current().0, format!("Added exercise \"{}\" x{} ({})",
                                                 w_exercise_set_name.current().0,
                                                 *w_exercise_set_reps.current(),
-                                                *w_exercise_set_weight.current()
+                                                format_weight(*w_exercise_set_weight.current(), unit)
                                             )));
 
                                             // Reset reps so that you cannot accidentally submit it twice.
@@ -219,8 +255,14 @@ pub fn AddExerciseSetWeighted<'a>(cx: Scope<'a, AddExerciseProps<'a>>) -> Elemen
 
 pub fn AddExerciseSetBodyweight<'a>(cx: Scope<'a, AddExerciseProps<'a>>) -> Element<'a> {
     let auth_token = use_read(&cx, ACTIVE_AUTH_TOKEN);
+    let unit = *use_read(&cx, WEIGHT_UNIT);
     let b_exercise_set_name = use_atom_state(&cx, B_EXERCISE_SET_NAME);
+    let rest_timer = use_atom_state(&cx, c::REST_TIMER);
     let b_exercise_set_reps = use_state(&cx, || 0);
+    let b_exercise_set_rpe = use_state(&cx, || None::<f64>);
+    let b_exercise_set_rir = use_state(&cx, || None::<i32>);
+    let b_exercise_set_type = use_state(&cx, || models::SetType::Working);
+    let b_exercise_set_note = use_state(&cx, || "".to_string());
     let b_exercise_set_date = use_state(&cx, || Local::now().naive_local().format("%Y-%m-%dT%H:%M").to_string());
 
     let names_datalist = cx.props.exercise_names.iter()
@@ -279,6 +321,19 @@ pub fn AddExerciseSetBodyweight<'a>(cx: Scope<'a, AddExerciseProps<'a>>) -> Elem
                         }
                     }
                 }
+                c::SetTypeSelect {
+                    id: "b-exercise-set-type",
+                    set_type: b_exercise_set_type
+                }
+                c::NoteInput {
+                    id: "b-exercise-set-note",
+                    note: b_exercise_set_note
+                }
+                c::EffortInput {
+                    id: "b-exercise-set",
+                    rpe: b_exercise_set_rpe,
+                    rir: b_exercise_set_rir
+                }
                 div { class: "w-100" }
                 div {
                     class: "form-group col col-sm-auto",
@@ -313,13 +368,12 @@ pub fn AddExerciseSetBodyweight<'a>(cx: Scope<'a, AddExerciseProps<'a>>) -> Elem
                         r#type: "button",
                         class: "col-3 col-sm-1 btn btn-sm btn-outline-success",
                         onclick: move |_| cx.spawn({
-                            to_owned![b_exercise_set_name, b_exercise_set_reps, b_exercise_set_date, auth_token];
+                            to_owned![b_exercise_set_name, b_exercise_set_type, b_exercise_set_note, b_exercise_set_rpe, b_exercise_set_rir, b_exercise_set_reps, b_exercise_set_date, rest_timer, auth_token];
                             let fetch_names = cx.props.fetch_names.clone();
+                            let exercise_names = cx.props.exercise_names.clone();
                             let display_message = cx.props.display_message.clone();
                             
                             async move {
-                                let client = reqwest::Client::new();
-                                
                                 if !b_exercise_set_name.current().is_empty() 
                                 && *b_exercise_set_reps.current() > 0 {
                                     // convert the datetime-local into a utc datetime string
@@ -329,18 +383,23 @@ pub fn AddExerciseSetBodyweight<'a>(cx: Scope<'a, AddExerciseProps<'a>>) -> Elem
                                     let exs: models::ExerciseSet = (models::ExerciseSetBodyweight {
                                         name: b_exercise_set_name.current().0.clone(),
                                         reps: *b_exercise_set_reps.current(),
+                                        rpe: *b_exercise_set_rpe.current(),
+                                        rir: *b_exercise_set_rir.current(),
+                                        set_type: *b_exercise_set_type.current(),
+                                        note: Some(b_exercise_set_note.current().as_ref().clone()),
                                         created_at: created_at.naive_utc().format("%Y-%m-%dT%H:%M").to_string(),
                                     }).into();
 
-                                    let res = client.post(api::EXERCISE_SET.as_str())
-                                        .json(&exs).bearer_auth(auth_token.unwrap_or("".into()))
-                                        .send().await
-                                        .handle_result(UIMessage::error("Submitting exercise failed.".to_string())).await;
+                                    let res = submit_exercise_set(auth_token, exs, unit).await;
 
                                     match res {
-                                        Ok(()) => {
+                                        Ok(submission) => {
+                                            let name = b_exercise_set_name.current().0.clone();
+                                            let rest = models::rest_for_exercise(&name, &exercise_names);
+                                            c::set_rest_timer(&rest_timer, Some(c::RestTimerState::start(name, rest)));
+                                            b_exercise_set_note.set("".to_string());
                                             fetch_names.send(c::main_page::FetchNames);
-                                            display_message.send(UIMessage::info(format!("Added exercise \"{}\" x {}",
+                                            display_message.send(UIMessage::submitted_set(&submission, &b_exercise_set_name.current().0, format!("Added exercise \"{}\" x {}",
                                                 b_exercise_set_name.current().0,
                                                 *b_exercise_set_reps.current()
                                             )));
@@ -359,4 +418,890 @@ pub fn AddExerciseSetBodyweight<'a>(cx: Scope<'a, AddExerciseProps<'a>>) -> Elem
             }
         }
     })
+}
+
+pub fn AddExerciseSetTimed<'a>(cx: Scope<'a, AddExerciseProps<'a>>) -> Element<'a> {
+    let auth_token = use_read(&cx, ACTIVE_AUTH_TOKEN);
+    let unit = *use_read(&cx, WEIGHT_UNIT);
+    let t_exercise_set_name = use_atom_state(&cx, T_EXERCISE_SET_NAME);
+    let rest_timer = use_atom_state(&cx, c::REST_TIMER);
+    let t_exercise_set_time = use_state(&cx, || 0);
+    let t_exercise_set_type = use_state(&cx, || models::SetType::Working);
+    let t_exercise_set_note = use_state(&cx, || "".to_string());
+    let t_exercise_set_date = use_state(&cx, || Local::now().naive_local().format("%Y-%m-%dT%H:%M").to_string());
+
+    let names_datalist = cx.props.exercise_names.iter()
+        .filter(|exn| exn.kind == models::ExerciseKind::Timed)
+        .map(|exn| 
+            rsx! { 
+                option { value: exn.name.as_str() }
+            }
+        );
+
+    cx.render(rsx! {
+        div {
+            class: "bg-body-tertiary my-3 p-2",
+            form {
+                class: "row g-1 g-sm-2",
+                p { 
+                    class: "col-12", 
+                    "Timed Exercise Set" 
+                }
+                div {
+                    class: "form-group col-12 col-sm-auto",
+                    label {
+                        r#for: "t-exercise-names",
+                        "Exercise Name"
+                    }
+                    input {
+                        class: "form-control",
+                        id: "t-exercise-names",
+                        list: "t-exercise-names-list",
+                        value: "{t_exercise_set_name.0}",
+                        placeholder: "exercise name",
+                        autocomplete: "off",
+                        oninput: move |evt| t_exercise_set_name.set(Wrapper3(evt.value.clone())),
+                    }
+                    datalist {
+                        id: "t-exercise-names-list",
+                        names_datalist
+                    }
+                }
+                div {
+                    class: "form-group col-12 col-sm-2",
+                    label {
+                        r#for: "t-exercise-set-time",
+                        "Time (s)"
+                    }
+                    input {
+                        class: "form-control",
+                        id: "t-exercise-set-time",
+                        r#type: "number",
+                        min: "0",
+                        value: "{t_exercise_set_time}",
+                        oninput: move |evt| {
+                            if let Ok(v) = evt.value.parse() {
+                                t_exercise_set_time.set(v)
+                            }
+                        }
+                    }
+                }
+                c::SetTypeSelect {
+                    id: "t-exercise-set-type",
+                    set_type: t_exercise_set_type
+                }
+                c::NoteInput {
+                    id: "t-exercise-set-note",
+                    note: t_exercise_set_note
+                }
+                div { class: "w-100" }
+                div {
+                    class: "form-group col col-sm-auto",
+                    label {
+                        r#for: "t-ecercise-set-date",
+                        "Date"
+                    }
+                    input {
+                        class: "form-control",
+                        id: "t-exercise-set-date",
+                        r#type: "datetime-local",
+                        value: "{t_exercise_set_date}",
+                        oninput: move |evt| {
+                            t_exercise_set_date.set(evt.value.clone())
+                        }
+                    }
+                }
+                div {
+                    class: "col-auto d-flex align-items-end",
+                    button {
+                        r#type: "button",
+                        class: "btn btn-outline-info",
+                        onclick: move |_| {
+                            t_exercise_set_date.set(Local::now().naive_local().format("%Y-%m-%dT%H:%M").to_string())
+                        },
+                        "🕒"
+                    }
+                }
+                div { class: "w-100" }
+                div {
+                    button {
+                        r#type: "button",
+                        class: "col-3 col-sm-1 btn btn-sm btn-outline-success",
+                        onclick: move |_| cx.spawn({
+                            to_owned![t_exercise_set_name, t_exercise_set_type, t_exercise_set_note, t_exercise_set_time, t_exercise_set_date, rest_timer, auth_token];
+                            let fetch_names = cx.props.fetch_names.clone();
+                            let exercise_names = cx.props.exercise_names.clone();
+                            let display_message = cx.props.display_message.clone();
+                            
+                            async move {
+                                if !t_exercise_set_name.current().is_empty() 
+                                && *t_exercise_set_time.current() > 0 {
+                                    // convert the datetime-local into a utc datetime string
+                                    let created_at = NaiveDateTime::parse_from_str(t_exercise_set_date.current().as_str(), "%Y-%m-%dT%H:%M").unwrap();
+                                    let created_at = Local.from_local_datetime(&created_at).unwrap();
+
+                                    let exs: models::ExerciseSet = (models::ExerciseSetTimed {
+                                        name: t_exercise_set_name.current().0.clone(),
+                                        time: *t_exercise_set_time.current(),
+                                        set_type: *t_exercise_set_type.current(),
+                                        note: Some(t_exercise_set_note.current().as_ref().clone()),
+                                        created_at: created_at.naive_utc().format("%Y-%m-%dT%H:%M").to_string(),
+                                    }).into();
+
+                                    let res = submit_exercise_set(auth_token, exs, unit).await;
+
+                                    match res {
+                                        Ok(submission) => {
+                                            let name = t_exercise_set_name.current().0.clone();
+                                            let rest = models::rest_for_exercise(&name, &exercise_names);
+                                            c::set_rest_timer(&rest_timer, Some(c::RestTimerState::start(name, rest)));
+                                            t_exercise_set_note.set("".to_string());
+                                            fetch_names.send(c::main_page::FetchNames);
+                                            display_message.send(UIMessage::submitted_set(&submission, &t_exercise_set_name.current().0, format!("Added exercise \"{}\" for {}",
+                                                t_exercise_set_name.current().0,
+                                                format_time(*t_exercise_set_time.current())
+                                            )));
+
+                                            // Reset time so that you cannot accidentally submit it twice.
+                                            t_exercise_set_time.set(0);
+                                        }
+                                        Err(e) => display_message.send(e)
+                                    }
+                                }
+                            }
+                        }),
+                        "+"
+                    }
+                }
+            }
+        }
+    })
+}
+
+pub fn AddExerciseSetDistance<'a>(cx: Scope<'a, AddExerciseProps<'a>>) -> Element<'a> {
+    let auth_token = use_read(&cx, ACTIVE_AUTH_TOKEN);
+    let unit = *use_read(&cx, WEIGHT_UNIT);
+    let d_exercise_set_name = use_atom_state(&cx, D_EXERCISE_SET_NAME);
+    let rest_timer = use_atom_state(&cx, c::REST_TIMER);
+    let d_exercise_set_distance = use_state(&cx, || 0.0);
+    let d_exercise_set_minutes = use_state(&cx, || 0);
+    let d_exercise_set_seconds = use_state(&cx, || 0);
+    let d_exercise_set_type = use_state(&cx, || models::SetType::Working);
+    let d_exercise_set_note = use_state(&cx, || "".to_string());
+    let d_exercise_set_date = use_state(&cx, || Local::now().naive_local().format("%Y-%m-%dT%H:%M").to_string());
+
+    let names_datalist = cx.props.exercise_names.iter()
+        .filter(|exn| exn.kind == models::ExerciseKind::Distance)
+        .map(|exn| 
+            rsx! { 
+                option { value: exn.name.as_str() }
+            }
+        );
+
+    cx.render(rsx! {
+        div {
+            class: "bg-body-tertiary my-3 p-2",
+            form {
+                class: "row g-1 g-sm-2",
+                p { 
+                    class: "col-12", 
+                    "Distance Exercise Set" 
+                }
+                div {
+                    class: "form-group col-12 col-sm-auto",
+                    label {
+                        r#for: "d-exercise-names",
+                        "Exercise Name"
+                    }
+                    input {
+                        class: "form-control",
+                        id: "d-exercise-names",
+                        list: "d-exercise-names-list",
+                        value: "{d_exercise_set_name.0}",
+                        placeholder: "exercise name",
+                        autocomplete: "off",
+                        oninput: move |evt| d_exercise_set_name.set(Wrapper4(evt.value.clone())),
+                    }
+                    datalist {
+                        id: "d-exercise-names-list",
+                        names_datalist
+                    }
+                }
+                div {
+                    class: "form-group col-12 col-sm-2",
+                    label {
+                        r#for: "d-exercise-set-distance",
+                        "Distance (km)"
+                    }
+                    input {
+                        class: "form-control",
+                        id: "d-exercise-set-distance",
+                        r#type: "number",
+                        min: "0",
+                        step: "any",
+                        value: "{d_exercise_set_distance}",
+                        oninput: move |evt| {
+                            if let Ok(v) = evt.value.parse() {
+                                d_exercise_set_distance.set(v)
+                            }
+                        }
+                    }
+                }
+                div {
+                    class: "form-group col-6 col-sm-2",
+                    label {
+                        r#for: "d-exercise-set-minutes",
+                        "Time (min)"
+                    }
+                    input {
+                        class: "form-control",
+                        id: "d-exercise-set-minutes",
+                        r#type: "number",
+                        min: "0",
+                        value: "{d_exercise_set_minutes}",
+                        oninput: move |evt| {
+                            if let Ok(v) = evt.value.parse() {
+                                d_exercise_set_minutes.set(v)
+                            }
+                        }
+                    }
+                }
+                div {
+                    class: "form-group col-6 col-sm-2",
+                    label {
+                        r#for: "d-exercise-set-seconds",
+                        "(s)"
+                    }
+                    input {
+                        class: "form-control",
+                        id: "d-exercise-set-seconds",
+                        r#type: "number",
+                        min: "0",
+                        max: "59",
+                        value: "{d_exercise_set_seconds}",
+                        oninput: move |evt| {
+                            if let Ok(v) = evt.value.parse() {
+                                d_exercise_set_seconds.set(v)
+                            }
+                        }
+                    }
+                }
+                c::SetTypeSelect {
+                    id: "d-exercise-set-type",
+                    set_type: d_exercise_set_type
+                }
+                c::NoteInput {
+                    id: "d-exercise-set-note",
+                    note: d_exercise_set_note
+                }
+                div { class: "w-100" }
+                div {
+                    class: "form-group col col-sm-auto",
+                    label {
+                        r#for: "d-ecercise-set-date",
+                        "Date"
+                    }
+                    input {
+                        class: "form-control",
+                        id: "d-exercise-set-date",
+                        r#type: "datetime-local",
+                        value: "{d_exercise_set_date}",
+                        oninput: move |evt| {
+                            d_exercise_set_date.set(evt.value.clone())
+                        }
+                    }
+                }
+                div {
+                    class: "col-auto d-flex align-items-end",
+                    button {
+                        r#type: "button",
+                        class: "btn btn-outline-info",
+                        onclick: move |_| {
+                            d_exercise_set_date.set(Local::now().naive_local().format("%Y-%m-%dT%H:%M").to_string())
+                        },
+                        "🕒"
+                    }
+                }
+                div { class: "w-100" }
+                div {
+                    button {
+                        r#type: "button",
+                        class: "col-3 col-sm-1 btn btn-sm btn-outline-success",
+                        onclick: move |_| cx.spawn({
+                            to_owned![d_exercise_set_name, d_exercise_set_type, d_exercise_set_note, d_exercise_set_distance, d_exercise_set_minutes, d_exercise_set_seconds, d_exercise_set_date, rest_timer, auth_token];
+                            let fetch_names = cx.props.fetch_names.clone();
+                            let exercise_names = cx.props.exercise_names.clone();
+                            let display_message = cx.props.display_message.clone();
+                            
+                            async move {
+                                // the form takes kilometers but distances are stored in meters
+                                let distance = *d_exercise_set_distance.current() * 1000.0;
+                                let time = *d_exercise_set_minutes.current() * 60 + *d_exercise_set_seconds.current();
+                                
+                                if !d_exercise_set_name.current().is_empty() 
+                                && distance > 0.0 && time > 0 {
+                                    // convert the datetime-local into a utc datetime string
+                                    let created_at = NaiveDateTime::parse_from_str(d_exercise_set_date.current().as_str(), "%Y-%m-%dT%H:%M").unwrap();
+                                    let created_at = Local.from_local_datetime(&created_at).unwrap();
+
+                                    let exs: models::ExerciseSet = (models::ExerciseSetDistance {
+                                        name: d_exercise_set_name.current().0.clone(),
+                                        distance,
+                                        time,
+                                        set_type: *d_exercise_set_type.current(),
+                                        note: Some(d_exercise_set_note.current().as_ref().clone()),
+                                        created_at: created_at.naive_utc().format("%Y-%m-%dT%H:%M").to_string(),
+                                    }).into();
+
+                                    let res = submit_exercise_set(auth_token, exs, unit).await;
+
+                                    match res {
+                                        Ok(submission) => {
+                                            let name = d_exercise_set_name.current().0.clone();
+                                            let rest = models::rest_for_exercise(&name, &exercise_names);
+                                            c::set_rest_timer(&rest_timer, Some(c::RestTimerState::start(name, rest)));
+                                            d_exercise_set_note.set("".to_string());
+                                            fetch_names.send(c::main_page::FetchNames);
+                                            display_message.send(UIMessage::submitted_set(&submission, &d_exercise_set_name.current().0, format!("Added exercise \"{}\" {} in {}",
+                                                d_exercise_set_name.current().0,
+                                                format_distance(distance),
+                                                format_time(time)
+                                            )));
+
+                                            // Reset the inputs so that you cannot accidentally submit it twice.
+                                            d_exercise_set_distance.set(0.0);
+                                            d_exercise_set_minutes.set(0);
+                                            d_exercise_set_seconds.set(0);
+                                        }
+                                        Err(e) => display_message.send(e)
+                                    }
+                                }
+                            }
+                        }),
+                        "+"
+                    }
+                }
+            }
+        }
+    })
+}
+
+/// Bodyweight exercises with added load (positive weight) or assistance (negative weight).
+pub fn AddExerciseSetWeightedBodyweight<'a>(cx: Scope<'a, AddExerciseProps<'a>>) -> Element<'a> {
+    let auth_token = use_read(&cx, ACTIVE_AUTH_TOKEN);
+    let unit = *use_read(&cx, WEIGHT_UNIT);
+    let wb_exercise_set_name = use_atom_state(&cx, WB_EXERCISE_SET_NAME);
+    let rest_timer = use_atom_state(&cx, c::REST_TIMER);
+    let wb_exercise_set_weight = use_atom_state(&cx, WB_EXERCISE_SET_WEIGHT);
+    let wb_exercise_set_reps = use_state(&cx, || 0);
+    let wb_exercise_set_rpe = use_state(&cx, || None::<f64>);
+    let wb_exercise_set_rir = use_state(&cx, || None::<i32>);
+    let wb_exercise_set_type = use_state(&cx, || models::SetType::Working);
+    let wb_exercise_set_note = use_state(&cx, || "".to_string());
+    let wb_exercise_set_date = use_state(&cx, || Local::now().naive_local().format("%Y-%m-%dT%H:%M").to_string());
+
+    let names_datalist = cx.props.exercise_names.iter()
+        .filter(|exn| exn.kind == models::ExerciseKind::WeightedBodyweight)
+        .map(|exn| 
+            rsx! { 
+                option { value: exn.name.as_str() }
+            }
+        );
+
+    cx.render(rsx! {
+        div {
+            class: "bg-body-tertiary my-3 p-2",
+            form {
+                class: "row g-1 g-sm-2",
+                p { 
+                    class: "col-12",
+                    "Weighted/Assisted Bodyweight Exercise Set" 
+                }
+                div {
+                    class: "form-group col-12 col-sm-auto",
+                    label {
+                        r#for: "wb-exercise-names",
+                        "Exercise Name"
+                    }
+                    input {
+                        class: "form-control",
+                        id: "wb-exercise-names",
+                        list: "wb-exercise-names-list",
+                        value: "{wb_exercise_set_name.0}",
+                        placeholder: "exercise name",
+                        autocomplete: "off",
+                        oninput: move |evt| {
+                            let new_name = evt.value.clone();
+                            if let Some(weight) = cx.props.exercise_names.iter()
+                                .find_map(|exn| {
+                                    if exn.name == new_name {
+                                        exn.last_weight.clone()
+                                    } else {
+                                        None
+                                    }
+                                }) {
+                                wb_exercise_set_weight.set(Wrapper6(weight))
+                            }
+                            
+                            wb_exercise_set_name.set(Wrapper5(new_name))
+                        }
+                    }
+                    datalist {
+                        id: "wb-exercise-names-list",
+                        names_datalist
+                    }
+                }
+                div {
+                    class: "form-group col-12 col-sm-2",
+                    label {
+                        r#for: "wb-exercise-set-weight",
+                        format!("Added weight ({}, negative for assistance)", unit.label())
+                    }
+                    input {
+                        class: "form-control",
+                        id: "wb-exercise-set-weight",
+                        r#type: "number",
+                        value: "{wb_exercise_set_weight.0}",
+                        step: "any",
+                        oninput: move |evt| {
+                            if let Ok(v) = evt.value.parse() {
+                                wb_exercise_set_weight.set(Wrapper6(v))
+                            }
+                        }
+                    }
+                }
+                div {
+                    class: "form-group col-12 col-sm-2",
+                    label {
+                        r#for: "wb-exercise-set-reps",
+                        "Reps"
+                    }
+                    input {
+                        class: "form-control",
+                        id: "wb-exercise-set-reps",
+                        r#type: "number",
+                        min: "0",
+                        value: "{wb_exercise_set_reps}",
+                        oninput: move |evt| {
+                            if let Ok(v) = evt.value.parse() {
+                                wb_exercise_set_reps.set(v)
+                            }
+                        }
+                    }
+                }
+                c::SetTypeSelect {
+                    id: "wb-exercise-set-type",
+                    set_type: wb_exercise_set_type
+                }
+                c::NoteInput {
+                    id: "wb-exercise-set-note",
+                    note: wb_exercise_set_note
+                }
+                c::EffortInput {
+                    id: "wb-exercise-set",
+                    rpe: wb_exercise_set_rpe,
+                    rir: wb_exercise_set_rir
+                }
+                div { class: "w-100" }
+                div {
+                    class: "form-group col col-sm-auto",
+                    label {
+                        r#for: "wb-exercise-set-date",
+                        "Date"
+                    }
+                    input {
+                        class: "form-control",
+                        id: "wb-exercise-set-date",
+                        r#type: "datetime-local",
+                        value: "{wb_exercise_set_date}",
+                        oninput: move |evt| {
+                            wb_exercise_set_date.set(evt.value.clone())
+                        }
+                    }
+                }
+                div {
+                    class: "col-auto d-flex align-items-end",
+                    button {
+                        r#type: "button",
+                        class: "btn btn-outline-info",
+                        onclick: move |_| {
+                            wb_exercise_set_date.set(Local::now().naive_local().format("%Y-%m-%dT%H:%M").to_string())
+                        },
+                        "🕒"
+                    }
+                }
+                div { class: "w-100" }
+                div {
+                    button {
+                        r#type: "button",
+                        class: "col-3 col-sm-1 btn btn-sm btn-outline-success",
+                        onclick: move |_| cx.spawn({
+                            to_owned![wb_exercise_set_name, wb_exercise_set_type, wb_exercise_set_note, wb_exercise_set_rpe, wb_exercise_set_rir, wb_exercise_set_reps, wb_exercise_set_weight, wb_exercise_set_date, rest_timer, auth_token];
+                            let fetch_names = cx.props.fetch_names.clone();
+                            let exercise_names = cx.props.exercise_names.clone();
+                            let display_message = cx.props.display_message.clone();
+                            
+                            async move {
+                                if !wb_exercise_set_name.current().is_empty()
+                                && *wb_exercise_set_reps.current() > 0 {
+                                    // convert the datetime-local into a utc datetime string
+                                    let created_at = NaiveDateTime::parse_from_str(wb_exercise_set_date.current().as_str(), "%Y-%m-%dT%H:%M").unwrap();
+                                    let created_at = Local.from_local_datetime(&created_at).unwrap();
+
+                                    let exs: models::ExerciseSet = (models::ExerciseSetWeightedBodyweight {
+                                        name: wb_exercise_set_name.current().0.clone(),
+                                        reps: *wb_exercise_set_reps.current(),
+                                        weight: wb_exercise_set_weight.current().0,
+                                        rpe: *wb_exercise_set_rpe.current(),
+                                        rir: *wb_exercise_set_rir.current(),
+                                        set_type: *wb_exercise_set_type.current(),
+                                        note: Some(wb_exercise_set_note.current().as_ref().clone()),
+                                        created_at: created_at.naive_utc().format("%Y-%m-%dT%H:%M").to_string(),
+                                    }).into();
+
+                                    let res = submit_exercise_set(auth_token, exs, unit).await;
+
+                                    match res {
+                                        Ok(submission) => {
+                                            let name = wb_exercise_set_name.current().0.clone();
+                                            let rest = models::rest_for_exercise(&name, &exercise_names);
+                                            c::set_rest_timer(&rest_timer, Some(c::RestTimerState::start(name, rest)));
+                                            wb_exercise_set_note.set("".to_string());
+                                            fetch_names.send(c::main_page::FetchNames);
+                                            display_message.send(UIMessage::submitted_set(&submission, &wb_exercise_set_name.current().0, format!("Added exercise \"{}\" x{} (身{})",
+                                                wb_exercise_set_name.current().0,
+                                                *wb_exercise_set_reps.current(),
+                                                format_added_weight(wb_exercise_set_weight.current().0, unit)
+                                            )));
+
+                                            // Reset reps so that you cannot accidentally submit it twice.
+                                            wb_exercise_set_reps.set(0);
+                                        }
+                                        Err(e) => display_message.send(e)
+                                    }
+                                }
+                            }
+                        }),
+                        "+"
+                    }
+                }
+            }
+        }
+    })
+}
+
+/// One exercise of a superset round.
+#[derive(Debug, Clone, Default, PartialEq)]
+struct GroupRow {
+    name: String,
+    weight: f64,
+    reps: i32,
+}
+
+/// Log a whole round of a superset or circuit at once. All rounds share the group id that is returned
+/// for the first round, until a new superset is started.
+pub fn AddExerciseSetGroup<'a>(cx: Scope<'a, AddExerciseProps<'a>>) -> Element<'a> {
+    let auth_token = use_read(&cx, ACTIVE_AUTH_TOKEN);
+    let rest_timer = use_atom_state(&cx, c::REST_TIMER);
+    let unit = *use_read(&cx, WEIGHT_UNIT);
+    let g_rows = use_ref(&cx, || vec![GroupRow::default(), GroupRow::default()]);
+    let g_group_id = use_state(&cx, || None::<i32>);
+    // the key of the round that started the group, while its id is not known because the round is still queued
+    let g_group_key = use_state(&cx, || None::<String>);
+    let g_round = use_state(&cx, || 1);
+    let g_exercise_set_date = use_state(&cx, || Local::now().naive_local().format("%Y-%m-%dT%H:%M").to_string());
+
+    let names_datalist = cx.props.exercise_names.iter()
+        .filter(|exn| matches!(exn.kind, models::ExerciseKind::Weighted | models::ExerciseKind::Bodyweight | models::ExerciseKind::WeightedBodyweight))
+        .map(|exn| 
+            rsx! { 
+                option { value: exn.name.as_str() }
+            }
+        );
+
+    let rows = g_rows.read().clone();
+    let row_inputs = rows.iter().enumerate().map(|(i, row)| {
+        let label = format!("A{}", i + 1);
+        let name_id = format!("g-exercise-names-{}", i);
+        let weight_id = format!("g-exercise-set-weight-{}", i);
+        let reps_id = format!("g-exercise-set-reps-{}", i);
+
+        rsx! {
+            div {
+                class: "form-group col-12 col-sm-auto",
+                label {
+                    r#for: "{name_id}",
+                    "{label}"
+                }
+                input {
+                    class: "form-control",
+                    id: "{name_id}",
+                    list: "g-exercise-names-list",
+                    value: "{row.name}",
+                    placeholder: "exercise name",
+                    autocomplete: "off",
+                    oninput: move |evt| {
+                        let new_name = evt.value.clone();
+                        let mut rows = g_rows.write();
+                        if let Some(weight) = cx.props.exercise_names.iter()
+                            .find_map(|exn| {
+                                if exn.name == new_name {
+                                    exn.last_weight.clone()
+                                } else {
+                                    None
+                                }
+                            }) {
+                            rows[i].weight = weight;
+                        }
+
+                        rows[i].name = new_name;
+                    }
+                }
+            }
+            div {
+                class: "form-group col-6 col-sm-2",
+                label {
+                    r#for: "{weight_id}",
+                    format!("Weight ({})", unit.label())
+                }
+                input {
+                    class: "form-control",
+                    id: "{weight_id}",
+                    r#type: "number",
+                    value: "{row.weight}",
+                    step: "any",
+                    oninput: move |evt| {
+                        if let Ok(v) = evt.value.parse() {
+                            g_rows.write()[i].weight = v;
+                        }
+                    }
+                }
+            }
+            div {
+                class: "form-group col-6 col-sm-2",
+                label {
+                    r#for: "{reps_id}",
+                    "Reps"
+                }
+                input {
+                    class: "form-control",
+                    id: "{reps_id}",
+                    r#type: "number",
+                    min: "0",
+                    value: "{row.reps}",
+                    oninput: move |evt| {
+                        if let Ok(v) = evt.value.parse() {
+                            g_rows.write()[i].reps = v;
+                        }
+                    }
+                }
+            }
+            div { class: "w-100" }
+        }
+    });
+
+    let title = match g_group_id.get() {
+        Some(_) => format!("Superset / Circuit (round {})", g_round),
+        None => "Superset / Circuit".to_string(),
+    };
+
+    cx.render(rsx! {
+        div {
+            class: "bg-body-tertiary my-3 p-2",
+            form {
+                class: "row g-1 g-sm-2",
+                p { 
+                    class: "col-12", 
+                    "{title}"
+                }
+                datalist {
+                    id: "g-exercise-names-list",
+                    names_datalist
+                }
+                row_inputs
+                div {
+                    class: "col-auto",
+                    button {
+                        r#type: "button",
+                        class: "btn btn-sm btn-outline-secondary",
+                        onclick: move |_| g_rows.write().push(GroupRow::default()),
+                        "Add exercise"
+                    }
+                }
+                div {
+                    class: "col-auto",
+                    button {
+                        r#type: "button",
+                        class: "btn btn-sm btn-outline-secondary",
+                        onclick: move |_| {
+                            let mut rows = g_rows.write();
+                            if rows.len() > 2 {
+                                rows.pop();
+                            }
+                        },
+                        "Remove exercise"
+                    }
+                }
+                div { class: "w-100" }
+                div {
+                    class: "form-group col col-sm-auto",
+                    label {
+                        r#for: "g-exercise-set-date",
+                        "Date"
+                    }
+                    input {
+                        class: "form-control",
+                        id: "g-exercise-set-date",
+                        r#type: "datetime-local",
+                        value: "{g_exercise_set_date}",
+                        oninput: move |evt| {
+                            g_exercise_set_date.set(evt.value.clone())
+                        }
+                    }
+                }
+                div {
+                    class: "col-auto d-flex align-items-end",
+                    button {
+                        r#type: "button",
+                        class: "btn btn-outline-info",
+                        onclick: move |_| {
+                            g_exercise_set_date.set(Local::now().naive_local().format("%Y-%m-%dT%H:%M").to_string())
+                        },
+                        "🕒"
+                    }
+                }
+                div { class: "w-100" }
+                div {
+                    class: "col-auto",
+                    button {
+                        r#type: "button",
+                        class: "btn btn-sm btn-outline-success",
+                        onclick: move |_| {
+                            // convert the datetime-local into a utc datetime string
+                            let created_at = NaiveDateTime::parse_from_str(g_exercise_set_date.current().as_str(), "%Y-%m-%dT%H:%M").unwrap();
+                            let created_at = Local.from_local_datetime(&created_at).unwrap()
+                                .naive_utc().format("%Y-%m-%dT%H:%M").to_string();
+
+                            // The kind of each set depends on the exercise. New exercises are weighted.
+                            let sets: Option<Vec<models::ExerciseSet>> = g_rows.read().iter()
+                                .filter(|row| !row.name.is_empty() && row.reps > 0)
+                                .map(|row| {
+                                    let kind = cx.props.exercise_names.iter()
+                                        .find(|exn| exn.name == row.name)
+                                        .map(|exn| exn.kind.clone())
+                                        .unwrap_or(models::ExerciseKind::Weighted);
+
+                                    match kind {
+                                        models::ExerciseKind::Weighted => Some(models::ExerciseSetWeighted {
+                                            name: row.name.clone(),
+                                            reps: row.reps,
+                                            weight: row.weight,
+                                            rpe: None,
+                                            rir: None,
+                                            set_type: models::SetType::Working,
+                                            note: None,
+                                            created_at: created_at.clone(),
+                                        }.into()),
+                                        models::ExerciseKind::Bodyweight => Some(models::ExerciseSetBodyweight {
+                                            name: row.name.clone(),
+                                            reps: row.reps,
+                                            rpe: None,
+                                            rir: None,
+                                            set_type: models::SetType::Working,
+                                            note: None,
+                                            created_at: created_at.clone(),
+                                        }.into()),
+                                        models::ExerciseKind::WeightedBodyweight => Some(models::ExerciseSetWeightedBodyweight {
+                                            name: row.name.clone(),
+                                            reps: row.reps,
+                                            weight: row.weight,
+                                            rpe: None,
+                                            rir: None,
+                                            set_type: models::SetType::Working,
+                                            note: None,
+                                            created_at: created_at.clone(),
+                                        }.into()),
+                                        models::ExerciseKind::Timed | models::ExerciseKind::Distance => None,
+                                    }
+                                })
+                                .collect();
+
+                            let sets = match sets {
+                                Some(sets) if sets.len() > 0 => sets,
+                                Some(_) => return,
+                                None => {
+                                    cx.props.display_message.send(UIMessage::error("Only exercises with reps can be part of a superset.".to_string()));
+                                    return;
+                                }
+                            };
+
+                            // the rest starts after the last exercise of the round
+                            let rest_name = sets.last().map(|exs| exs.name().to_string()).unwrap_or_default();
+                            let rest = models::rest_for_exercise(&rest_name, &cx.props.exercise_names);
+
+                            cx.spawn({
+                                to_owned![g_rows, g_group_id, g_group_key, g_round, rest_timer, auth_token];
+                                let fetch_names = cx.props.fetch_names.clone();
+                                let display_message = cx.props.display_message.clone();
+
+                                async move {
+                                    let key = new_idempotency_key();
+                                    let group = models::ExerciseSetGroup {
+                                        group_id: *g_group_id.current(),
+                                        group_key: (*g_group_key.current()).clone(),
+                                        sets,
+                                    };
+                                    let starts_group = group.group_id.is_none() && group.group_key.is_none();
+
+                                    let res = submit_exercise_set_group(auth_token, key.clone(), group, unit).await;
+
+                                    match res {
+                                        Ok(submission) => {
+                                            c::set_rest_timer(&rest_timer, Some(c::RestTimerState::start(rest_name, rest)));
+                                            fetch_names.send(c::main_page::FetchNames);
+
+                                            match submission {
+                                                Submission::Sent(models::ExerciseSetGroupId { group_id }) => {
+                                                    display_message.send(UIMessage::info(format!("Added round {} of superset", *g_round.current())));
+                                                    g_group_id.set(Some(group_id));
+                                                }
+                                                Submission::Queued => display_message.send(UIMessage::info(format!(
+                                                    "No connection. Round {} of the superset is saved and will be sent when you are back online.",
+                                                    *g_round.current()
+                                                ))),
+                                            }
+                                            if starts_group {
+                                                g_group_key.set(Some(key));
+                                            }
+                                            g_round.set(*g_round.current() + 1);
+
+                                            // Reset reps so that you cannot accidentally submit it twice.
+                                            for row in g_rows.write().iter_mut() {
+                                                row.reps = 0;
+                                            }
+                                        }
+                                        Err(e) => display_message.send(e)
+                                    }
+                                }
+                            })
+                        },
+                        "Log round"
+                    }
+                }
+                div {
+                    class: "col-auto",
+                    button {
+                        r#type: "button",
+                        class: "btn btn-sm btn-outline-secondary",
+                        onclick: move |_| {
+                            g_group_id.set(None);
+                            g_group_key.set(None);
+                            g_round.set(1);
+                        },
+                        "New superset"
+                    }
+                }
+            }
+        }
+    })
 }
\ No newline at end of file
@@ -6,6 +6,7 @@ use gt_core::models;
 use crate::{
     api,
     auth::{self, ACTIVE_AUTH_TOKEN},
+    components as c,
     messages::{MessageProps, UIMessage},
     request_ext::RequestExt,
 };
@@ -14,6 +15,7 @@ fn MergeNames<'a>(cx: Scope<'a, MessageProps<'a>>) -> Element<'a> {
     let auth_token = use_read(&cx, ACTIVE_AUTH_TOKEN);
     let to_delete = use_state(&cx, || "".to_string());
     let to_expand = use_state(&cx, || "".to_string());
+    let keep_alias = use_state(&cx, || false);
 
     cx.render(rsx! {
         div {
@@ -41,10 +43,23 @@ fn MergeNames<'a>(cx: Scope<'a, MessageProps<'a>>) -> Element<'a> {
                 }
             }
             div { class: "w-100" }
+            input {
+                class: "form-check-input",
+                id: "keep-alias",
+                r#type: "checkbox",
+                checked: "{keep_alias}",
+                onclick: move |_| keep_alias.set(!*keep_alias.get()),
+            }
+            label {
+                class: "form-check-label ms-1",
+                r#for: "keep-alias",
+                "Keep deleted name as alias"
+            }
+            div { class: "w-100" }
             button {
                 class: "btn btn-outline-danger",
                 onclick: move |_| cx.spawn({
-                    to_owned![to_delete, to_expand, auth_token];
+                    to_owned![to_delete, to_expand, keep_alias, auth_token];
                     let display_message = cx.props.display_message.clone();
 
                     async move {
@@ -55,6 +70,7 @@ fn MergeNames<'a>(cx: Scope<'a, MessageProps<'a>>) -> Element<'a> {
                             let names = models::MergeNames {
                                 to_delete: (*to_delete.current()).clone(),
                                 to_expand: (*to_expand.current()).clone(),
+                                keep_alias: *keep_alias.current(),
                             };
 
                             let res = client.post(api::MERGE_NAMES.as_str())
@@ -77,6 +93,206 @@ fn MergeNames<'a>(cx: Scope<'a, MessageProps<'a>>) -> Element<'a> {
     })
 }
 
+fn ExerciseAliases<'a>(cx: Scope<'a, MessageProps<'a>>) -> Element<'a> {
+    let auth_token = use_read(&cx, ACTIVE_AUTH_TOKEN);
+    let alias = use_state(&cx, || "".to_string());
+    let name = use_state(&cx, || "".to_string());
+
+    let fetch = use_future(&cx, (), |()| {
+        to_owned![auth_token];
+        let display_message = cx.props.display_message.clone();
+
+        async move {
+            let client = reqwest::Client::new();
+            let res = client
+                .get(api::EXERCISE_ALIAS.as_str())
+                .bearer_auth(auth_token.unwrap_or("".into()))
+                .send()
+                .await
+                .handle_result::<Vec<models::ExerciseAlias>>(UIMessage::error(
+                    "Fetching aliases failed.".to_string(),
+                ))
+                .await;
+
+            match res {
+                Ok(aliases) => aliases,
+                Err(e) => {
+                    display_message.send(e);
+                    vec![]
+                }
+            }
+        }
+    });
+
+    let alias_list = fetch.value().into_iter().flatten().map(|exercise_alias| {
+        let payload = exercise_alias.clone();
+        rsx! {
+            li {
+                "{exercise_alias.alias} → {exercise_alias.name} "
+                button {
+                    class: "btn btn-sm btn-outline-danger",
+                    onclick: move |_| cx.spawn({
+                        to_owned![auth_token, fetch, payload];
+                        let display_message = cx.props.display_message.clone();
+
+                        async move {
+                            let client = reqwest::Client::new();
+
+                            let res = client.delete(api::ADMIN_EXERCISE_ALIAS.as_str())
+                                .json(&payload).bearer_auth(auth_token.unwrap_or("".into()))
+                                .send().await
+                                .handle_result::<()>(UIMessage::error("Deleting alias failed.".to_string())).await;
+
+                            match res {
+                                Ok(()) => fetch.restart(),
+                                Err(e) => display_message.send(e)
+                            }
+                        }
+                    }),
+                    "🗑️"
+                }
+            }
+        }
+    });
+
+    cx.render(rsx! {
+        div {
+            ul { alias_list }
+            label {
+                r#for: "alias",
+                "Alias: "
+            }
+            input {
+                id: "alias",
+                value: "{alias}",
+                oninput: move |evt| {
+                    alias.set(evt.value.clone())
+                }
+            }
+            div { class: "w-100" }
+            label {
+                r#for: "alias-name",
+                "Exercise: "
+            }
+            input {
+                id: "alias-name",
+                value: "{name}",
+                oninput: move |evt| {
+                    name.set(evt.value.clone())
+                }
+            }
+            div { class: "w-100" }
+            button {
+                class: "btn btn-outline-danger",
+                onclick: move |_| cx.spawn({
+                    to_owned![alias, name, fetch, auth_token];
+                    let display_message = cx.props.display_message.clone();
+
+                    async move {
+                        let client = reqwest::Client::new();
+
+                        if !alias.is_empty()
+                        && !name.is_empty() {
+                            let exercise_alias = models::ExerciseAlias {
+                                alias: (*alias.current()).clone(),
+                                name: (*name.current()).clone(),
+                            };
+
+                            let res = client.post(api::ADMIN_EXERCISE_ALIAS.as_str())
+                                .json(&exercise_alias).bearer_auth(auth_token.unwrap_or("".into()))
+                                .send().await
+                                .handle_result::<()>(UIMessage::error("Adding alias failed.".to_string())).await;
+
+                            match res {
+                                Ok(()) => {
+                                    display_message.send(UIMessage::info(format!("Added alias {} for {}.", alias.current(), name.current())));
+                                    fetch.restart();
+                                }
+                                Err(e) => display_message.send(e)
+                            }
+                        }
+                    }
+                }),
+                "Add Alias"
+            }
+        }
+    })
+}
+
+fn PrivateExerciseNames<'a>(cx: Scope<'a, MessageProps<'a>>) -> Element<'a> {
+    let auth_token = use_read(&cx, ACTIVE_AUTH_TOKEN);
+
+    let fetch = use_future(&cx, (), |()| {
+        to_owned![auth_token];
+        let display_message = cx.props.display_message.clone();
+
+        async move {
+            let client = reqwest::Client::new();
+            let res = client
+                .get(api::PRIVATE_EXERCISE_NAMES.as_str())
+                .bearer_auth(auth_token.unwrap_or("".into()))
+                .send()
+                .await
+                .handle_result::<Vec<models::PrivateExerciseNameQuery>>(UIMessage::error(
+                    "Fetching private exercises failed.".to_string(),
+                ))
+                .await;
+
+            match res {
+                Ok(names) => names,
+                Err(e) => {
+                    display_message.send(e);
+                    vec![]
+                }
+            }
+        }
+    });
+
+    let name_list = fetch.value().into_iter().flatten().map(|private_name| {
+        let promote = models::ExerciseNamePromote {
+            name: private_name.name.clone(),
+            username: private_name.username.clone(),
+        };
+        rsx! {
+            li {
+                "{private_name.name} ({private_name.username}) "
+                button {
+                    class: "btn btn-sm btn-outline-danger",
+                    onclick: move |_| cx.spawn({
+                        to_owned![auth_token, fetch, promote];
+                        let display_message = cx.props.display_message.clone();
+
+                        async move {
+                            let client = reqwest::Client::new();
+
+                            let res = client.post(api::PROMOTE_EXERCISE_NAME.as_str())
+                                .json(&promote).bearer_auth(auth_token.unwrap_or("".into()))
+                                .send().await
+                                .handle_result::<()>(UIMessage::error("Promoting exercise failed.".to_string())).await;
+
+                            match res {
+                                Ok(()) => {
+                                    display_message.send(UIMessage::info(format!("Added {} to the catalog.", promote.name)));
+                                    fetch.restart();
+                                }
+                                Err(e) => display_message.send(e)
+                            }
+                        }
+                    }),
+                    "Promote"
+                }
+            }
+        }
+    });
+
+    cx.render(rsx! {
+        div {
+            p { "Private exercises" }
+            ul { name_list }
+        }
+    })
+}
+
 fn ResetPassword<'a>(cx: Scope<'a, MessageProps<'a>>) -> Element<'a> {
     let auth_token = use_read(&cx, ACTIVE_AUTH_TOKEN);
     let username = use_state(&cx, || "".to_string());
@@ -144,12 +360,151 @@ fn ResetPassword<'a>(cx: Scope<'a, MessageProps<'a>>) -> Element<'a> {
     })
 }
 
+fn ExerciseNameMetadataEditor<'a>(cx: Scope<'a, MessageProps<'a>>) -> Element<'a> {
+    let auth_token = use_read(&cx, ACTIVE_AUTH_TOKEN);
+    let exercise_names = use_state(&cx, Vec::<models::ExerciseNameQuery>::new);
+    let name = use_state(&cx, || "".to_string());
+    let primary_muscle = use_state(&cx, || None::<models::MuscleGroup>);
+    let secondary_muscle = use_state(&cx, || None::<models::MuscleGroup>);
+    let equipment = use_state(&cx, || None::<models::Equipment>);
+    let movement_pattern = use_state(&cx, || None::<models::MovementPattern>);
+
+    let fetch_names = use_future(&cx, (), |()| {
+        to_owned![auth_token, exercise_names];
+        let display_message = cx.props.display_message.clone();
+
+        async move {
+            let client = reqwest::Client::new();
+            let res = client
+                .get(api::EXERCISE_NAME.as_str())
+                .bearer_auth(auth_token.unwrap_or("".into()))
+                .send()
+                .await
+                .handle_result::<Vec<models::ExerciseNameQuery>>(UIMessage::error(
+                    "Fetching exercise names failed.".to_string(),
+                ))
+                .await;
+
+            match res {
+                Ok(names) => exercise_names.set(names),
+                Err(e) => display_message.send(e),
+            }
+        }
+    });
+
+    let names_datalist = exercise_names.get().iter().map(|exn| {
+        rsx! {
+            option { value: exn.name.as_str() }
+        }
+    });
+
+    cx.render(rsx! {
+        div {
+            class: "row g-1 g-sm-2 my-3",
+            div {
+                class: "form-group col-12 col-sm-auto",
+                label {
+                    r#for: "metadata-name",
+                    "Exercise: "
+                }
+                input {
+                    class: "form-control",
+                    id: "metadata-name",
+                    list: "metadata-names-list",
+                    autocomplete: "off",
+                    value: "{name}",
+                    oninput: move |evt| {
+                        // Show the current catalog information when an existing exercise is picked.
+                        if let Some(exn) = exercise_names.get().iter().find(|exn| exn.name == evt.value) {
+                            primary_muscle.set(exn.primary_muscle);
+                            secondary_muscle.set(exn.secondary_muscle);
+                            equipment.set(exn.equipment);
+                            movement_pattern.set(exn.movement_pattern);
+                        }
+                        name.set(evt.value.clone())
+                    }
+                }
+                datalist {
+                    id: "metadata-names-list",
+                    names_datalist
+                }
+            }
+            c::CatalogSelect {
+                id: "metadata-primary-muscle",
+                label: "Primary muscle group",
+                value: *primary_muscle.get(),
+                onchange: move |v| primary_muscle.set(v),
+            }
+            c::CatalogSelect {
+                id: "metadata-secondary-muscle",
+                label: "Secondary muscle group",
+                value: *secondary_muscle.get(),
+                onchange: move |v| secondary_muscle.set(v),
+            }
+            c::CatalogSelect {
+                id: "metadata-equipment",
+                label: "Equipment",
+                value: *equipment.get(),
+                onchange: move |v| equipment.set(v),
+            }
+            c::CatalogSelect {
+                id: "metadata-movement-pattern",
+                label: "Movement pattern",
+                value: *movement_pattern.get(),
+                onchange: move |v| movement_pattern.set(v),
+            }
+            div { class: "w-100" }
+            div {
+                class: "col-auto",
+                button {
+                    class: "btn btn-outline-danger",
+                    onclick: move |_| cx.spawn({
+                        to_owned![name, primary_muscle, secondary_muscle, equipment, movement_pattern, fetch_names, auth_token];
+                        let display_message = cx.props.display_message.clone();
+
+                        async move {
+                            let client = reqwest::Client::new();
+
+                            if !name.is_empty() {
+                                let metadata = models::ExerciseNameMetadata {
+                                    name: (*name.current()).clone(),
+                                    primary_muscle: *primary_muscle.current(),
+                                    secondary_muscle: *secondary_muscle.current(),
+                                    equipment: *equipment.current(),
+                                    movement_pattern: *movement_pattern.current(),
+                                };
+
+                                let res = client.put(api::EXERCISE_NAME_METADATA.as_str())
+                                    .json(&metadata).bearer_auth(auth_token.unwrap_or("".into()))
+                                    .send().await
+                                    .handle_result::<()>(UIMessage::error("Updating exercise failed.".to_string())).await;
+
+                                match res {
+                                    Ok(()) => {
+                                        display_message.send(UIMessage::info(format!("Updated {}.", name.current())));
+                                        fetch_names.restart();
+                                    }
+                                    Err(e) => display_message.send(e)
+                                }
+                            }
+                        }
+                    }),
+                    "Save"
+                }
+            }
+        }
+    })
+}
+
 pub fn AdminPage<'a>(cx: Scope<'a, MessageProps<'a>>) -> Element<'a> {
     let auth_token = use_read(&cx, ACTIVE_AUTH_TOKEN);
 
     cx.render(rsx! {
         div {
             MergeNames { display_message: cx.props.display_message },
+            ExerciseAliases { display_message: cx.props.display_message },
+            PrivateExerciseNames { display_message: cx.props.display_message },
+            ExerciseNameMetadataEditor { display_message: cx.props.display_message },
             ResetPassword { display_message: cx.props.display_message }
         }
     })
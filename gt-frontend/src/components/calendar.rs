@@ -0,0 +1,212 @@
+#![allow(non_snake_case)]
+use chrono::{Datelike, Duration, Local, NaiveDate};
+use dioxus::prelude::*;
+use fermi::use_read;
+
+use crate::messages::{MessageProps, UIMessage};
+use crate::request_ext::RequestExt;
+use crate::{api, auth::ACTIVE_AUTH_TOKEN, components as c, util::utc_offset_param};
+use gt_core::models;
+
+/// Number of sets per day at which a day is shown with full intensity.
+const CALENDAR_MAX_SETS: i64 = 25;
+
+const WEEKDAYS: [&str; 7] = ["Mo", "", "We", "", "Fr", "", "Su"];
+
+fn cell_style(sets: i64) -> String {
+    let base = "width: 0.8rem; height: 0.8rem; padding: 0; border: 1px solid var(--bs-body-bg); cursor: pointer;";
+    if sets == 0 {
+        format!("{} background-color: var(--bs-secondary-bg);", base)
+    } else {
+        let alpha = 0.25 + 0.75 * (sets.min(CALENDAR_MAX_SETS) as f64 / CALENDAR_MAX_SETS as f64);
+        format!(
+            "{} background-color: rgba(25, 135, 84, {:.2});",
+            base, alpha
+        )
+    }
+}
+
+/// Heatmap of the sets per day of a year. Selecting a day lists the sets of that day.
+pub fn TrainingCalendar<'a>(cx: Scope<'a, MessageProps<'a>>) -> Element<'a> {
+    let auth_token = use_read(&cx, ACTIVE_AUTH_TOKEN);
+    let year = use_state(&cx, || Local::now().year());
+    let selected_day = use_state(&cx, || None::<NaiveDate>);
+
+    let fetch = use_future(&cx, (*year.get(),), |(year,)| {
+        to_owned![auth_token];
+        let display_message = cx.props.display_message.clone();
+
+        async move {
+            let client = reqwest::Client::new();
+            let res = client
+                .get(api::STATS_CALENDAR.as_str())
+                .query(&models::CalendarParams { year: Some(year) })
+                .query(&utc_offset_param())
+                .bearer_auth(auth_token.unwrap_or("".into()))
+                .send()
+                .await
+                .handle_result::<models::CalendarQuery>(UIMessage::error(
+                    "Fetching training calendar failed.".to_string(),
+                ))
+                .await;
+
+            match res {
+                Ok(calendar) => Some(calendar),
+                Err(e) => {
+                    display_message.send(e);
+                    None
+                }
+            }
+        }
+    });
+
+    let fetch_day = use_future(&cx, (*selected_day.get(),), |(date,)| {
+        to_owned![auth_token];
+        let display_message = cx.props.display_message.clone();
+
+        async move {
+            let date = date?;
+            let client = reqwest::Client::new();
+            let res = client
+                .get(api::EXERCISE_SET.as_str())
+                .query(&models::ExerciseSetHistoryParams {
+                    from: Some(date),
+                    to: Some(date),
+                    ..Default::default()
+                })
+                .query(&utc_offset_param())
+                .bearer_auth(auth_token.unwrap_or("".into()))
+                .send()
+                .await
+                .handle_result::<Vec<models::ExerciseSetQuery>>(UIMessage::error(
+                    "Fetching exercise sets failed.".to_string(),
+                ))
+                .await;
+
+            match res {
+                Ok(sets) => Some(sets),
+                Err(e) => {
+                    display_message.send(e);
+                    None
+                }
+            }
+        }
+    });
+
+    let content = match fetch.value() {
+        Some(Some(calendar)) => {
+            let first_day = NaiveDate::from_ymd_opt(calendar.year, 1, 1).unwrap();
+            let first_week =
+                first_day - Duration::days(first_day.weekday().num_days_from_monday() as i64);
+            let weeks = (0..54)
+                .map(|week| first_week + Duration::weeks(week))
+                .filter(|week| week.year() <= calendar.year)
+                .collect::<Vec<_>>();
+
+            let rows = (0..7).map(move |weekday| {
+                let label = WEEKDAYS[weekday as usize];
+                let cells = weeks.clone().into_iter().map(move |week| {
+                    let date = week + Duration::days(weekday);
+                    if date.year() != calendar.year {
+                        return rsx! {
+                            td { key: "{date}", style: "padding: 0;" }
+                        };
+                    }
+                    let sets = calendar
+                        .days
+                        .iter()
+                        .find(|day| day.date == date)
+                        .map(|day| day.sets)
+                        .unwrap_or(0);
+                    let style = cell_style(sets);
+                    let title = format!("{}: {} sets", date.format("%d.%m.%y"), sets);
+                    rsx! {
+                        td {
+                            key: "{date}",
+                            style: "{style}",
+                            title: "{title}",
+                            onclick: move |_| selected_day.set(Some(date)),
+                        }
+                    }
+                });
+                rsx! {
+                    tr {
+                        key: "{weekday}",
+                        th { class: "fw-light small pe-1", style: "padding: 0;", "{label}" }
+                        cells
+                    }
+                }
+            });
+
+            rsx! {
+                p { "Weekly streak: {calendar.current_streak} (longest {calendar.longest_streak})" }
+                div {
+                    class: "table-responsive",
+                    table {
+                        style: "border-collapse: separate; border-spacing: 2px;",
+                        tbody { rows }
+                    }
+                }
+            }
+        }
+        _ => {
+            rsx! {
+                p { "Loading" }
+            }
+        }
+    };
+
+    let day_content = match (*selected_day.get(), fetch_day.value()) {
+        (Some(date), Some(Some(sets))) => {
+            let date = date.format("%d.%m.%y").to_string();
+            let set_list = sets.iter().map(|exs| {
+                let id = exs.id();
+                rsx! {
+                    li {
+                        key: "{id}",
+                        class: "list-group-item",
+                        c::ExerciseSetInfo { exs: exs }
+                    }
+                }
+            });
+            rsx! {
+                p { "Sets on {date}" }
+                if sets.is_empty() {
+                    rsx! { p { class: "fw-light", "No sets on this day." } }
+                } else {
+                    rsx! {
+                        ul {
+                            class: "list-group list-group-flush",
+                            set_list
+                        }
+                    }
+                }
+            }
+        }
+        _ => rsx! { "" },
+    };
+
+    cx.render(rsx! {
+        div {
+            class: "bg-body-tertiary my-3 p-2",
+            div {
+                class: "d-flex align-items-center gap-2 mb-2",
+                button {
+                    class: "btn btn-sm btn-outline-secondary",
+                    r#type: "button",
+                    onclick: move |_| year.modify(|year| year - 1),
+                    "<"
+                }
+                span { "{year}" }
+                button {
+                    class: "btn btn-sm btn-outline-secondary",
+                    r#type: "button",
+                    onclick: move |_| year.modify(|year| year + 1),
+                    ">"
+                }
+            }
+            content
+            day_content
+        }
+    })
+}
@@ -0,0 +1,123 @@
+#![allow(non_snake_case)]
+use dioxus::prelude::*;
+use fermi::use_read;
+
+use crate::{api, auth::ACTIVE_AUTH_TOKEN, messages::UIMessage, request_ext::RequestExt};
+use gt_core::models::{self, CatalogValue};
+
+#[derive(Props)]
+pub struct CatalogSelectProps<'a, T: CatalogValue> {
+    id: &'a str,
+    label: &'a str,
+    value: Option<T>,
+    onchange: EventHandler<'a, Option<T>>,
+}
+
+/// Selector for one of the catalog properties of an exercise. The empty option stands for "not set".
+pub fn CatalogSelect<'a, T: CatalogValue>(cx: Scope<'a, CatalogSelectProps<'a, T>>) -> Element<'a> {
+    let options = T::ALL.iter().map(|v| {
+        let value: i32 = (*v).into();
+        rsx! {
+            option {
+                value: "{value}",
+                selected: Some(*v) == cx.props.value,
+                v.label()
+            }
+        }
+    });
+
+    cx.render(rsx! {
+        div {
+            class: "form-group col-12 col-sm-auto",
+            label {
+                r#for: cx.props.id,
+                cx.props.label
+            }
+            select {
+                class: "form-select",
+                id: cx.props.id,
+                onchange: move |evt| {
+                    let v = evt
+                        .value
+                        .parse::<i32>()
+                        .ok()
+                        .and_then(|v| T::try_from(v).ok());
+                    cx.props.onchange.call(v)
+                },
+                option {
+                    value: "",
+                    selected: cx.props.value.is_none(),
+                    "-"
+                }
+                options
+            }
+        }
+    })
+}
+
+#[derive(Props)]
+pub struct ExerciseNameFilterSelectProps<'a> {
+    filter: &'a UseState<models::ExerciseNameFilter>,
+}
+
+/// Filter exercises by muscle group, equipment and movement pattern.
+pub fn ExerciseNameFilterSelect<'a>(
+    cx: Scope<'a, ExerciseNameFilterSelectProps<'a>>,
+) -> Element<'a> {
+    let filter = cx.props.filter;
+
+    cx.render(rsx! {
+        CatalogSelect {
+            id: "filter-muscle",
+            label: "Muscle group",
+            value: filter.get().muscle,
+            onchange: move |muscle| filter.modify(|f| models::ExerciseNameFilter { muscle, ..f.clone() }),
+        }
+        CatalogSelect {
+            id: "filter-equipment",
+            label: "Equipment",
+            value: filter.get().equipment,
+            onchange: move |equipment| filter.modify(|f| models::ExerciseNameFilter { equipment, ..f.clone() }),
+        }
+        CatalogSelect {
+            id: "filter-movement-pattern",
+            label: "Movement pattern",
+            value: filter.get().movement_pattern,
+            onchange: move |movement_pattern| filter.modify(|f| models::ExerciseNameFilter { movement_pattern, ..f.clone() }),
+        }
+    })
+}
+
+/// Fetch the exercise names once, which the catalog filter needs to match exercises by their muscle group, equipment etc.
+pub fn use_exercise_names<'a>(
+    cx: &'a ScopeState,
+    display_message: &Coroutine<UIMessage>,
+) -> &'a UseState<Vec<models::ExerciseNameQuery>> {
+    let auth_token = use_read(cx, ACTIVE_AUTH_TOKEN);
+    let exercise_names = use_state(cx, Vec::<models::ExerciseNameQuery>::new);
+
+    use_future(cx, (), |()| {
+        to_owned![auth_token, exercise_names];
+        let display_message = display_message.clone();
+
+        async move {
+            let client = reqwest::Client::new();
+            let res = client
+                .get(api::EXERCISE_NAME.as_str())
+                .bearer_auth(auth_token.unwrap_or("".into()))
+                .send()
+                .await
+                .handle_result::<Vec<models::ExerciseNameQuery>>(UIMessage::error(
+                    "Fetching exercise names failed.".to_string(),
+                ))
+                .await;
+
+            match res {
+                Ok(names) => exercise_names.set(names),
+                Err(e) => display_message.send(e),
+            }
+        }
+    });
+
+    exercise_names
+}
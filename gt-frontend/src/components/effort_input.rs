@@ -0,0 +1,79 @@
+#![allow(non_snake_case)]
+use dioxus::prelude::*;
+
+use gt_core::models;
+
+#[derive(Props)]
+pub struct EffortInputProps<'a> {
+    id: &'a str,
+    rpe: &'a UseState<Option<f64>>,
+    rir: &'a UseState<Option<i32>>,
+}
+
+/// Inputs for the effort of a set. Since a set records either RPE or reps in reserve,
+/// entering one of them clears the other.
+pub fn EffortInput<'a>(cx: Scope<'a, EffortInputProps<'a>>) -> Element<'a> {
+    let rpe = cx.props.rpe;
+    let rir = cx.props.rir;
+    let rpe_id = format!("{}-rpe", cx.props.id);
+    let rir_id = format!("{}-rir", cx.props.id);
+    let rir_value = rir.get().map(|v| v.to_string()).unwrap_or_default();
+
+    let rpe_options = models::RPE_VALUES.iter().map(|v| {
+        rsx! {
+            option {
+                value: "{v}",
+                selected: Some(*v) == *rpe.get(),
+                "{v}"
+            }
+        }
+    });
+
+    cx.render(rsx! {
+        div {
+            class: "form-group col-6 col-sm-1",
+            label {
+                r#for: "{rpe_id}",
+                "RPE"
+            }
+            select {
+                class: "form-select",
+                id: "{rpe_id}",
+                onchange: move |evt| {
+                    let v = evt.value.parse::<f64>().ok();
+                    if v.is_some() {
+                        rir.set(None);
+                    }
+                    rpe.set(v)
+                },
+                option {
+                    value: "",
+                    selected: rpe.get().is_none(),
+                    "-"
+                }
+                rpe_options
+            }
+        }
+        div {
+            class: "form-group col-6 col-sm-1",
+            label {
+                r#for: "{rir_id}",
+                "RIR"
+            }
+            input {
+                class: "form-control",
+                id: "{rir_id}",
+                r#type: "number",
+                min: "0",
+                value: "{rir_value}",
+                oninput: move |evt| {
+                    let v = evt.value.parse::<i32>().ok();
+                    if v.is_some() {
+                        rpe.set(None);
+                    }
+                    rir.set(v)
+                }
+            }
+        }
+    })
+}
@@ -5,6 +5,7 @@ use fermi::use_read;
 
 use crate::{
     auth::ACTIVE_AUTH_TOKEN,
+    components::nav::WEIGHT_UNIT,
     request_ext::RequestExt,
     api,
     messages::UIMessage,
@@ -17,6 +18,7 @@ pub fn ExerciseSetWeighted<'a>(
     cx: Scope,
     exs: &'a models::ExerciseSetWeightedQuery,
 ) -> Element<'a> {
+    let unit = *use_read(&cx, WEIGHT_UNIT);
     let created_at_local = Local.from_utc_datetime(&exs.created_at);
 
     cx.render(rsx! {
@@ -30,11 +32,13 @@ pub fn ExerciseSetWeighted<'a>(
             div {
                 class: "col-auto",
                 p { class: "fw-bold",
-                    format_running_sum(format_weighted_reps(exs.reps, exs.weight), exs.reps_sum) }
+                    format_running_sum(format_weighted_reps(exs.reps, exs.weight, unit), exs.reps_sum) }
             } 
             div { class: "w-100" }
             div {
-                class: "col"
+                class: "col",
+                p { class: "fw-light",
+                    format_effort(exs.rpe, exs.rir) }
             }
             div {
                 class: "col-auto",
@@ -66,6 +70,41 @@ pub fn ExerciseSetBodyweight<'a>(
                     format_running_sum(format_bodyweight_reps(exs.reps), exs.reps_sum) }
             }
             div { class: "w-100" }
+            div {
+                class: "col",
+                p { class: "fw-light",
+                    format_effort(exs.rpe, exs.rir) }
+            }
+            div {
+                class: "col-auto",
+                p { class: "fw-light",
+                    format_date(created_at_local) }
+            }
+        }
+    })
+}
+
+#[inline_props]
+pub fn ExerciseSetTimed<'a>(
+    cx: Scope,
+    exs: &'a models::ExerciseSetTimedQuery,
+) -> Element<'a> {
+    let created_at_local = Local.from_utc_datetime(&exs.created_at);
+
+    cx.render(rsx! {
+        div {
+            class: "row",
+            div {
+                class: "col",
+                p { class: "fw-bold",
+                    exs.name.clone() }
+            }
+            div {
+                class: "col-auto",
+                p { class: "fw-bold",
+                    format_time(exs.time) }
+            }
+            div { class: "w-100" }
             div {
                 class: "col"
             }
@@ -78,36 +117,193 @@ pub fn ExerciseSetBodyweight<'a>(
     })
 }
 
+#[inline_props]
+pub fn ExerciseSetDistance<'a>(
+    cx: Scope,
+    exs: &'a models::ExerciseSetDistanceQuery,
+) -> Element<'a> {
+    let created_at_local = Local.from_utc_datetime(&exs.created_at);
+    let pace = exs.pace().map(format_pace).unwrap_or_default();
+
+    cx.render(rsx! {
+        div {
+            class: "row",
+            div {
+                class: "col",
+                p { class: "fw-bold",
+                    exs.name.clone() }
+            }
+            div {
+                class: "col-auto",
+                p { class: "fw-bold",
+                    format!("{} in {}", format_distance(exs.distance), format_time(exs.time)) }
+            }
+            div { class: "w-100" }
+            div {
+                class: "col"
+            }
+            div {
+                class: "col-auto",
+                p { class: "fw-light",
+                    format!("{} ({})", format_date(created_at_local), pace) }
+            }
+        }
+    })
+}
+
+#[inline_props]
+pub fn ExerciseSetWeightedBodyweight<'a>(
+    cx: Scope,
+    exs: &'a models::ExerciseSetWeightedBodyweightQuery,
+) -> Element<'a> {
+    let unit = *use_read(&cx, WEIGHT_UNIT);
+    let created_at_local = Local.from_utc_datetime(&exs.created_at);
+    let effective_weight = match exs.effective_weight() {
+        Some(weight) => format!("{} ({})", format_date(created_at_local), format_weight(weight, unit)),
+        None => format_date(created_at_local),
+    };
+
+    cx.render(rsx! {
+        div {
+            class: "row",
+            div {
+                class: "col",
+                p { class: "fw-bold",
+                    exs.name.clone() }
+            }
+            div {
+                class: "col-auto",
+                p { class: "fw-bold",
+                    format_running_sum(format_weighted_bodyweight_reps(exs.reps, exs.weight, unit), exs.reps_sum) }
+            }
+            div { class: "w-100" }
+            div {
+                class: "col",
+                p { class: "fw-light",
+                    format_effort(exs.rpe, exs.rir) }
+            }
+            div {
+                class: "col-auto",
+                p { class: "fw-light",
+                    effective_weight }
+            }
+        }
+    })
+}
+
+/// Read-only display of any kind of exercise set.
+#[inline_props]
+pub fn ExerciseSetInfo<'a>(cx: Scope, exs: &'a models::ExerciseSetQuery) -> Element<'a> {
+    let info = match exs {
+        models::ExerciseSetQuery::Weighted(exs) => rsx! { ExerciseSetWeighted { exs: exs } },
+        models::ExerciseSetQuery::Bodyweight(exs) => rsx! { ExerciseSetBodyweight { exs: exs } },
+        models::ExerciseSetQuery::Timed(exs) => rsx! { ExerciseSetTimed { exs: exs } },
+        models::ExerciseSetQuery::Distance(exs) => rsx! { ExerciseSetDistance { exs: exs } },
+        models::ExerciseSetQuery::WeightedBodyweight(exs) => rsx! { ExerciseSetWeightedBodyweight { exs: exs } },
+    };
+    let note = exs.note().map(|note| rsx! {
+        p { class: "fst-italic mb-1", "📝 {note}" }
+    });
+    // Sets of a superset round are logged at the same time, so they have no rest in between.
+    let rest = exs.rest().filter(|rest| *rest > 0).map(|rest| {
+        let rest = format_time(rest);
+        rsx! {
+            p { class: "fw-light mb-1", "⏱ {rest} rest before" }
+        }
+    });
+
+    cx.render(rsx! {
+        info
+        note
+        rest
+    })
+}
+
 #[derive(Props)]
 pub struct ExerciseSetProps<'a> {
     pub exs: &'a models::ExerciseSetQuery,
     pub display_message: &'a Coroutine<UIMessage>,
+    /// If given, an edit button sets it to the id of the set.
+    #[props(optional)]
+    pub editing: Option<&'a UseState<Option<i32>>>,
 }
 
 pub fn ExerciseSet<'a>(cx: Scope<'a, ExerciseSetProps<'a>>) -> Element<'a> {
     let auth_token = use_read(&cx, ACTIVE_AUTH_TOKEN);
     let deleted = use_state(&cx, || false);
 
-    let info = match cx.props.exs {
-        models::ExerciseSetQuery::Weighted(exs) => rsx! { ExerciseSetWeighted { exs: exs } },
-        models::ExerciseSetQuery::Bodyweight(exs) => rsx! { ExerciseSetBodyweight { exs: exs } },
-    };
+    let info = rsx! { ExerciseSetInfo { exs: cx.props.exs } };
     
     let exercise_set_id = cx.props.exs.id();
-    let should_display = if *deleted.current() { "none" } else { "block" };
+    let set_type = cx.props.exs.set_type();
+    let set_type_badge = (set_type != models::SetType::Working).then(|| rsx! {
+        span { class: "badge text-bg-secondary", set_type.label() }
+    });
+    let edit_button = cx.props.editing.map(|editing| rsx! {
+        button {
+            class: "btn btn-sm btn-outline-secondary me-1",
+            onclick: move |_| editing.set(Some(exercise_set_id)),
+            "✏️"
+        }
+    });
+
+    // Deleted sets are moved to the trash, so they can be brought back right away.
+    if *deleted.get() {
+        let name = cx.props.exs.name();
+        return cx.render(rsx! {
+            li {
+                class: "list-group-item",
+                div {
+                    class: "row",
+                    div {
+                        class: "col d-flex align-items-center",
+                        span { class: "fw-light", "Deleted \"{name}\"." }
+                    }
+                    div {
+                        class: "col-auto",
+                        button {
+                            class: "btn btn-sm btn-outline-secondary",
+                            onclick: move |_| cx.spawn({
+                                to_owned![auth_token, deleted];
+                                let display_message = cx.props.display_message.clone();
+
+                                async move {
+                                    let client = reqwest::Client::new();
+
+                                    let item = models::TrashItem::ExerciseSet(exercise_set_id);
+
+                                    let res = client.post(api::TRASH_RESTORE.as_str())
+                                        .json(&item).bearer_auth(auth_token.unwrap_or("".into()))
+                                        .send().await
+                                        .handle_result::<()>(UIMessage::error("Restoring exercise failed.".to_string())).await;
+
+                                    match res {
+                                        Ok(()) => deleted.set(false),
+                                        Err(e) => display_message.send(e)
+                                    }
+                                }
+                            }),
+                            "Undo"
+                        }
+                    }
+                }
+            }
+        });
+    }
 
     cx.render(rsx! {
         li {
             class: "list-group-item",
-            display: should_display,
             div {
                 class: "row",
                 div {
                     class: "col",
+                    set_type_badge
                     info
                 }
                 div {
                     class: "col-auto d-flex align-items-center",
+                    edit_button
                     button {
                         class: "btn btn-sm btn-outline-danger",
                         onclick: move |_| cx.spawn({
@@ -0,0 +1,157 @@
+#![allow(non_snake_case)]
+use chrono::NaiveDate;
+use dioxus::prelude::*;
+use fermi::use_read;
+use gloo_file::{Blob, ObjectUrl};
+use gloo_timers::future::TimeoutFuture;
+use log::info;
+use wasm_bindgen::JsCast;
+
+use crate::{
+    api,
+    auth::ACTIVE_AUTH_TOKEN,
+    messages::{MessageProps, UIMessage},
+    util::utc_offset_param,
+};
+use gt_core::models::{self, AuthToken};
+
+/// How long the downloaded file is kept around so that the browser can save it.
+const DOWNLOAD_URL_LIFETIME_MS: u32 = 10_000;
+
+fn parse_date(value: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d").ok()
+}
+
+/// Let the browser save the bytes as a file by clicking a temporary link to them.
+fn save_file(bytes: &[u8], filename: &str) -> Option<ObjectUrl> {
+    let url = ObjectUrl::from(Blob::new_with_options(bytes, Some("text/csv")));
+    let link = web_sys::window()?
+        .document()?
+        .create_element("a")
+        .ok()?
+        .dyn_into::<web_sys::HtmlElement>()
+        .ok()?;
+    link.set_attribute("href", &url).ok()?;
+    link.set_attribute("download", filename).ok()?;
+    link.click();
+
+    Some(url)
+}
+
+/// The file is requested here instead of linking to it directly, because the request needs the auth token.
+async fn download_csv(
+    auth_token: Option<AuthToken>,
+    url: &str,
+    range: models::ExportRange,
+    filename: &str,
+) -> Result<(), UIMessage> {
+    let error = UIMessage::error(format!("Downloading {} failed.", filename));
+
+    let client = reqwest::Client::new();
+    let res = client
+        .get(url)
+        .query(&range)
+        .query(&utc_offset_param())
+        .bearer_auth(auth_token.unwrap_or("".into()))
+        .send()
+        .await
+        .map_err(|e| {
+            info!("{}", e);
+            UIMessage::server_error()
+        })?;
+    if !res.status().is_success() {
+        return Err(error);
+    }
+    let bytes = res.bytes().await.map_err(|e| {
+        info!("{}", e);
+        error.clone()
+    })?;
+
+    let url = save_file(bytes.as_ref(), filename).ok_or(error)?;
+    TimeoutFuture::new(DOWNLOAD_URL_LIFETIME_MS).await;
+    drop(url);
+    Ok(())
+}
+
+/// Download the exercise sets and body measurements as CSV files, optionally only in a range of days.
+pub fn ExportData<'a>(cx: Scope<'a, MessageProps<'a>>) -> Element<'a> {
+    let auth_token = use_read(&cx, ACTIVE_AUTH_TOKEN);
+    let from = use_state(&cx, || None::<NaiveDate>);
+    let to = use_state(&cx, || None::<NaiveDate>);
+
+    let from_value = from
+        .get()
+        .map(|d| d.format("%Y-%m-%d").to_string())
+        .unwrap_or_default();
+    let to_value = to
+        .get()
+        .map(|d| d.format("%Y-%m-%d").to_string())
+        .unwrap_or_default();
+
+    let download = move |url: &'static str, filename: &'static str| {
+        cx.spawn({
+            to_owned![auth_token];
+            let range = models::ExportRange {
+                from: *from.current(),
+                to: *to.current(),
+            };
+            let display_message = cx.props.display_message.clone();
+
+            async move {
+                if let Err(e) = download_csv(auth_token, url, range, filename).await {
+                    display_message.send(e);
+                }
+            }
+        })
+    };
+
+    cx.render(rsx! {
+        div {
+            class: "bg-body-tertiary my-3 p-2",
+            form {
+                class: "row g-1 g-sm-2 align-items-end",
+                h3 {
+                    class: "col-12",
+                    "Export"
+                }
+                div {
+                    class: "form-group col-6 col-sm-auto",
+                    label { r#for: "export-from", "From" }
+                    input {
+                        class: "form-control",
+                        id: "export-from",
+                        r#type: "date",
+                        value: "{from_value}",
+                        oninput: move |evt| from.set(parse_date(&evt.value)),
+                    }
+                }
+                div {
+                    class: "form-group col-6 col-sm-auto",
+                    label { r#for: "export-to", "To" }
+                    input {
+                        class: "form-control",
+                        id: "export-to",
+                        r#type: "date",
+                        value: "{to_value}",
+                        oninput: move |evt| to.set(parse_date(&evt.value)),
+                    }
+                }
+                div {
+                    class: "col-12 col-sm-auto",
+                    button {
+                        class: "btn btn-outline-primary me-2",
+                        r#type: "button",
+                        onclick: move |_| download(api::EXPORT_SETS.as_str(), "sets.csv"),
+                        "Download sets"
+                    }
+                    button {
+                        class: "btn btn-outline-primary",
+                        r#type: "button",
+                        onclick: move |_| download(api::EXPORT_BODY.as_str(), "body.csv"),
+                        "Download body measurements"
+                    }
+                }
+            }
+        }
+    })
+}
@@ -12,15 +12,17 @@ use plotters_canvas::CanvasBackend;
 use crate::{
     api,
     auth::ACTIVE_AUTH_TOKEN,
+    components as c,
     messages::{MessageProps, UIMessage},
     request_ext::RequestExt,
     scroll_to_end,
-    util::lerp,
+    util::{format_pace, lerp},
 };
 use gt_core::models;
 
 const PADDING_DAYS: i64 = 1;
 const PADDING_KG: f64 = 5.0;
+const PADDING_PACE: f64 = 30.0;
 
 #[derive(Props)]
 pub struct GraphProps<'a> {
@@ -59,10 +61,10 @@ impl LabelPosition {
 }
 
 pub fn Graph<'a>(cx: Scope<'a, GraphProps<'a>>) -> Element<'a> {
-    use_future(&cx, (), |()| {
+    // Redraw whenever the data changes, e.g. when warm-ups are toggled.
+    use_future(&cx, (cx.props.data.clone(),), |(data,)| {
         let canvas_wrapper_id = cx.props.canvas_wrapper_id.clone();
         let canvas_id = cx.props.canvas_id.clone();
-        let data = cx.props.data.clone();
 
         async move {
             //
@@ -126,6 +128,7 @@ pub fn draw(canvas_id: &str, data: &models::ExerciseGraphQuery) -> Result<()> {
     //     .as_slice();
 
     // On the y-axis we render the max and min of submitted weights +- PADDING_KG.
+    // The estimated one-rep max is usually above all submitted weights so it counts for the max.
     let (from_kg, to_kg) = (
         data.per_date
             .iter()
@@ -136,7 +139,12 @@ pub fn draw(canvas_id: &str, data: &models::ExerciseGraphQuery) -> Result<()> {
             .0,
         data.per_date
             .iter()
-            .flat_map(|exg| exg.weights.iter().map(|(weight, _)| OrderedFloat(*weight)))
+            .flat_map(|exg| {
+                exg.weights
+                    .iter()
+                    .map(|(weight, _)| OrderedFloat(*weight))
+                    .chain(exg.e1rm.map(OrderedFloat))
+            })
             .max()
             .map(|f| f + PADDING_KG)
             .unwrap_or(OrderedFloat(100.0))
@@ -269,6 +277,153 @@ pub fn draw(canvas_id: &str, data: &models::ExerciseGraphQuery) -> Result<()> {
     // Draw the line.
     chart.draw_series(LineSeries::new(max_weight, GREEN))?;
 
+    // Compute coordinates for an e1RM trendline to compare sets with different rep counts.
+    // y = The best estimated one-rep max of the day.
+    let e1rm = data
+        .per_date
+        .iter()
+        .filter_map(|exg| exg.e1rm.map(|e1rm| (exg.date, e1rm)));
+
+    // Draw the line.
+    chart.draw_series(LineSeries::new(e1rm, MAGENTA))?;
+
+    root.present()?;
+    Ok(())
+}
+
+#[derive(Props)]
+pub struct PaceGraphProps<'a> {
+    canvas_id: &'a String,
+    canvas_wrapper_id: &'a String,
+    data: &'a models::ExercisePaceGraphQuery,
+}
+
+pub fn PaceGraph<'a>(cx: Scope<'a, PaceGraphProps<'a>>) -> Element<'a> {
+    use_future(&cx, (cx.props.data.clone(),), |(data,)| {
+        let canvas_wrapper_id = cx.props.canvas_wrapper_id.clone();
+        let canvas_id = cx.props.canvas_id.clone();
+
+        async move {
+            match draw_pace(&canvas_id, &data) {
+                Ok(()) => {
+                    // JS function to scroll the canvas all the way to the right.
+                    scroll_to_end(&canvas_wrapper_id);
+                }
+                Err(e) => error!("{}", e),
+            }
+        }
+    });
+
+    let from_date = cx.props.data.per_date.first().unwrap().date - Duration::days(PADDING_DAYS);
+    let to_date = (cx.props.data.per_date.last().unwrap().date + Duration::days(PADDING_DAYS))
+        .max(from_date + Duration::days(7));
+    let width = (to_date - from_date).num_days() * 60;
+
+    cx.render(rsx! {
+        div {
+            h3 {
+                cx.props.data.name.clone()
+            }
+            div {
+                style: "overflow-x: auto; overflow-y: hidden;",
+                id: cx.props.canvas_wrapper_id.as_str(),
+                canvas {
+                    id: cx.props.canvas_id.as_str(),
+                    height: 500,
+                    width: width
+                }
+            }
+        }
+    })
+}
+
+pub fn draw_pace(canvas_id: &str, data: &models::ExercisePaceGraphQuery) -> Result<()> {
+    let backend = CanvasBackend::new(canvas_id).expect("cannot find canvas");
+    let root = backend.into_drawing_area();
+    let font_big: FontDesc = ("sans-serif", 20.0).into();
+
+    root.fill(&WHITE)?;
+
+    if data.per_date.len() < 1 {
+        return Err(anyhow!("No data available."));
+    }
+
+    // On the x-axis we render at least a week and leave PADDING_DAYS free to the left and right.
+    let from_date = data.per_date.first().unwrap().date - Duration::days(PADDING_DAYS);
+    let to_date = (data.per_date.last().unwrap().date + Duration::days(PADDING_DAYS))
+        .max(from_date + Duration::days(7));
+
+    // On the y-axis we render the fastest and slowest pace +- PADDING_PACE.
+    let paces = || {
+        data.per_date
+            .iter()
+            .flat_map(|exg| exg.paces.iter().map(|(pace, _)| OrderedFloat(*pace)))
+    };
+    let (from_pace, to_pace) = (
+        paces()
+            .min()
+            .map(|f| (f - PADDING_PACE).max(OrderedFloat(0.0)))
+            .unwrap_or(OrderedFloat(0.0))
+            .0,
+        paces()
+            .max()
+            .map(|f| f + PADDING_PACE)
+            .unwrap_or(OrderedFloat(600.0))
+            .0,
+    );
+
+    let mut chart = ChartBuilder::on(&root)
+        .margin(10u32)
+        .caption(format!("{} Pace", data.name), font_big)
+        .x_label_area_size(30u32)
+        .y_label_area_size(50u32)
+        .right_y_label_area_size(50u32)
+        .build_cartesian_2d(from_date..to_date, from_pace..to_pace)?;
+
+    chart
+        .configure_mesh()
+        .x_labels((to_date - from_date).num_days() as usize)
+        .y_max_light_lines(2)
+        .x_max_light_lines(0)
+        .x_label_formatter(&|date| date.format("%d. %b").to_string())
+        .y_label_formatter(&|pace| format_pace(*pace))
+        .draw()?;
+
+    // Draw the points for each set. The circle is scaled according to the distance, up to 10km.
+    let points = data.per_date.iter().flat_map(|exg| {
+        exg.paces
+            .iter()
+            .map(|(pace, distance)| (exg.date, *pace, *distance))
+    });
+    chart.draw_series(PointSeries::of_element(
+        points,
+        5.0,
+        &RED,
+        &|(x, y, distance), s, st| {
+            EmptyElement::at((x, y))
+                + Circle::new(
+                    (0, 0),
+                    lerp(s, 3.0 * s, (distance / 10000.0).min(1.0)),
+                    st.filled(),
+                )
+        },
+    ))?;
+
+    // Compute coordinates for a trendline of the fastest pace of each day.
+    let best_pace = data.per_date.iter().map(|exg| {
+        let best_pace = exg
+            .paces
+            .iter()
+            .map(|(pace, _)| OrderedFloat(*pace))
+            .min()
+            .unwrap_or(OrderedFloat(0.0));
+
+        (exg.date, best_pace.0)
+    });
+
+    // Draw the line.
+    chart.draw_series(LineSeries::new(best_pace, GREEN))?;
+
     root.present()?;
     Ok(())
 }
@@ -278,9 +433,53 @@ pub fn GraphPage<'a>(cx: Scope<'a, MessageProps<'a>>) -> Element<'a> {
     let graph_data = use_state(&cx, || {
         Vec::<(String, String, models::ExerciseGraphQuery)>::new()
     });
+    let pace_graph_data = use_state(&cx, || {
+        Vec::<(String, String, models::ExercisePaceGraphQuery)>::new()
+    });
     let search_term = use_state(&cx, || "".to_string());
+    let exclude_warmup = use_state(&cx, || true);
+    let name_filter = use_state(&cx, models::ExerciseNameFilter::default);
+    let exercise_names = c::use_exercise_names(&cx, cx.props.display_message);
+
+    let _fetch_pace = use_future(&cx, (*exclude_warmup.get(),), |(exclude_warmup,)| {
+        to_owned![auth_token, pace_graph_data];
+        let display_message = cx.props.display_message.clone();
+
+        async move {
+            let client = reqwest::Client::new();
+            let res = client
+                .get(api::EXERCISE_GRAPH_PACE.as_str())
+                .query(&models::SetTypeFilter { exclude_warmup })
+                .bearer_auth(auth_token.clone().unwrap_or("".into()))
+                .send()
+                .await
+                .handle_result::<Vec<models::ExercisePaceGraphQuery>>(UIMessage::error(
+                    "Requesting pace graph failed.".to_string(),
+                ))
+                .await;
 
-    let _fetch = use_future(&cx, (), |()| {
+            match res {
+                Ok(data) => {
+                    let data_with_id = data
+                        .into_iter()
+                        .map(|exg| {
+                            (
+                                format!("pace-canvas-{}", exg.name),
+                                format!("pace-canvas-wrapper-{}", exg.name),
+                                exg,
+                            )
+                        })
+                        .collect();
+                    pace_graph_data.set(data_with_id);
+                }
+                Err(e) => {
+                    display_message.send(e);
+                }
+            }
+        }
+    });
+
+    let _fetch = use_future(&cx, (*exclude_warmup.get(),), |(exclude_warmup,)| {
         to_owned![auth_token, graph_data];
         let display_message = cx.props.display_message.clone();
 
@@ -288,6 +487,7 @@ pub fn GraphPage<'a>(cx: Scope<'a, MessageProps<'a>>) -> Element<'a> {
             let client = reqwest::Client::new();
             let res = client
                 .get(api::EXERCISE_GRAPH.as_str())
+                .query(&models::SetTypeFilter { exclude_warmup })
                 .bearer_auth(auth_token.clone().unwrap_or("".into()))
                 .send()
                 .await
@@ -320,14 +520,16 @@ pub fn GraphPage<'a>(cx: Scope<'a, MessageProps<'a>>) -> Element<'a> {
     // We render a list of checkboxes to toggle the visibility of graphs for individual exercises.
     // let exercise_names: Vec<String> = graph_data.iter().map(|exg| exg.name.clone()).collect();
 
+    let is_shown = |name: &str| {
+        let search = search_term.current();
+        name.to_lowercase().contains(search.as_ref())
+            && name_filter.matches_name(name, exercise_names.get())
+    };
+
     let graphs = graph_data
         .get()
         .iter()
-        .map(|x| {
-            let name = x.2.name.to_lowercase();
-            let search = search_term.current();
-            (x, name.contains(search.as_ref()))
-        })
+        .map(|x| (x, is_shown(&x.2.name)))
         .map(|((canvas_id, canvas_wrapper_id, exg), flg_show)| {
             rsx! {
                 div {
@@ -341,6 +543,23 @@ pub fn GraphPage<'a>(cx: Scope<'a, MessageProps<'a>>) -> Element<'a> {
             }
         });
 
+    let pace_graphs = pace_graph_data
+        .get()
+        .iter()
+        .map(|x| (x, is_shown(&x.2.name)))
+        .map(|((canvas_id, canvas_wrapper_id, exg), flg_show)| {
+            rsx! {
+                div {
+                    display: if flg_show { "block" } else { "none" },
+                    PaceGraph {
+                        canvas_id: canvas_id,
+                        canvas_wrapper_id: canvas_wrapper_id,
+                        data: exg
+                    }
+                }
+            }
+        });
+
     cx.render(rsx! {
         div {
             class: "my-3 p-2",
@@ -355,8 +574,11 @@ pub fn GraphPage<'a>(cx: Scope<'a, MessageProps<'a>>) -> Element<'a> {
                         oninput: move |evt| { search_term.set(evt.value.to_lowercase()) }
                     }
                 }
+                c::ExcludeWarmup { exclude_warmup: exclude_warmup }
+                c::ExerciseNameFilterSelect { filter: name_filter }
             }
             graphs
+            pace_graphs
         }
 
     })
@@ -1,4 +1,5 @@
 #![allow(non_snake_case)]
+use chrono::{Local, NaiveDate, NaiveDateTime, TimeZone};
 use dioxus::prelude::*;
 use fermi::use_read;
 use futures_util::StreamExt;
@@ -7,40 +8,337 @@ use crate::{
     api,
     auth::ACTIVE_AUTH_TOKEN,
     components as c,
+    components::nav::WEIGHT_UNIT,
     messages::{MessageProps, UIMessage},
     request_ext::RequestExt,
+    util::utc_offset_param,
     PAGE_SIZE,
 };
 use gt_core::models;
 
+const HISTORY_LIST_ID: &str = "history-list";
+
+/// Load the next page when the history list is scrolled this close to its end.
+const SCROLL_THRESHOLD: i32 = 100;
+
+/// Either fetch the history again from the newest set or append the next page to it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HistoryRequest {
+    Reset,
+    More,
+}
+
+fn parse_date(value: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d").ok()
+}
+
+/// Whether the history list is scrolled to (almost) its end.
+fn scrolled_to_end() -> bool {
+    web_sys::window()
+        .and_then(|window| window.document())
+        .and_then(|document| document.get_element_by_id(HISTORY_LIST_ID))
+        .map(|list| {
+            list.scroll_top() + list.client_height() >= list.scroll_height() - SCROLL_THRESHOLD
+        })
+        .unwrap_or(false)
+}
+
+/// The reps and weight of a set, if its kind has them.
+fn reps_and_weight(exs: &models::ExerciseSetQuery) -> (Option<i32>, Option<f64>) {
+    match exs {
+        models::ExerciseSetQuery::Weighted(exs) => (Some(exs.reps), Some(exs.weight)),
+        models::ExerciseSetQuery::Bodyweight(exs) => (Some(exs.reps), None),
+        models::ExerciseSetQuery::WeightedBodyweight(exs) => (Some(exs.reps), Some(exs.weight)),
+        models::ExerciseSetQuery::Timed(_) | models::ExerciseSetQuery::Distance(_) => (None, None),
+    }
+}
+
+/// The set with changed name, reps, weight and time. Everything else is kept as it is.
+fn edited_set(
+    exs: &models::ExerciseSetQuery,
+    name: String,
+    reps: i32,
+    weight: f64,
+    created_at: String,
+) -> models::ExerciseSet {
+    match exs {
+        models::ExerciseSetQuery::Weighted(exs) => models::ExerciseSetWeighted {
+            name,
+            reps,
+            weight,
+            rpe: exs.rpe,
+            rir: exs.rir,
+            set_type: exs.set_type,
+            note: exs.note.clone(),
+            created_at,
+        }
+        .into(),
+        models::ExerciseSetQuery::Bodyweight(exs) => models::ExerciseSetBodyweight {
+            name,
+            reps,
+            rpe: exs.rpe,
+            rir: exs.rir,
+            set_type: exs.set_type,
+            note: exs.note.clone(),
+            created_at,
+        }
+        .into(),
+        models::ExerciseSetQuery::Timed(exs) => models::ExerciseSetTimed {
+            name,
+            time: exs.time,
+            set_type: exs.set_type,
+            note: exs.note.clone(),
+            created_at,
+        }
+        .into(),
+        models::ExerciseSetQuery::Distance(exs) => models::ExerciseSetDistance {
+            name,
+            distance: exs.distance,
+            time: exs.time,
+            set_type: exs.set_type,
+            note: exs.note.clone(),
+            created_at,
+        }
+        .into(),
+        models::ExerciseSetQuery::WeightedBodyweight(exs) => models::ExerciseSetWeightedBodyweight {
+            name,
+            reps,
+            weight,
+            rpe: exs.rpe,
+            rir: exs.rir,
+            set_type: exs.set_type,
+            note: exs.note.clone(),
+            created_at,
+        }
+        .into(),
+    }
+}
+
+#[derive(Props)]
+struct ExerciseSetEditProps<'a> {
+    exs: &'a models::ExerciseSetQuery,
+    editing: &'a UseState<Option<i32>>,
+    fetch: &'a Coroutine<HistoryRequest>,
+    display_message: &'a Coroutine<UIMessage>,
+}
+
+/// Inline form to change the name, reps, weight and time of a set in the history.
+fn ExerciseSetEdit<'a>(cx: Scope<'a, ExerciseSetEditProps<'a>>) -> Element<'a> {
+    let auth_token = use_read(&cx, ACTIVE_AUTH_TOKEN);
+    let unit = *use_read(&cx, WEIGHT_UNIT);
+    let exs = cx.props.exs;
+    let (initial_reps, initial_weight) = reps_and_weight(exs);
+    let name = use_state(&cx, || exs.name().to_string());
+    let reps = use_state(&cx, || initial_reps.unwrap_or(0));
+    let weight = use_state(&cx, || initial_weight.unwrap_or(0.0));
+    let date = use_state(&cx, || {
+        Local
+            .from_utc_datetime(&exs.created_at())
+            .naive_local()
+            .format("%Y-%m-%dT%H:%M")
+            .to_string()
+    });
+    let editing = cx.props.editing;
+    let exercise_set_id = exs.id();
+
+    let reps_input = initial_reps.map(|_| {
+        rsx! {
+            div {
+                class: "form-group col-6 col-sm-2",
+                label { r#for: "edit-exercise-set-reps", "Reps" }
+                input {
+                    class: "form-control",
+                    id: "edit-exercise-set-reps",
+                    r#type: "number",
+                    min: "0",
+                    value: "{reps}",
+                    oninput: move |evt| {
+                        if let Ok(v) = evt.value.parse() {
+                            reps.set(v)
+                        }
+                    }
+                }
+            }
+        }
+    });
+    let weight_label = format!("Weight ({})", unit.label());
+    let weight_input = initial_weight.map(|_| {
+        rsx! {
+            div {
+                class: "form-group col-6 col-sm-2",
+                label { r#for: "edit-exercise-set-weight", "{weight_label}" }
+                input {
+                    class: "form-control",
+                    id: "edit-exercise-set-weight",
+                    r#type: "number",
+                    step: "any",
+                    value: "{weight}",
+                    oninput: move |evt| {
+                        if let Ok(v) = evt.value.parse() {
+                            weight.set(v)
+                        }
+                    }
+                }
+            }
+        }
+    });
+
+    cx.render(rsx! {
+        li {
+            class: "list-group-item",
+            form {
+                class: "row g-1 g-sm-2",
+                div {
+                    class: "form-group col-12 col-sm-auto",
+                    label { r#for: "edit-exercise-set-name", "Exercise Name" }
+                    input {
+                        class: "form-control",
+                        id: "edit-exercise-set-name",
+                        value: "{name}",
+                        oninput: move |evt| name.set(evt.value.clone()),
+                    }
+                }
+                reps_input
+                weight_input
+                div {
+                    class: "form-group col-12 col-sm-auto",
+                    label { r#for: "edit-exercise-set-date", "Date" }
+                    input {
+                        class: "form-control",
+                        id: "edit-exercise-set-date",
+                        r#type: "datetime-local",
+                        value: "{date}",
+                        oninput: move |evt| date.set(evt.value.clone()),
+                    }
+                }
+                div {
+                    class: "col-auto d-flex align-items-end",
+                    button {
+                        r#type: "button",
+                        class: "btn btn-sm btn-outline-success me-1",
+                        onclick: move |_| cx.spawn({
+                            to_owned![auth_token, name, reps, weight, date, editing];
+                            let exs = exs.clone();
+                            let fetch = cx.props.fetch.clone();
+                            let display_message = cx.props.display_message.clone();
+
+                            async move {
+                                if name.current().is_empty() {
+                                    return;
+                                }
+
+                                // convert the datetime-local into a utc datetime string
+                                let created_at = match NaiveDateTime::parse_from_str(date.current().as_str(), "%Y-%m-%dT%H:%M") {
+                                    Ok(created_at) => created_at,
+                                    Err(_) => return,
+                                };
+                                let created_at = Local.from_local_datetime(&created_at).unwrap()
+                                    .naive_utc().format("%Y-%m-%dT%H:%M").to_string();
+
+                                let update = models::ExerciseSetUpdate {
+                                    id: exercise_set_id,
+                                    set: edited_set(&exs, name.current().as_ref().clone(), *reps.current(), *weight.current(), created_at),
+                                };
+
+                                let client = reqwest::Client::new();
+                                let res = client.put(api::EXERCISE_SET.as_str())
+                                    .json(&update).bearer_auth(auth_token.unwrap_or("".into()))
+                                    .send().await
+                                    .handle_result::<()>(UIMessage::error("Changing exercise set failed.".to_string())).await;
+
+                                match res {
+                                    Ok(()) => {
+                                        editing.set(None);
+                                        fetch.send(HistoryRequest::Reset);
+                                    }
+                                    Err(e) => display_message.send(e),
+                                }
+                            }
+                        }),
+                        "Save"
+                    }
+                    button {
+                        r#type: "button",
+                        class: "btn btn-sm btn-outline-secondary",
+                        onclick: move |_| editing.set(None),
+                        "Cancel"
+                    }
+                }
+            }
+        }
+    })
+}
+
 pub fn HistoryPage<'a>(cx: Scope<'a, MessageProps<'a>>) -> Element<'a> {
     let auth_token = use_read(&cx, ACTIVE_AUTH_TOKEN);
     let history = use_state(&cx, || Vec::<models::ExerciseSetQuery>::new());
-    let search_term = use_state(&cx, || "".to_string());
+    let has_more = use_state(&cx, || false);
+    let loading = use_state(&cx, || false);
+    let name = use_state(&cx, || "".to_string());
+    let kind = use_state(&cx, || None::<models::ExerciseKind>);
+    let from = use_state(&cx, || None::<NaiveDate>);
+    let to = use_state(&cx, || None::<NaiveDate>);
+    let exclude_warmup = use_state(&cx, || true);
+    let editing = use_state(&cx, || None::<i32>);
 
-    let fetch = use_coroutine(&cx, |mut rx: UnboundedReceiver<Option<u64>>| {
-        to_owned![auth_token, history];
+    let fetch = use_coroutine(&cx, |mut rx: UnboundedReceiver<HistoryRequest>| {
+        to_owned![auth_token, history, has_more, loading, name, kind, from, to, exclude_warmup];
         let display_message = cx.props.display_message.clone();
 
         async move {
-            while let Some(page_size_opt) = rx.next().await {
-                let url = match page_size_opt {
-                    Some(page_size) => format!("{}/{}", api::EXERCISE_SET.as_str(), page_size),
-                    None => api::EXERCISE_SET.to_string(),
+            while let Some(request) = rx.next().await {
+                // continue after the last set we have
+                let cursor = match request {
+                    HistoryRequest::Reset => None,
+                    HistoryRequest::More => {
+                        if !*has_more.current() {
+                            continue;
+                        }
+                        history.current().last().map(|exs| (exs.created_at(), exs.id()))
+                    }
+                };
+                let name = name.current().trim().to_string();
+                let params = models::ExerciseSetHistoryParams {
+                    name: if name.is_empty() { None } else { Some(name) },
+                    kind: (*kind.current()).clone(),
+                    from: *from.current(),
+                    to: *to.current(),
+                    before_created_at: cursor.map(|(created_at, _)| created_at),
+                    before_id: cursor.map(|(_, id)| id),
+                    limit: Some(*PAGE_SIZE),
+                    ..Default::default()
                 };
+
+                loading.set(true);
                 let client = reqwest::Client::new();
                 let res = client
-                    .get(url)
+                    .get(api::EXERCISE_SET.as_str())
+                    .query(&params)
+                    .query(&utc_offset_param())
+                    .query(&models::SetTypeFilter {
+                        exclude_warmup: *exclude_warmup.current(),
+                    })
                     .bearer_auth(auth_token.clone().unwrap_or("".into()))
                     .send()
                     .await
-                    .handle_result(UIMessage::error(
+                    .handle_result::<Vec<models::ExerciseSetQuery>>(UIMessage::error(
                         "Requesting exercise history failed.".to_string(),
                     ))
                     .await;
+                loading.set(false);
 
                 match res {
-                    Ok(h) => history.set(h),
+                    Ok(page) => {
+                        has_more.set(page.len() as u64 == *PAGE_SIZE);
+                        match request {
+                            HistoryRequest::Reset => history.set(page),
+                            HistoryRequest::More => history.modify(|h| {
+                                let mut h = h.clone();
+                                h.extend(page);
+                                h
+                            }),
+                        }
+                    }
                     Err(e) => {
                         display_message.send(e);
                     }
@@ -48,57 +346,167 @@ pub fn HistoryPage<'a>(cx: Scope<'a, MessageProps<'a>>) -> Element<'a> {
             }
         }
     });
-    use_future(&cx, (), |()| {
-        to_owned![fetch];
-        async move { fetch.send(Some(*PAGE_SIZE)) }
-    });
+    // Start from the newest set when a filter changes. Warm-ups also change the running sum of reps.
+    use_future(
+        &cx,
+        (
+            name.get().clone(),
+            kind.get().clone(),
+            *from.get(),
+            *to.get(),
+            *exclude_warmup.get(),
+        ),
+        |_| {
+            to_owned![fetch];
+            async move { fetch.send(HistoryRequest::Reset) }
+        },
+    );
 
     let content = {
-        let filtered_history = history
-            // filter by search box
-            .get()
-            .iter()
-            .filter(|&exs| {
-                let name = exs.name().to_lowercase();
-                let search = search_term.current();
-                name.contains(search.as_ref())
-            });
-        let hlist = filtered_history.map(|exs| {
+        // Consecutive sets of the same superset or circuit are shown together.
+        let mut groups: Vec<Vec<&models::ExerciseSetQuery>> = Vec::new();
+        for exs in history.get().iter() {
+            match groups.last_mut() {
+                Some(group)
+                    if exs.group_id().is_some() && group[0].group_id() == exs.group_id() =>
+                {
+                    group.push(exs)
+                }
+                _ => groups.push(vec![exs]),
+            }
+        }
+
+        let render_set = move |exs: &'a models::ExerciseSetQuery| {
+            if *editing.get() == Some(exs.id()) {
+                rsx! {
+                    ExerciseSetEdit { exs: exs, editing: editing, fetch: fetch, display_message: cx.props.display_message }
+                }
+            } else {
+                rsx! {
+                    c::ExerciseSet { exs: exs, display_message: cx.props.display_message, editing: editing }
+                }
+            }
+        };
+
+        let hlist = groups.into_iter().map(|group| {
+            if group.len() == 1 {
+                render_set(group[0])
+            } else {
+                let sets = group.into_iter().map(render_set);
+                rsx! {
+                    li {
+                        class: "list-group-item border-start border-3 border-info",
+                        p { class: "fw-light mb-0", "Superset" }
+                        ul {
+                            class: "list-group list-group-flush",
+                            sets
+                        }
+                    }
+                }
+            }
+        });
+
+        let kind_options = models::ExerciseKind::ALL.iter().map(|k| {
+            let value: i32 = k.clone().into();
             rsx! {
-                c::ExerciseSet { exs: exs, display_message: cx.props.display_message }
+                option {
+                    value: "{value}",
+                    selected: Some(k) == kind.get().as_ref(),
+                    k.label()
+                }
             }
         });
+        let from_value = from.get().map(|d| d.format("%Y-%m-%d").to_string()).unwrap_or_default();
+        let to_value = to.get().map(|d| d.format("%Y-%m-%d").to_string()).unwrap_or_default();
+
         rsx! {
             div {
                 class: "my-3 p-2",
                 form {
                     class: "row g-1 g-sm-2",
+                    div {
+                        class: "form-group col-12 col-sm",
+                        label { r#for: "history-name", "Exercise Name" }
+                        input {
+                            class: "form-control",
+                            id: "history-name",
+                            value: "{name}",
+                            placeholder: "All exercises",
+                            onchange: move |evt| name.set(evt.value.clone()),
+                        }
+                    }
                     div {
                         class: "form-group col-12 col-sm-auto",
-                        button {
-                            class: "btn btn-outline-secondary",
-                            r#type: "button",
-                            onclick: move |_| fetch.send(None),
-                            "Show All"
+                        label { r#for: "history-kind", "Kind" }
+                        select {
+                            class: "form-select",
+                            id: "history-kind",
+                            onchange: move |evt| {
+                                kind.set(evt.value.parse::<i32>().ok().and_then(|v| models::ExerciseKind::try_from(v).ok()))
+                            },
+                            option {
+                                value: "",
+                                selected: kind.get().is_none(),
+                                "-"
+                            }
+                            kind_options
                         }
                     }
                     div {
-                        class: "form-group col-12 col-sm",
+                        class: "form-group col-6 col-sm-auto",
+                        label { r#for: "history-from", "From" }
+                        input {
+                            class: "form-control",
+                            id: "history-from",
+                            r#type: "date",
+                            value: "{from_value}",
+                            oninput: move |evt| from.set(parse_date(&evt.value)),
+                        }
+                    }
+                    div {
+                        class: "form-group col-6 col-sm-auto",
+                        label { r#for: "history-to", "To" }
                         input {
                             class: "form-control",
-                            value: "{ search_term }",
-                            placeholder: "Search",
-                            oninput: move |evt| { search_term.set(evt.value.to_lowercase()) }
+                            id: "history-to",
+                            r#type: "date",
+                            value: "{to_value}",
+                            oninput: move |evt| to.set(parse_date(&evt.value)),
                         }
                     }
+                    c::ExcludeWarmup { exclude_warmup: exclude_warmup }
                 }
                 if !history.current().is_empty() {
                     rsx!{
-                        ul {
-                            class: "list-group list-group-flush my-3",
-                            hlist
+                        div {
+                            id: HISTORY_LIST_ID,
+                            style: "max-height: 70vh; overflow-y: auto;",
+                            onscroll: move |_| {
+                                if *has_more.get() && !*loading.get() && scrolled_to_end() {
+                                    fetch.send(HistoryRequest::More)
+                                }
+                            },
+                            ul {
+                                class: "list-group list-group-flush my-3",
+                                hlist
+                            }
+                            if *has_more.get() {
+                                rsx!{
+                                    button {
+                                        class: "btn btn-outline-secondary mb-3",
+                                        r#type: "button",
+                                        disabled: "{loading}",
+                                        onclick: move |_| fetch.send(HistoryRequest::More),
+                                        "Load more"
+                                    }
+                                }
+                            }
                         }
                     }
+                } else if !*loading.get() {
+                    rsx!{
+                        p { class: "my-3", "No exercise sets found." }
+                    }
                 }
             }
         }
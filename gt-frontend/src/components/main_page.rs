@@ -53,6 +53,15 @@ fn LoggedInMainPage<'a>(cx: Scope<'a, MessageProps<'a>>) -> Element<'a> {
 
     cx.render(rsx! {
         div {
+            c::SessionControl { display_message: &cx.props.display_message }
+            c::StartRoutine {
+                fetch_names: fetch_names,
+                display_message: &cx.props.display_message
+            }
+            c::RestTimer {
+                fetch_names: fetch_names,
+                display_message: &cx.props.display_message
+            }
             c::AddExerciseSetWeighted {
                 exercise_names: exercise_names.get().to_owned(),
                 fetch_names: fetch_names,
@@ -63,6 +72,26 @@ fn LoggedInMainPage<'a>(cx: Scope<'a, MessageProps<'a>>) -> Element<'a> {
                 fetch_names: fetch_names,
                 display_message: &cx.props.display_message
             }
+            c::AddExerciseSetTimed {
+                exercise_names: exercise_names.get().to_owned(),
+                fetch_names: fetch_names,
+                display_message: &cx.props.display_message
+            }
+            c::AddExerciseSetDistance {
+                exercise_names: exercise_names.get().to_owned(),
+                fetch_names: fetch_names,
+                display_message: &cx.props.display_message
+            }
+            c::AddExerciseSetWeightedBodyweight {
+                exercise_names: exercise_names.get().to_owned(),
+                fetch_names: fetch_names,
+                display_message: &cx.props.display_message
+            }
+            c::AddExerciseSetGroup {
+                exercise_names: exercise_names.get().to_owned(),
+                fetch_names: fetch_names,
+                display_message: &cx.props.display_message
+            }
         }
     })
 }
@@ -1,29 +1,53 @@
 mod access_control;
 mod add_exercise_set;
 mod admin_page;
+mod calendar;
+mod catalog_select;
+mod effort_input;
 mod exercise_set;
+mod export_data;
 mod graph_page;
 mod history_page;
 mod login_page;
 mod main_page;
 mod messages;
 mod nav;
+mod note_input;
+mod pending_sets;
 mod pr_page;
+mod program_page;
 mod register_page;
+mod rest_timer;
+mod routine_page;
+mod session_page;
+mod set_type;
 mod stats_page;
+mod trash_page;
 mod user_page;
 
 pub use access_control::*;
 pub use add_exercise_set::*;
 pub use admin_page::*;
+pub use calendar::*;
+pub use catalog_select::*;
+pub use effort_input::*;
 pub use exercise_set::*;
+pub use export_data::*;
 pub use graph_page::*;
 pub use history_page::*;
 pub use login_page::*;
 pub use main_page::*;
 pub use messages::*;
 pub use nav::*;
+pub use note_input::*;
+pub use pending_sets::*;
 pub use pr_page::*;
+pub use program_page::*;
 pub use register_page::*;
+pub use rest_timer::*;
+pub use routine_page::*;
+pub use session_page::*;
+pub use set_type::*;
 pub use stats_page::*;
+pub use trash_page::*;
 pub use user_page::*;
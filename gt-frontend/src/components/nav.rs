@@ -16,11 +16,15 @@ use crate::{
     messages::UIMessage,
     to_dataurl, APP_BASE, LOGO,
 };
+use gt_core::models;
 
 #[derive(Deref)]
 pub struct WrapperUserPicture<T>(pub T);
 pub static USER_PICTURE: Atom<WrapperUserPicture<String>> = |_| WrapperUserPicture((*LOGO).clone());
 
+/// The unit the user reads and enters weights in. The backend already converts all weights to it.
+pub static WEIGHT_UNIT: Atom<models::WeightUnit> = |_| models::WeightUnit::Kg;
+
 pub fn reset_user_picture<'a, T: 'a>(cx: &'a Scope<'a, T>) {
     let user_picture = use_set(cx, USER_PICTURE);
     user_picture(WrapperUserPicture((*LOGO).clone()));
@@ -30,6 +34,7 @@ pub fn reset_user_picture<'a, T: 'a>(cx: &'a Scope<'a, T>) {
 pub fn Navbar<'a>(cx: Scope<'a>, ui_messages: &'a UseRef<VecDeque<UIMessage>>) -> Element<'a> {
     let auth_token = use_atom_state(&cx, ACTIVE_AUTH_TOKEN);
     let user_picture = use_atom_state(&cx, USER_PICTURE);
+    let weight_unit = use_atom_state(&cx, WEIGHT_UNIT);
 
     // Asynchronously fetch user picture and set the Atom.
     // Somehow the whole site broke when the USER_PICTURE atom was in the main_page module, so for now we keep it in the navbar module.
@@ -67,6 +72,32 @@ pub fn Navbar<'a>(cx: Scope<'a>, ui_messages: &'a UseRef<VecDeque<UIMessage>>) -
         }
     });
 
+    let _fetch_weight_unit = use_future(&cx, auth_token, |auth_token_opt| {
+        to_owned![weight_unit];
+        async move {
+            if let Some(auth_token) = auth_token_opt.current().as_ref() {
+                let client = reqwest::Client::new();
+                let res = client
+                    .get(api::USER_INFO.as_str())
+                    .bearer_auth(auth_token)
+                    .send()
+                    .await;
+
+                match res {
+                    Ok(res) => match res.json::<models::UserInfoQuery>().await {
+                        Ok(user_info) => weight_unit.set(user_info.weight_unit),
+                        Err(e) => {
+                            info!("{}", e);
+                        }
+                    },
+                    Err(e) => {
+                        info!("{}", e);
+                    }
+                }
+            }
+        }
+    });
+
     cx.render(rsx! {
         div {
             class: "sticky-top",
@@ -98,6 +129,27 @@ pub fn Navbar<'a>(cx: Scope<'a>, ui_messages: &'a UseRef<VecDeque<UIMessage>>) -
                                     to: concatcp!(APP_BASE, "/history"), "History"
                                 }
                             }
+                            div {
+                                class: "nav-item navbar-text",
+                                Link {
+                                    class: "nav-link",
+                                    to: concatcp!(APP_BASE, "/sessions"), "Sessions"
+                                }
+                            }
+                            div {
+                                class: "nav-item navbar-text",
+                                Link {
+                                    class: "nav-link",
+                                    to: concatcp!(APP_BASE, "/routines"), "Routines"
+                                }
+                            }
+                            div {
+                                class: "nav-item navbar-text",
+                                Link {
+                                    class: "nav-link",
+                                    to: concatcp!(APP_BASE, "/programs"), "Programs"
+                                }
+                            }
                             div {
                                 class: "nav-item navbar-text",
                                 Link {
@@ -119,6 +171,13 @@ pub fn Navbar<'a>(cx: Scope<'a>, ui_messages: &'a UseRef<VecDeque<UIMessage>>) -
                                     to: concatcp!(APP_BASE, "/user"), "User"
                                 }
                             }
+                            div {
+                                class: "nav-item navbar-text",
+                                Link {
+                                    class: "nav-link",
+                                    to: concatcp!(APP_BASE, "/trash"), "Trash"
+                                }
+                            }
                             if is_superuser(&cx) {
                                 rsx! {
                                     div {
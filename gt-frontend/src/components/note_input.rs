@@ -0,0 +1,30 @@
+#![allow(non_snake_case)]
+use dioxus::prelude::*;
+
+#[derive(Props)]
+pub struct NoteInputProps<'a> {
+    id: &'a str,
+    note: &'a UseState<String>,
+}
+
+/// Free-text note like "belt" or "paused reps" that is shared by all add exercise set forms.
+pub fn NoteInput<'a>(cx: Scope<'a, NoteInputProps<'a>>) -> Element<'a> {
+    let note = cx.props.note;
+
+    cx.render(rsx! {
+        div {
+            class: "form-group col-12 col-sm",
+            label {
+                r#for: cx.props.id,
+                "Note"
+            }
+            input {
+                class: "form-control",
+                id: cx.props.id,
+                value: "{note}",
+                placeholder: "note",
+                oninput: move |evt| note.set(evt.value.clone()),
+            }
+        }
+    })
+}
@@ -0,0 +1,82 @@
+#![allow(non_snake_case)]
+use dioxus::prelude::*;
+use fermi::use_read;
+use futures_util::StreamExt;
+use gloo_timers::future::TimeoutFuture;
+use itertools::join;
+
+use crate::{
+    auth::ACTIVE_AUTH_TOKEN,
+    messages::{MessageProps, UIMessage},
+    offline_queue::{queued_count, replay_queue},
+};
+use gt_core::models::AuthToken;
+
+/// How often sets that were logged without a connection are sent again.
+const REPLAY_INTERVAL_MS: u32 = 15_000;
+
+/// Sends the sets that were logged without a connection once the server can be reached again,
+/// and shows how many are still waiting.
+pub fn PendingSets<'a>(cx: Scope<'a, MessageProps<'a>>) -> Element<'a> {
+    let auth_token = use_read(&cx, ACTIVE_AUTH_TOKEN);
+    let pending = use_state(&cx, queued_count);
+
+    let replay = use_coroutine(&cx, |mut rx: UnboundedReceiver<Option<AuthToken>>| {
+        to_owned![pending];
+        let display_message = cx.props.display_message.clone();
+
+        async move {
+            while let Some(auth_token) = rx.next().await {
+                if auth_token.is_some() && queued_count() > 0 {
+                    let (sent, rejected) = replay_queue(auth_token).await;
+
+                    if sent > 0 {
+                        display_message.send(UIMessage::info(format!(
+                            "Sent {} set(s) that were logged without a connection.",
+                            sent
+                        )));
+                    }
+                    if !rejected.is_empty() {
+                        display_message.send(UIMessage::error(format!(
+                            "Submitting {} failed.",
+                            join(rejected.iter().map(|name| format!("\"{}\"", name)), ", ")
+                        )));
+                    }
+                }
+                pending.set(queued_count());
+            }
+        }
+    });
+    // The token is passed along since the user might only log in after the page is loaded.
+    use_future(&cx, (auth_token.clone(),), |(auth_token,)| {
+        to_owned![replay];
+
+        async move {
+            loop {
+                replay.send(auth_token.clone());
+                TimeoutFuture::new(REPLAY_INTERVAL_MS).await;
+            }
+        }
+    });
+
+    if *pending.get() == 0 {
+        return None;
+    }
+    let status = format!(
+        "📶 {} set(s) logged without a connection are waiting to be sent.",
+        pending.get()
+    );
+
+    cx.render(rsx! {
+        div {
+            class: "alert alert-warning d-flex flex-wrap align-items-center gap-2",
+            span { class: "me-auto", "{status}" }
+            button {
+                r#type: "button",
+                class: "btn btn-sm btn-outline-secondary",
+                onclick: move |_| replay.send(auth_token.clone()),
+                "Send now"
+            }
+        }
+    })
+}
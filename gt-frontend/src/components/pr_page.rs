@@ -1,18 +1,36 @@
 #![allow(non_snake_case)]
+use chrono::{Local, TimeZone};
 use dioxus::prelude::*;
 use fermi::use_read;
 use itertools::join;
 
 use crate::messages::{MessageProps, UIMessage};
 use crate::request_ext::RequestExt;
-use crate::{api, auth::ACTIVE_AUTH_TOKEN};
+use crate::{
+    api,
+    auth::ACTIVE_AUTH_TOKEN,
+    components as c,
+    components::nav::WEIGHT_UNIT,
+    util::{format_added_weight, format_distance, format_time},
+};
 use gt_core::models;
 
+fn format_e1rm(e1rm: Option<f64>, unit: models::WeightUnit) -> String {
+    match e1rm {
+        Some(e1rm) => format!(" e1RM {:.1}{}", e1rm, unit.label()),
+        None => "".to_string(),
+    }
+}
+
 pub fn PRPage<'a>(cx: Scope<'a, MessageProps<'a>>) -> Element<'a> {
     let auth_token = use_read(&cx, ACTIVE_AUTH_TOKEN);
+    let unit = *use_read(&cx, WEIGHT_UNIT);
     let search_term = use_state(&cx, || "".to_string());
+    let exclude_warmup = use_state(&cx, || true);
+    let name_filter = use_state(&cx, models::ExerciseNameFilter::default);
+    let exercise_names = c::use_exercise_names(&cx, cx.props.display_message);
 
-    let fetch = use_future(&cx, (), |()| {
+    let fetch = use_future(&cx, (*exclude_warmup.get(),), |(exclude_warmup,)| {
         to_owned![auth_token];
         let display_message = cx.props.display_message.clone();
 
@@ -20,6 +38,7 @@ pub fn PRPage<'a>(cx: Scope<'a, MessageProps<'a>>) -> Element<'a> {
             let client = reqwest::Client::new();
             let res = client
                 .get(api::EXERCISE_PR.as_str())
+                .query(&models::SetTypeFilter { exclude_warmup })
                 .bearer_auth(auth_token.unwrap_or("".into()))
                 .send()
                 .await
@@ -40,30 +59,96 @@ pub fn PRPage<'a>(cx: Scope<'a, MessageProps<'a>>) -> Element<'a> {
 
     let content = match fetch.value() {
         Some(Some(prs)) => {
-            let prlist_weighted = prs.weighted.iter().filter(|pr| {
-                let name = pr.name.to_lowercase();
+            let is_shown = move |name: &str| {
                 let search = search_term.current();
-                name.contains(search.as_ref())
-            }).map(|pr| {
+                name.to_lowercase().contains(search.as_ref())
+                    && name_filter.matches_name(name, exercise_names.get())
+            };
+            let rep_max_header = (1..=models::REP_MAX_REPS).map(|reps| {
                 rsx! {
-                    li { format!("{}: [ {} ]", pr.name.clone(), join(pr.pr.iter()
-                            .map(|(weight, reps)| format!("{} × {:.1}kg", reps, weight)), " | ")) }
+                    th { key: "{reps}", "{reps}RM" }
                 }
             });
+            let rep_max_rows = prs
+                .weighted
+                .iter()
+                .filter(move |pr| is_shown(&pr.name))
+                .map(|pr| {
+                    let cells = (1..=models::REP_MAX_REPS).map(move |reps| {
+                        match pr.rep_maxes.iter().find(|rep_max| rep_max.reps == reps) {
+                            Some(rep_max) => {
+                                let weight = format!("{:.1}", rep_max.weight);
+                                let date = Local
+                                    .from_utc_datetime(&rep_max.created_at)
+                                    .format("%d.%m.%y")
+                                    .to_string();
+                                rsx! {
+                                    td {
+                                        key: "{reps}",
+                                        div { "{weight}" }
+                                        small { class: "text-body-secondary", "{date}" }
+                                    }
+                                }
+                            }
+                            None => rsx! {
+                                td { key: "{reps}", "-" }
+                            },
+                        }
+                    });
+                    let e1rm = pr
+                        .e1rm
+                        .map(|e1rm| format!("{:.1}", e1rm))
+                        .unwrap_or("-".to_string());
+
+                    rsx! {
+                        tr {
+                            key: "{pr.name}",
+                            th { "{pr.name}" }
+                            cells
+                            td { "{e1rm}" }
+                        }
+                    }
+                });
             let prlist_bodyweight = prs
                 .bodyweight
                 .iter()
-                .filter(|pr| {
-                    let name = pr.name.to_lowercase();
-                    let search = search_term.current();
-                    name.contains(search.as_ref())
-                })
+                .filter(move |pr| is_shown(&pr.name))
                 .map(|pr| {
                     rsx! {
                         li { format!("{}: [ {} ]", pr.name.clone(), join(pr.pr.iter()
                                 .map(|reps| format!("{} × 身", reps)), " | ")) }
                     }
                 });
+            let prlist_timed = prs
+                .timed
+                .iter()
+                .filter(move |pr| is_shown(&pr.name))
+                .map(|pr| {
+                    rsx! {
+                        li { format!("{}: [ {} ]", pr.name.clone(), join(pr.pr.iter()
+                                .map(|time| format_time(*time)), " | ")) }
+                    }
+                });
+            let prlist_distance = prs
+                .distance
+                .iter()
+                .filter(move |pr| is_shown(&pr.name))
+                .map(|pr| {
+                    rsx! {
+                        li { format!("{}: [ longest {} | {} ]", pr.name.clone(), format_distance(pr.longest), join(pr.fastest.iter()
+                                .map(|(distance, time, estimated)| format!("{} in {}{}", format_distance(*distance), format_time(*time), if *estimated { " (estimated)" } else { "" })), " | ")) }
+                    }
+                });
+            let prlist_weighted_bodyweight = prs
+                .weighted_bodyweight
+                .iter()
+                .filter(move |pr| is_shown(&pr.name))
+                .map(|pr| {
+                    rsx! {
+                        li { format!("{}: [ {} ]{}", pr.name.clone(), join(pr.pr.iter()
+                                .map(|(load, weight, reps)| format!("{} × {:.1}{} (身{})", reps, load, unit.label(), format_added_weight(*weight, unit))), " | "), format_e1rm(pr.e1rm, unit)) }
+                    }
+                });
             rsx! {
                 div {
                     class: "my-3 p-2",
@@ -88,17 +173,47 @@ pub fn PRPage<'a>(cx: Scope<'a, MessageProps<'a>>) -> Element<'a> {
                                 "Refresh"
                             }
                         }
+                        c::ExcludeWarmup { exclude_warmup: exclude_warmup }
+                        c::ExerciseNameFilterSelect { filter: name_filter }
                     }
                     div {
                         class: "bg-body-tertiary",
-                        p { "By Weight" }
-                        ul { prlist_weighted }
+                        p { format!("By Weight ({})", unit.label()) }
+                        div {
+                            class: "table-responsive",
+                            table {
+                                class: "table table-sm table-striped",
+                                thead {
+                                    tr {
+                                        th { "Exercise" }
+                                        rep_max_header
+                                        th { "e1RM" }
+                                    }
+                                }
+                                tbody { rep_max_rows }
+                            }
+                        }
                     }
                     div {
                         class: "bg-body-tertiary",
                         p { "By Bodyweight" }
                         ul { prlist_bodyweight }
                     }
+                    div {
+                        class: "bg-body-tertiary",
+                        p { "By Time" }
+                        ul { prlist_timed }
+                    }
+                    div {
+                        class: "bg-body-tertiary",
+                        p { "By Distance" }
+                        ul { prlist_distance }
+                    }
+                    div {
+                        class: "bg-body-tertiary",
+                        p { "By Bodyweight + Load" }
+                        ul { prlist_weighted_bodyweight }
+                    }
                 }
             }
         }
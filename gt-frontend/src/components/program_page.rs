@@ -0,0 +1,405 @@
+#![allow(non_snake_case)]
+use dioxus::prelude::*;
+use fermi::use_read;
+use itertools::join;
+
+use crate::{
+    api,
+    auth::ACTIVE_AUTH_TOKEN,
+    components::nav::WEIGHT_UNIT,
+    messages::{MessageProps, UIMessage},
+    request_ext::RequestExt,
+    util::utc_offset_param,
+};
+use gt_core::models;
+
+fn default_program_exercise() -> models::ProgramExercise {
+    models::ProgramExercise {
+        name: "".to_string(),
+        rule: models::ProgressionRule::Linear,
+        sets: 3,
+        min_reps: 5,
+        max_reps: 5,
+        start_weight: 20.0,
+        increment: 2.5,
+    }
+}
+
+fn format_prescribed_set(set: &models::PrescribedSet, unit: models::WeightUnit) -> String {
+    match set.set_type {
+        models::SetType::Amrap => format!("{}+ × {:.1}{}", set.reps, set.weight, unit.label()),
+        _ => format!("{} × {:.1}{}", set.reps, set.weight, unit.label()),
+    }
+}
+
+#[derive(Props)]
+pub struct ProgramTodayProps<'a> {
+    program_id: i32,
+    display_message: &'a Coroutine<UIMessage>,
+}
+
+/// The sets the program prescribes for today.
+fn ProgramToday<'a>(cx: Scope<'a, ProgramTodayProps<'a>>) -> Element<'a> {
+    let auth_token = use_read(&cx, ACTIVE_AUTH_TOKEN);
+    let unit = *use_read(&cx, WEIGHT_UNIT);
+
+    let fetch = use_future(&cx, (cx.props.program_id,), |(program_id,)| {
+        to_owned![auth_token];
+        let display_message = cx.props.display_message.clone();
+
+        async move {
+            let client = reqwest::Client::new();
+            let res = client
+                .get(format!("{}/{}/today", api::PROGRAM.as_str(), program_id))
+                .query(&utc_offset_param())
+                .bearer_auth(auth_token.unwrap_or("".into()))
+                .send()
+                .await
+                .handle_result::<models::PrescribedWorkout>(UIMessage::error(
+                    "Requesting today's workout failed.".to_string(),
+                ))
+                .await;
+
+            match res {
+                Ok(workout) => Some(workout),
+                Err(e) => {
+                    display_message.send(e);
+                    None
+                }
+            }
+        }
+    });
+
+    match fetch.value() {
+        Some(Some(workout)) => {
+            let exercises = workout.exercises.iter().map(|exercise| {
+                rsx! {
+                    li {
+                        format!("{} ({}): [ {} ]", exercise.name, exercise.rule.label(),
+                            join(exercise.sets.iter().map(|set| format_prescribed_set(set, unit)), " | "))
+                    }
+                }
+            });
+            cx.render(rsx! {
+                ul { exercises }
+            })
+        }
+        _ => cx.render(rsx! {
+            p { "Loading" }
+        }),
+    }
+}
+
+pub fn ProgramPage<'a>(cx: Scope<'a, MessageProps<'a>>) -> Element<'a> {
+    let auth_token = use_read(&cx, ACTIVE_AUTH_TOKEN);
+    let unit = *use_read(&cx, WEIGHT_UNIT);
+    let program_name = use_state(&cx, || "".to_string());
+    let exercises = use_ref(&cx, || vec![default_program_exercise()]);
+
+    let fetch = use_future(&cx, (), |()| {
+        to_owned![auth_token];
+        let display_message = cx.props.display_message.clone();
+
+        async move {
+            let client = reqwest::Client::new();
+            let res = client
+                .get(api::PROGRAM.as_str())
+                .bearer_auth(auth_token.unwrap_or("".into()))
+                .send()
+                .await
+                .handle_result::<Vec<models::ProgramQuery>>(UIMessage::error(
+                    "Requesting programs failed.".to_string(),
+                ))
+                .await;
+
+            match res {
+                Ok(programs) => Some(programs),
+                Err(e) => {
+                    display_message.send(e);
+                    None
+                }
+            }
+        }
+    });
+
+    let program_list = match fetch.value() {
+        Some(Some(programs)) => {
+            let program_items = programs.iter().map(|program| {
+                let program_id = program.id;
+                let start_date = program.start_date.format("%d.%m.%Y");
+
+                rsx! {
+                    li {
+                        key: "{program.id}",
+                        class: "list-group-item",
+                        div {
+                            class: "row",
+                            div {
+                                class: "col",
+                                p { class: "fw-bold", "{program.name} (since {start_date})" }
+                                ProgramToday {
+                                    program_id: program.id,
+                                    display_message: cx.props.display_message
+                                }
+                            }
+                            div {
+                                class: "col-auto d-flex align-items-center",
+                                button {
+                                    class: "btn btn-sm btn-outline-danger",
+                                    onclick: move |_| cx.spawn({
+                                        to_owned![auth_token, fetch];
+                                        let display_message = cx.props.display_message.clone();
+
+                                        async move {
+                                            let client = reqwest::Client::new();
+
+                                            let program = models::ProgramDelete { id: program_id };
+
+                                            let res = client.delete(api::PROGRAM.as_str())
+                                                .json(&program).bearer_auth(auth_token.unwrap_or("".into()))
+                                                .send().await
+                                                .handle_result::<()>(UIMessage::error("Deleting program failed.".to_string())).await;
+
+                                            match res {
+                                                Ok(()) => fetch.restart(),
+                                                Err(e) => display_message.send(e)
+                                            }
+                                        }
+                                    }),
+                                    "🗑️"
+                                }
+                            }
+                        }
+                    }
+                }
+            });
+            rsx! {
+                ul {
+                    class: "list-group list-group-flush",
+                    program_items
+                }
+            }
+        }
+        _ => {
+            rsx! {
+                p { "Loading" }
+            }
+        }
+    };
+
+    let exercise_inputs = exercises
+        .read()
+        .clone()
+        .into_iter()
+        .enumerate()
+        .map(|(i, exercise)| {
+            let rule_options = models::ProgressionRule::ALL.iter().map(move |rule| {
+                let value: i32 = (*rule).into();
+                let selected = *rule == exercise.rule;
+                rsx! {
+                    option {
+                        value: "{value}",
+                        selected: selected,
+                        rule.label()
+                    }
+                }
+            });
+            let name_value = exercise.name;
+
+            rsx! {
+                div {
+                    class: "form-group col-12 col-sm-3",
+                    label { "Exercise" }
+                    input {
+                        class: "form-control",
+                        value: "{name_value}",
+                        oninput: move |evt| exercises.write()[i].name = evt.value.clone()
+                    }
+                }
+                div {
+                    class: "form-group col-12 col-sm-2",
+                    label { "Progression" }
+                    select {
+                        class: "form-select",
+                        onchange: move |evt| {
+                            if let Some(rule) = evt
+                                .value
+                                .parse::<i32>()
+                                .ok()
+                                .and_then(|v| models::ProgressionRule::try_from(v).ok())
+                            {
+                                exercises.write()[i].rule = rule;
+                            }
+                        },
+                        rule_options
+                    }
+                }
+                div {
+                    class: "form-group col-4 col-sm-1",
+                    label { "Sets" }
+                    input {
+                        class: "form-control",
+                        r#type: "number",
+                        min: "1",
+                        value: "{exercise.sets}",
+                        oninput: move |evt| {
+                            if let Ok(v) = evt.value.parse() {
+                                exercises.write()[i].sets = v;
+                            }
+                        }
+                    }
+                }
+                div {
+                    class: "form-group col-4 col-sm-1",
+                    label { "Min reps" }
+                    input {
+                        class: "form-control",
+                        r#type: "number",
+                        min: "1",
+                        value: "{exercise.min_reps}",
+                        oninput: move |evt| {
+                            if let Ok(v) = evt.value.parse() {
+                                exercises.write()[i].min_reps = v;
+                            }
+                        }
+                    }
+                }
+                div {
+                    class: "form-group col-4 col-sm-1",
+                    label { "Max reps" }
+                    input {
+                        class: "form-control",
+                        r#type: "number",
+                        min: "1",
+                        value: "{exercise.max_reps}",
+                        oninput: move |evt| {
+                            if let Ok(v) = evt.value.parse() {
+                                exercises.write()[i].max_reps = v;
+                            }
+                        }
+                    }
+                }
+                div {
+                    class: "form-group col-6 col-sm-2",
+                    label { format!("Start weight ({})", unit.label()) }
+                    input {
+                        class: "form-control",
+                        r#type: "number",
+                        step: "any",
+                        value: "{exercise.start_weight}",
+                        oninput: move |evt| {
+                            if let Ok(v) = evt.value.parse() {
+                                exercises.write()[i].start_weight = v;
+                            }
+                        }
+                    }
+                }
+                div {
+                    class: "form-group col-6 col-sm-2",
+                    label { format!("Increment ({})", unit.label()) }
+                    input {
+                        class: "form-control",
+                        r#type: "number",
+                        step: "any",
+                        value: "{exercise.increment}",
+                        oninput: move |evt| {
+                            if let Ok(v) = evt.value.parse() {
+                                exercises.write()[i].increment = v;
+                            }
+                        }
+                    }
+                }
+                div { class: "w-100" }
+            }
+        });
+
+    cx.render(rsx! {
+        div {
+            p { "Programs page" }
+            div {
+                class: "my-3 p-2",
+                program_list
+            }
+            div {
+                class: "bg-body-tertiary my-3 p-2",
+                form {
+                    class: "row g-1 g-sm-2",
+                    p {
+                        class: "col-12",
+                        "New program. For 5/3/1 the start weight is the training max and it grows by the increment every cycle."
+                    }
+                    div {
+                        class: "form-group col-12",
+                        input {
+                            class: "form-control",
+                            value: "{program_name}",
+                            placeholder: "program name",
+                            oninput: move |evt| program_name.set(evt.value.clone()),
+                        }
+                    }
+                    exercise_inputs
+                    div {
+                        class: "col-auto",
+                        button {
+                            r#type: "button",
+                            class: "btn btn-sm btn-outline-secondary",
+                            onclick: move |_| exercises.write().push(default_program_exercise()),
+                            "Add exercise"
+                        }
+                    }
+                    div {
+                        class: "col-auto",
+                        button {
+                            r#type: "button",
+                            class: "btn btn-sm btn-outline-secondary",
+                            onclick: move |_| {
+                                exercises.write().pop();
+                            },
+                            "Remove exercise"
+                        }
+                    }
+                    div { class: "w-100" }
+                    div {
+                        class: "col-auto",
+                        button {
+                            r#type: "button",
+                            class: "btn btn-sm btn-outline-success",
+                            onclick: move |_| cx.spawn({
+                                to_owned![auth_token, fetch, program_name, exercises];
+                                let display_message = cx.props.display_message.clone();
+
+                                async move {
+                                    let client = reqwest::Client::new();
+
+                                    let program = models::Program {
+                                        name: program_name.current().as_ref().clone(),
+                                        exercises: exercises.read().iter()
+                                            .filter(|exercise| !exercise.name.is_empty())
+                                            .cloned()
+                                            .collect(),
+                                    };
+
+                                    let res = client.post(api::PROGRAM.as_str())
+                                        .json(&program).bearer_auth(auth_token.unwrap_or("".into()))
+                                        .send().await
+                                        .handle_result::<models::ProgramQuery>(UIMessage::error("Saving program failed.".to_string())).await;
+
+                                    match res {
+                                        Ok(program) => {
+                                            display_message.send(UIMessage::info(format!("Saved program \"{}\"", program.name)));
+                                            program_name.set("".to_string());
+                                            exercises.set(vec![default_program_exercise()]);
+                                            fetch.restart();
+                                        }
+                                        Err(e) => display_message.send(e)
+                                    }
+                                }
+                            }),
+                            "Save"
+                        }
+                    }
+                }
+            }
+        }
+    })
+}
@@ -0,0 +1,217 @@
+#![allow(non_snake_case)]
+use chrono::{NaiveDateTime, Utc};
+use dioxus::prelude::*;
+use fermi::{use_atom_state, use_read, Atom, UseAtomState};
+use gloo_timers::future::TimeoutFuture;
+use web_sys::window;
+
+use crate::{
+    api, auth::ACTIVE_AUTH_TOKEN, components::main_page::FetchNames, messages::UIMessage,
+    request_ext::RequestExt, util::format_time,
+};
+use gt_core::models;
+
+const REST_TIMER_KEY: &str = "rest_timer";
+
+/// How much a button changes the rest of the running timer.
+const REST_STEP_SECONDS: i32 = 15;
+
+/// How long the timer stays visible after the rest is over.
+const REST_OVER_VISIBLE_SECONDS: i64 = 10 * 60;
+
+/// The rest after the last submitted set. `started_at` is in UTC.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RestTimerState {
+    pub name: String,
+    pub started_at: NaiveDateTime,
+    pub rest: i32,
+}
+
+impl RestTimerState {
+    pub fn start(name: String, rest: i32) -> Self {
+        Self {
+            name,
+            started_at: Utc::now().naive_utc(),
+            rest,
+        }
+    }
+}
+
+/// The running rest timer, which starts out as the one in the local storage so that it survives a page reload.
+pub static REST_TIMER: Atom<Option<RestTimerState>> = |_| get_stored_rest_timer();
+
+/// The timer is stored as "<started_at>;<rest>;<name>" with the start as a unix timestamp.
+fn get_stored_rest_timer() -> Option<RestTimerState> {
+    let stored = window()?
+        .local_storage()
+        .ok()??
+        .get_item(REST_TIMER_KEY)
+        .ok()??;
+    let mut parts = stored.splitn(3, ';');
+    let started_at = NaiveDateTime::from_timestamp_opt(parts.next()?.parse().ok()?, 0)?;
+    let rest = parts.next()?.parse().ok()?;
+    let name = parts.next()?.to_string();
+
+    Some(RestTimerState {
+        name,
+        started_at,
+        rest,
+    })
+}
+
+fn store_rest_timer(timer: Option<&RestTimerState>) {
+    let storage = match window().and_then(|window| window.local_storage().ok().flatten()) {
+        Some(storage) => storage,
+        None => return,
+    };
+
+    let res = match timer {
+        Some(timer) => storage.set_item(
+            REST_TIMER_KEY,
+            &format!(
+                "{};{};{}",
+                timer.started_at.timestamp(),
+                timer.rest,
+                timer.name
+            ),
+        ),
+        None => storage.remove_item(REST_TIMER_KEY),
+    };
+    if res.is_err() {
+        log::error!("Storing the rest timer failed.");
+    }
+}
+
+/// Replace the running rest timer, also in the local storage.
+pub fn set_rest_timer(
+    rest_timer: &UseAtomState<Option<RestTimerState>>,
+    timer: Option<RestTimerState>,
+) {
+    store_rest_timer(timer.as_ref());
+    rest_timer.set(timer);
+}
+
+#[derive(Props)]
+pub struct RestTimerProps<'a> {
+    fetch_names: &'a Coroutine<FetchNames>,
+    display_message: &'a Coroutine<UIMessage>,
+}
+
+/// Counts down the rest after a set. The rest can be changed and saved as the default for the exercise.
+pub fn RestTimer<'a>(cx: Scope<'a, RestTimerProps<'a>>) -> Element<'a> {
+    let auth_token = use_read(&cx, ACTIVE_AUTH_TOKEN);
+    let rest_timer = use_atom_state(&cx, REST_TIMER);
+    let now = use_state(&cx, || Utc::now().naive_utc());
+
+    use_future(&cx, (), |()| {
+        to_owned![now, rest_timer];
+
+        async move {
+            loop {
+                TimeoutFuture::new(1000).await;
+                if rest_timer.current().is_some() {
+                    now.set(Utc::now().naive_utc());
+                }
+            }
+        }
+    });
+
+    let timer = match rest_timer.get() {
+        Some(timer) => timer.clone(),
+        None => return None,
+    };
+    let elapsed = (*now.get() - timer.started_at).num_seconds().max(0);
+    let remaining = timer.rest as i64 - elapsed;
+    if remaining < -REST_OVER_VISIBLE_SECONDS {
+        return None;
+    }
+
+    let name = timer.name.clone();
+    let (class, status) = if remaining > 0 {
+        (
+            "alert alert-info",
+            format!("Rest {} after \"{}\"", format_time(remaining as i32), name),
+        )
+    } else {
+        (
+            "alert alert-success",
+            format!("Rest is over, time for the next set after \"{}\"!", name),
+        )
+    };
+    let rest_label = format!("of {}", format_time(timer.rest));
+    let shorter = timer.clone();
+    let longer = timer.clone();
+
+    cx.render(rsx! {
+        div {
+            class: "{class} d-flex flex-wrap align-items-center gap-2 my-3",
+            span { class: "me-auto", "⏱ {status}" }
+            span { class: "fw-light", "{rest_label}" }
+            button {
+                r#type: "button",
+                class: "btn btn-sm btn-outline-secondary",
+                onclick: move |_| {
+                    let rest = (shorter.rest - REST_STEP_SECONDS).max(REST_STEP_SECONDS);
+                    set_rest_timer(rest_timer, Some(RestTimerState { rest, ..shorter.clone() }))
+                },
+                "-15s"
+            }
+            button {
+                r#type: "button",
+                class: "btn btn-sm btn-outline-secondary",
+                onclick: move |_| {
+                    let rest = (longer.rest + REST_STEP_SECONDS).min(models::MAX_REST_SECONDS);
+                    set_rest_timer(rest_timer, Some(RestTimerState { rest, ..longer.clone() }))
+                },
+                "+15s"
+            }
+            button {
+                r#type: "button",
+                class: "btn btn-sm btn-outline-primary",
+                onclick: move |_| cx.spawn({
+                    to_owned![auth_token];
+                    let timer = timer.clone();
+                    let fetch_names = cx.props.fetch_names.clone();
+                    let display_message = cx.props.display_message.clone();
+
+                    async move {
+                        let exercise_rest = models::ExerciseRest {
+                            name: timer.name.clone(),
+                            rest: Some(timer.rest),
+                        };
+
+                        let client = reqwest::Client::new();
+                        let res = client
+                            .put(api::EXERCISE_REST.as_str())
+                            .json(&exercise_rest)
+                            .bearer_auth(auth_token.unwrap_or("".into()))
+                            .send()
+                            .await
+                            .handle_result::<()>(UIMessage::error(
+                                "Saving default rest failed.".to_string(),
+                            ))
+                            .await;
+
+                        match res {
+                            Ok(()) => {
+                                fetch_names.send(FetchNames);
+                                display_message.send(UIMessage::info(format!(
+                                    "Rest after \"{}\" is now {} by default.",
+                                    timer.name,
+                                    format_time(timer.rest)
+                                )));
+                            }
+                            Err(e) => display_message.send(e),
+                        }
+                    }
+                }),
+                "Save as default"
+            }
+            button {
+                r#type: "button",
+                class: "btn-close",
+                onclick: move |_| set_rest_timer(rest_timer, None),
+            }
+        }
+    })
+}
@@ -0,0 +1,734 @@
+#![allow(non_snake_case)]
+use chrono::Utc;
+use dioxus::prelude::*;
+use fermi::use_read;
+use itertools::join;
+
+use crate::{
+    api,
+    auth::ACTIVE_AUTH_TOKEN,
+    components as c,
+    components::nav::WEIGHT_UNIT,
+    messages::{MessageProps, UIMessage},
+    request_ext::RequestExt,
+    util::format_weight,
+};
+use gt_core::models;
+
+const KINDS: [(models::ExerciseKind, &str); 5] = [
+    (models::ExerciseKind::Weighted, "Weighted"),
+    (models::ExerciseKind::Bodyweight, "Bodyweight"),
+    (
+        models::ExerciseKind::WeightedBodyweight,
+        "Weighted/Assisted Bodyweight",
+    ),
+    (models::ExerciseKind::Timed, "Timed"),
+    (models::ExerciseKind::Distance, "Distance"),
+];
+
+/// One exercise in the routine editor.
+#[derive(Debug, Clone, PartialEq)]
+struct RoutineRow {
+    name: String,
+    kind: models::ExerciseKind,
+    target_sets: i32,
+    target_reps: Option<i32>,
+    target_weight: Option<f64>,
+}
+
+impl Default for RoutineRow {
+    fn default() -> Self {
+        Self {
+            name: "".to_string(),
+            kind: models::ExerciseKind::Weighted,
+            target_sets: 3,
+            target_reps: None,
+            target_weight: None,
+        }
+    }
+}
+
+impl From<&models::RoutineExerciseQuery> for RoutineRow {
+    fn from(exercise: &models::RoutineExerciseQuery) -> Self {
+        Self {
+            name: exercise.name.clone(),
+            kind: exercise.kind.clone(),
+            target_sets: exercise.target_sets,
+            target_reps: exercise.target_reps,
+            target_weight: exercise.target_weight,
+        }
+    }
+}
+
+fn format_target(exercise: &models::RoutineExerciseQuery, unit: models::WeightUnit) -> String {
+    let reps = exercise
+        .target_reps
+        .map(|reps| reps.to_string())
+        .unwrap_or("?".to_string());
+    match exercise.target_weight {
+        Some(weight) => format!(
+            "{} {}×{} @ {}",
+            exercise.name,
+            exercise.target_sets,
+            reps,
+            format_weight(weight, unit)
+        ),
+        None => format!("{} {}×{}", exercise.name, exercise.target_sets, reps),
+    }
+}
+
+pub fn RoutinePage<'a>(cx: Scope<'a, MessageProps<'a>>) -> Element<'a> {
+    let auth_token = use_read(&cx, ACTIVE_AUTH_TOKEN);
+    let unit = *use_read(&cx, WEIGHT_UNIT);
+    let exercise_names = use_state(&cx, || Vec::<models::ExerciseNameQuery>::new());
+    let editing_id = use_state(&cx, || None::<i32>);
+    let routine_name = use_state(&cx, || "".to_string());
+    let rows = use_ref(&cx, || vec![RoutineRow::default()]);
+
+    let fetch = use_future(&cx, (), |()| {
+        to_owned![auth_token];
+        let display_message = cx.props.display_message.clone();
+
+        async move {
+            let client = reqwest::Client::new();
+            let res = client
+                .get(api::ROUTINE.as_str())
+                .bearer_auth(auth_token.unwrap_or("".into()))
+                .send()
+                .await
+                .handle_result::<Vec<models::RoutineQuery>>(UIMessage::error(
+                    "Requesting routines failed.".to_string(),
+                ))
+                .await;
+
+            match res {
+                Ok(routines) => Some(routines),
+                Err(e) => {
+                    display_message.send(e);
+                    None
+                }
+            }
+        }
+    });
+
+    use_future(&cx, (), |()| {
+        to_owned![auth_token, exercise_names];
+        let display_message = cx.props.display_message.clone();
+
+        async move {
+            let client = reqwest::Client::new();
+            let res = client
+                .get(api::EXERCISE_NAME.as_str())
+                .bearer_auth(auth_token.unwrap_or("".into()))
+                .send()
+                .await
+                .handle_result::<Vec<models::ExerciseNameQuery>>(UIMessage::error(
+                    "Fetching exercise names failed.".to_string(),
+                ))
+                .await;
+
+            match res {
+                Ok(names) => exercise_names.set(names),
+                Err(e) => display_message.send(e),
+            }
+        }
+    });
+
+    let routine_list = match fetch.value() {
+        Some(Some(routines)) => {
+            let routine_items = routines.iter().map(|routine| {
+                let routine_id = routine.id;
+                let summary = join(
+                    routine
+                        .exercises
+                        .iter()
+                        .map(|exercise| format_target(exercise, unit)),
+                    ", ",
+                );
+
+                rsx! {
+                    li {
+                        class: "list-group-item",
+                        div {
+                            class: "row",
+                            div {
+                                class: "col",
+                                p { class: "fw-bold", routine.name.clone() }
+                                p { class: "fw-light", "{summary}" }
+                            }
+                            div {
+                                class: "col-auto d-flex align-items-center",
+                                button {
+                                    class: "btn btn-sm btn-outline-secondary me-1",
+                                    onclick: move |_| {
+                                        editing_id.set(Some(routine.id));
+                                        routine_name.set(routine.name.clone());
+                                        rows.set(routine.exercises.iter().map(RoutineRow::from).collect());
+                                    },
+                                    "✏️"
+                                }
+                                button {
+                                    class: "btn btn-sm btn-outline-danger",
+                                    onclick: move |_| cx.spawn({
+                                        to_owned![auth_token, fetch];
+                                        let display_message = cx.props.display_message.clone();
+
+                                        async move {
+                                            let client = reqwest::Client::new();
+
+                                            let routine = models::RoutineDelete { id: routine_id };
+
+                                            let res = client.delete(api::ROUTINE.as_str())
+                                                .json(&routine).bearer_auth(auth_token.unwrap_or("".into()))
+                                                .send().await
+                                                .handle_result::<()>(UIMessage::error("Deleting routine failed.".to_string())).await;
+
+                                            match res {
+                                                Ok(()) => fetch.restart(),
+                                                Err(e) => display_message.send(e)
+                                            }
+                                        }
+                                    }),
+                                    "🗑️"
+                                }
+                            }
+                        }
+                    }
+                }
+            });
+            rsx! {
+                ul {
+                    class: "list-group list-group-flush",
+                    routine_items
+                }
+            }
+        }
+        _ => {
+            rsx! {
+                p { "Loading" }
+            }
+        }
+    };
+
+    let names_datalist = exercise_names.get().iter().map(|exn| {
+        rsx! {
+            option { value: exn.name.as_str() }
+        }
+    });
+
+    let row_inputs = rows.read().clone().into_iter().enumerate().map(|(i, row)| {
+        let row_kind = row.kind.clone();
+        let kind_options = KINDS.iter().map(move |(kind, label)| {
+            let value: i32 = kind.clone().into();
+            let selected = *kind == row_kind;
+            rsx! {
+                option {
+                    value: "{value}",
+                    selected: selected,
+                    *label
+                }
+            }
+        });
+        let reps_value = row.target_reps.map(|v| v.to_string()).unwrap_or_default();
+        let weight_value = row.target_weight.map(|v| v.to_string()).unwrap_or_default();
+        let name_value = row.name;
+
+        rsx! {
+            div {
+                class: "form-group col-12 col-sm-auto",
+                input {
+                    class: "form-control",
+                    list: "routine-exercise-names-list",
+                    value: "{name_value}",
+                    placeholder: "exercise name",
+                    autocomplete: "off",
+                    oninput: move |evt| {
+                        let new_name = evt.value.clone();
+                        let mut rows = rows.write();
+                        // Existing exercises keep their kind.
+                        if let Some(exn) = exercise_names.get().iter().find(|exn| exn.name == new_name) {
+                            rows[i].kind = exn.kind.clone();
+                        }
+                        rows[i].name = new_name;
+                    }
+                }
+            }
+            div {
+                class: "form-group col-12 col-sm-auto",
+                select {
+                    class: "form-select",
+                    onchange: move |evt| {
+                        if let Some(kind) = evt
+                            .value
+                            .parse::<i32>()
+                            .ok()
+                            .and_then(|v| models::ExerciseKind::try_from(v).ok())
+                        {
+                            rows.write()[i].kind = kind;
+                        }
+                    },
+                    kind_options
+                }
+            }
+            div {
+                class: "form-group col-4 col-sm-1",
+                input {
+                    class: "form-control",
+                    r#type: "number",
+                    min: "1",
+                    value: "{row.target_sets}",
+                    placeholder: "sets",
+                    oninput: move |evt| {
+                        if let Ok(v) = evt.value.parse() {
+                            rows.write()[i].target_sets = v;
+                        }
+                    }
+                }
+            }
+            div {
+                class: "form-group col-4 col-sm-1",
+                input {
+                    class: "form-control",
+                    r#type: "number",
+                    min: "0",
+                    value: "{reps_value}",
+                    placeholder: "reps",
+                    oninput: move |evt| rows.write()[i].target_reps = evt.value.parse().ok()
+                }
+            }
+            div {
+                class: "form-group col-4 col-sm-1",
+                input {
+                    class: "form-control",
+                    r#type: "number",
+                    step: "any",
+                    value: "{weight_value}",
+                    placeholder: unit.label(),
+                    oninput: move |evt| rows.write()[i].target_weight = evt.value.parse().ok()
+                }
+            }
+            div { class: "w-100" }
+        }
+    });
+
+    let editor_title = if editing_id.get().is_some() {
+        "Edit routine"
+    } else {
+        "New routine"
+    };
+
+    cx.render(rsx! {
+        div {
+            p { "Routines page" }
+            div {
+                class: "my-3 p-2",
+                routine_list
+            }
+            div {
+                class: "bg-body-tertiary my-3 p-2",
+                form {
+                    class: "row g-1 g-sm-2",
+                    p {
+                        class: "col-12",
+                        "{editor_title}"
+                    }
+                    div {
+                        class: "form-group col-12",
+                        input {
+                            class: "form-control",
+                            value: "{routine_name}",
+                            placeholder: "routine name",
+                            oninput: move |evt| routine_name.set(evt.value.clone()),
+                        }
+                    }
+                    datalist {
+                        id: "routine-exercise-names-list",
+                        names_datalist
+                    }
+                    row_inputs
+                    div {
+                        class: "col-auto",
+                        button {
+                            r#type: "button",
+                            class: "btn btn-sm btn-outline-secondary",
+                            onclick: move |_| rows.write().push(RoutineRow::default()),
+                            "Add exercise"
+                        }
+                    }
+                    div {
+                        class: "col-auto",
+                        button {
+                            r#type: "button",
+                            class: "btn btn-sm btn-outline-secondary",
+                            onclick: move |_| {
+                                rows.write().pop();
+                            },
+                            "Remove exercise"
+                        }
+                    }
+                    div { class: "w-100" }
+                    div {
+                        class: "col-auto",
+                        button {
+                            r#type: "button",
+                            class: "btn btn-sm btn-outline-success",
+                            onclick: move |_| cx.spawn({
+                                to_owned![auth_token, fetch, editing_id, routine_name, rows];
+                                let display_message = cx.props.display_message.clone();
+
+                                async move {
+                                    let client = reqwest::Client::new();
+
+                                    let routine = models::Routine {
+                                        name: routine_name.current().as_ref().clone(),
+                                        exercises: rows.read().iter()
+                                            .filter(|row| !row.name.is_empty())
+                                            .map(|row| models::RoutineExercise {
+                                                name: row.name.clone(),
+                                                kind: row.kind.clone(),
+                                                target_sets: row.target_sets,
+                                                target_reps: row.target_reps,
+                                                target_weight: row.target_weight,
+                                            })
+                                            .collect(),
+                                    };
+
+                                    let req = match *editing_id.current() {
+                                        Some(id) => client.put(format!("{}/{}", api::ROUTINE.as_str(), id)),
+                                        None => client.post(api::ROUTINE.as_str()),
+                                    };
+                                    let res = req
+                                        .json(&routine).bearer_auth(auth_token.unwrap_or("".into()))
+                                        .send().await
+                                        .handle_result::<models::RoutineQuery>(UIMessage::error("Saving routine failed.".to_string())).await;
+
+                                    match res {
+                                        Ok(routine) => {
+                                            display_message.send(UIMessage::info(format!("Saved routine \"{}\"", routine.name)));
+                                            editing_id.set(None);
+                                            routine_name.set("".to_string());
+                                            rows.set(vec![RoutineRow::default()]);
+                                            fetch.restart();
+                                        }
+                                        Err(e) => display_message.send(e)
+                                    }
+                                }
+                            }),
+                            "Save"
+                        }
+                    }
+                    div {
+                        class: "col-auto",
+                        button {
+                            r#type: "button",
+                            class: "btn btn-sm btn-outline-secondary",
+                            onclick: move |_| {
+                                editing_id.set(None);
+                                routine_name.set("".to_string());
+                                rows.set(vec![RoutineRow::default()]);
+                            },
+                            "Cancel"
+                        }
+                    }
+                }
+            }
+        }
+    })
+}
+
+#[derive(Props)]
+pub struct RoutineExerciseLogProps<'a> {
+    exercise: &'a models::RoutineExerciseQuery,
+    fetch_names: &'a Coroutine<c::main_page::FetchNames>,
+    display_message: &'a Coroutine<UIMessage>,
+}
+
+/// Log the sets of one exercise of a routine. Weight and reps are pre-filled from the last logged set.
+fn RoutineExerciseLog<'a>(cx: Scope<'a, RoutineExerciseLogProps<'a>>) -> Element<'a> {
+    let auth_token = use_read(&cx, ACTIVE_AUTH_TOKEN);
+    let exercise = cx.props.exercise;
+    let weight = use_state(&cx, || exercise.prefill_weight().unwrap_or(0.0));
+    let reps = use_state(&cx, || exercise.prefill_reps().unwrap_or(0));
+    let sets_done = use_state(&cx, || 0);
+
+    let has_weight = matches!(
+        exercise.kind,
+        models::ExerciseKind::Weighted | models::ExerciseKind::WeightedBodyweight
+    );
+    let has_reps = has_weight || exercise.kind == models::ExerciseKind::Bodyweight;
+    let progress = format!("{}/{} sets", sets_done, exercise.target_sets);
+    let name = exercise.name.clone();
+    let kind = exercise.kind.clone();
+
+    let inputs = if has_reps {
+        rsx! {
+            if has_weight {
+                rsx! {
+                    div {
+                        class: "form-group col-6 col-sm-2",
+                        input {
+                            class: "form-control",
+                            r#type: "number",
+                            step: "any",
+                            value: "{weight}",
+                            oninput: move |evt| {
+                                if let Ok(v) = evt.value.parse() {
+                                    weight.set(v)
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            div {
+                class: "form-group col-6 col-sm-2",
+                input {
+                    class: "form-control",
+                    r#type: "number",
+                    min: "0",
+                    value: "{reps}",
+                    oninput: move |evt| {
+                        if let Ok(v) = evt.value.parse() {
+                            reps.set(v)
+                        }
+                    }
+                }
+            }
+            div {
+                class: "col-auto",
+                button {
+                    r#type: "button",
+                    class: "btn btn-sm btn-outline-success",
+                    onclick: move |_| cx.spawn({
+                        to_owned![auth_token, weight, reps, sets_done, name, kind];
+                        let fetch_names = cx.props.fetch_names.clone();
+                        let display_message = cx.props.display_message.clone();
+
+                        async move {
+                            if *reps.current() <= 0 {
+                                return;
+                            }
+
+                            let client = reqwest::Client::new();
+                            let created_at = Utc::now().naive_utc().format("%Y-%m-%dT%H:%M").to_string();
+
+                            let exs: models::ExerciseSet = match kind {
+                                models::ExerciseKind::Weighted => models::ExerciseSetWeighted {
+                                    name: name.clone(),
+                                    reps: *reps.current(),
+                                    weight: *weight.current(),
+                                    rpe: None,
+                                    rir: None,
+                                    set_type: models::SetType::Working,
+                                    note: None,
+                                    created_at,
+                                }.into(),
+                                models::ExerciseKind::WeightedBodyweight => models::ExerciseSetWeightedBodyweight {
+                                    name: name.clone(),
+                                    reps: *reps.current(),
+                                    weight: *weight.current(),
+                                    rpe: None,
+                                    rir: None,
+                                    set_type: models::SetType::Working,
+                                    note: None,
+                                    created_at,
+                                }.into(),
+                                _ => models::ExerciseSetBodyweight {
+                                    name: name.clone(),
+                                    reps: *reps.current(),
+                                    rpe: None,
+                                    rir: None,
+                                    set_type: models::SetType::Working,
+                                    note: None,
+                                    created_at,
+                                }.into(),
+                            };
+
+                            let res = client.post(api::EXERCISE_SET.as_str())
+                                .json(&exs).bearer_auth(auth_token.unwrap_or("".into()))
+                                .send().await
+                                .handle_result::<models::NewRecords>(UIMessage::error("Submitting exercise failed.".to_string())).await;
+
+                            match res {
+                                Ok(records) => {
+                                    fetch_names.send(c::main_page::FetchNames);
+                                    display_message.send(UIMessage::added_set(&records, &name, format!("Added exercise \"{}\" x{}", name, *reps.current())));
+                                    sets_done.set(*sets_done.current() + 1);
+                                }
+                                Err(e) => display_message.send(e)
+                            }
+                        }
+                    }),
+                    "+"
+                }
+            }
+        }
+    } else {
+        rsx! {
+            p {
+                class: "col fw-light",
+                "Log this exercise with its form below."
+            }
+        }
+    };
+
+    cx.render(rsx! {
+        div {
+            class: "row g-1 g-sm-2 my-1",
+            div {
+                class: "col-12 col-sm-3",
+                p { class: "fw-bold mb-0", exercise.name.clone() }
+                p { class: "fw-light mb-0", "{progress}" }
+            }
+            inputs
+        }
+    })
+}
+
+#[derive(Props)]
+pub struct StartRoutineProps<'a> {
+    fetch_names: &'a Coroutine<c::main_page::FetchNames>,
+    display_message: &'a Coroutine<UIMessage>,
+}
+
+/// Pick one of the user's routines on the main page and log its exercises.
+pub fn StartRoutine<'a>(cx: Scope<'a, StartRoutineProps<'a>>) -> Element<'a> {
+    let auth_token = use_read(&cx, ACTIVE_AUTH_TOKEN);
+    let routines = use_state(&cx, || Vec::<models::RoutineQuery>::new());
+    let selected_id = use_state(&cx, || None::<i32>);
+    let active_routine = use_state(&cx, || None::<models::RoutineQuery>);
+
+    use_future(&cx, (), |()| {
+        to_owned![auth_token, routines, selected_id];
+        let display_message = cx.props.display_message.clone();
+
+        async move {
+            let client = reqwest::Client::new();
+            let res = client
+                .get(api::ROUTINE.as_str())
+                .bearer_auth(auth_token.unwrap_or("".into()))
+                .send()
+                .await
+                .handle_result::<Vec<models::RoutineQuery>>(UIMessage::error(
+                    "Requesting routines failed.".to_string(),
+                ))
+                .await;
+
+            match res {
+                Ok(res) => {
+                    selected_id.set(res.first().map(|routine| routine.id));
+                    routines.set(res);
+                }
+                Err(e) => display_message.send(e),
+            }
+        }
+    });
+
+    // Nothing to show if the user has no routines yet.
+    if routines.get().is_empty() {
+        return None;
+    }
+
+    let routine_options = routines.get().iter().map(|routine| {
+        rsx! {
+            option {
+                value: "{routine.id}",
+                selected: Some(routine.id) == *selected_id.get(),
+                routine.name.clone()
+            }
+        }
+    });
+
+    let content = match active_routine.get() {
+        Some(routine) => {
+            let exercise_logs = routine.exercises.iter().map(|exercise| {
+                rsx! {
+                    RoutineExerciseLog {
+                        key: "{exercise.id}",
+                        exercise: exercise,
+                        fetch_names: cx.props.fetch_names,
+                        display_message: cx.props.display_message
+                    }
+                }
+            });
+            rsx! {
+                p { class: "col-12 fw-bold", routine.name.clone() }
+                div {
+                    class: "col-12",
+                    exercise_logs
+                }
+                div {
+                    class: "col-auto",
+                    button {
+                        r#type: "button",
+                        class: "btn btn-sm btn-outline-secondary",
+                        onclick: move |_| active_routine.set(None),
+                        "Done"
+                    }
+                }
+            }
+        }
+        None => {
+            rsx! {
+                div {
+                    class: "form-group col-12 col-sm-auto",
+                    select {
+                        class: "form-select",
+                        onchange: move |evt| selected_id.set(evt.value.parse().ok()),
+                        routine_options
+                    }
+                }
+                div {
+                    class: "col-auto",
+                    button {
+                        r#type: "button",
+                        class: "btn btn-outline-success",
+                        onclick: move |_| cx.spawn({
+                            to_owned![auth_token, selected_id, active_routine];
+                            let display_message = cx.props.display_message.clone();
+
+                            async move {
+                                let routine_id = match *selected_id.current() {
+                                    Some(routine_id) => routine_id,
+                                    None => return,
+                                };
+
+                                // Fetch the routine again so that the last logged values are up to date.
+                                let client = reqwest::Client::new();
+                                let res = client
+                                    .get(format!("{}/{}", api::ROUTINE.as_str(), routine_id))
+                                    .bearer_auth(auth_token.unwrap_or("".into()))
+                                    .send()
+                                    .await
+                                    .handle_result::<models::RoutineQuery>(UIMessage::error(
+                                        "Requesting routine failed.".to_string(),
+                                    ))
+                                    .await;
+
+                                match res {
+                                    Ok(routine) => active_routine.set(Some(routine)),
+                                    Err(e) => display_message.send(e),
+                                }
+                            }
+                        }),
+                        "Start routine"
+                    }
+                }
+            }
+        }
+    };
+
+    cx.render(rsx! {
+        div {
+            class: "bg-body-tertiary my-3 p-2",
+            form {
+                class: "row g-1 g-sm-2",
+                p {
+                    class: "col-12",
+                    "Start from Routine"
+                }
+                content
+            }
+        }
+    })
+}
@@ -0,0 +1,343 @@
+#![allow(non_snake_case)]
+use chrono::{Local, TimeZone, Utc};
+use dioxus::prelude::*;
+use fermi::use_read;
+
+use crate::{
+    api,
+    auth::ACTIVE_AUTH_TOKEN,
+    components as c,
+    messages::{MessageProps, UIMessage},
+    request_ext::RequestExt,
+    util::*,
+};
+use gt_core::models;
+
+#[derive(Props)]
+pub struct SessionProps<'a> {
+    session: &'a models::WorkoutSessionQuery,
+    display_message: &'a Coroutine<UIMessage>,
+}
+
+fn Session<'a>(cx: Scope<'a, SessionProps<'a>>) -> Element<'a> {
+    let auth_token = use_read(&cx, ACTIVE_AUTH_TOKEN);
+    let sets = use_state(&cx, || None::<Vec<models::ExerciseSetQuery>>);
+    let show_sets = use_state(&cx, || false);
+
+    let session = cx.props.session;
+    let session_id = session.id;
+    let start_local = Local.from_utc_datetime(&session.start_time);
+    let duration = match session.end_time {
+        Some(end_time) => format_session_duration(session.start_time, end_time),
+        None => "in progress".to_string(),
+    };
+    let title = session.title.clone().unwrap_or("Workout".to_string());
+    let notes = session.notes.as_ref().map(|notes| {
+        rsx! {
+            p { class: "fst-italic", notes.clone() }
+        }
+    });
+    let toggle_label = if *show_sets.get() {
+        "Hide sets"
+    } else {
+        "Show sets"
+    };
+
+    let set_list = sets.get().iter().flatten().map(|exs| {
+        rsx! {
+            c::ExerciseSet { exs: exs, display_message: cx.props.display_message }
+        }
+    });
+
+    cx.render(rsx! {
+        li {
+            class: "list-group-item",
+            div {
+                class: "row",
+                div {
+                    class: "col",
+                    p { class: "fw-bold", title }
+                }
+                div {
+                    class: "col-auto",
+                    p { class: "fw-bold", format!("{} sets", session.set_count) }
+                }
+                div { class: "w-100" }
+                div {
+                    class: "col",
+                    p { class: "fw-light", format_date(start_local) }
+                }
+                div {
+                    class: "col-auto",
+                    p { class: "fw-light", duration }
+                }
+            }
+            notes
+            button {
+                class: "btn btn-sm btn-outline-secondary",
+                onclick: move |_| {
+                    show_sets.set(!*show_sets.current());
+
+                    if sets.current().is_none() {
+                        cx.spawn({
+                            to_owned![auth_token, sets];
+                            let display_message = cx.props.display_message.clone();
+
+                            async move {
+                                let client = reqwest::Client::new();
+                                let res = client
+                                    .get(format!("{}/{}", api::SESSION.as_str(), session_id))
+                                    .bearer_auth(auth_token.unwrap_or("".into()))
+                                    .send()
+                                    .await
+                                    .handle_result::<models::WorkoutSessionDetailQuery>(UIMessage::error(
+                                        "Requesting workout session failed.".to_string(),
+                                    ))
+                                    .await;
+
+                                match res {
+                                    Ok(detail) => sets.set(Some(detail.sets)),
+                                    Err(e) => display_message.send(e),
+                                }
+                            }
+                        })
+                    }
+                },
+                "{toggle_label}"
+            }
+            if *show_sets.get() {
+                rsx! {
+                    ul {
+                        class: "list-group list-group-flush my-2",
+                        set_list
+                    }
+                }
+            }
+        }
+    })
+}
+
+pub fn SessionPage<'a>(cx: Scope<'a, MessageProps<'a>>) -> Element<'a> {
+    let auth_token = use_read(&cx, ACTIVE_AUTH_TOKEN);
+
+    let fetch = use_future(&cx, (), |()| {
+        to_owned![auth_token];
+        let display_message = cx.props.display_message.clone();
+
+        async move {
+            let client = reqwest::Client::new();
+            let res = client
+                .get(api::SESSION.as_str())
+                .bearer_auth(auth_token.unwrap_or("".into()))
+                .send()
+                .await
+                .handle_result::<Vec<models::WorkoutSessionQuery>>(UIMessage::error(
+                    "Requesting workout sessions failed.".to_string(),
+                ))
+                .await;
+
+            match res {
+                Ok(sessions) => Some(sessions),
+                Err(e) => {
+                    display_message.send(e);
+                    None
+                }
+            }
+        }
+    });
+
+    let content = match fetch.value() {
+        Some(Some(sessions)) => {
+            let session_list = sessions.iter().map(|session| {
+                rsx! {
+                    Session { session: session, display_message: cx.props.display_message }
+                }
+            });
+            rsx! {
+                div {
+                    class: "my-3 p-2",
+                    ul {
+                        class: "list-group list-group-flush",
+                        session_list
+                    }
+                }
+            }
+        }
+        _ => {
+            rsx! {
+                p { "Loading" }
+            }
+        }
+    };
+
+    cx.render(rsx! {
+        div {
+            p { "Sessions page" }
+            rsx! { content }
+        }
+    })
+}
+
+/// Start and finish workout sessions from the main page.
+pub fn SessionControl<'a>(cx: Scope<'a, MessageProps<'a>>) -> Element<'a> {
+    let auth_token = use_read(&cx, ACTIVE_AUTH_TOKEN);
+    let active_session = use_state(&cx, || None::<models::WorkoutSessionQuery>);
+    let title = use_state(&cx, || "".to_string());
+    let notes = use_state(&cx, || "".to_string());
+
+    use_future(&cx, (), |()| {
+        to_owned![auth_token, active_session];
+        let display_message = cx.props.display_message.clone();
+
+        async move {
+            let client = reqwest::Client::new();
+            let res = client
+                .get(api::SESSION_ACTIVE.as_str())
+                .bearer_auth(auth_token.unwrap_or("".into()))
+                .send()
+                .await
+                .handle_result::<Option<models::WorkoutSessionQuery>>(UIMessage::error(
+                    "Requesting active workout session failed.".to_string(),
+                ))
+                .await;
+
+            match res {
+                Ok(session) => active_session.set(session),
+                Err(e) => display_message.send(e),
+            }
+        }
+    });
+
+    let content = match active_session.get() {
+        Some(session) => {
+            let session_id = session.id;
+            let start_local = Local.from_utc_datetime(&session.start_time);
+            let duration = format_session_duration(session.start_time, Utc::now().naive_utc());
+
+            rsx! {
+                p {
+                    class: "col-12",
+                    format!(
+                        "{} in progress since {} ({})",
+                        session.title.clone().unwrap_or("Workout".to_string()),
+                        start_local.format("%H:%M"),
+                        duration
+                    )
+                }
+                div {
+                    class: "form-group col-12 col-sm",
+                    label {
+                        r#for: "session-notes",
+                        "Notes"
+                    }
+                    input {
+                        class: "form-control",
+                        id: "session-notes",
+                        value: "{notes}",
+                        placeholder: "notes",
+                        autocomplete: "off",
+                        oninput: move |evt| notes.set(evt.value.clone()),
+                    }
+                }
+                div { class: "w-100" }
+                div {
+                    button {
+                        r#type: "button",
+                        class: "col-3 col-sm-1 btn btn-sm btn-outline-danger",
+                        onclick: move |_| cx.spawn({
+                            to_owned![auth_token, active_session, notes];
+                            let display_message = cx.props.display_message.clone();
+
+                            async move {
+                                let client = reqwest::Client::new();
+
+                                let finish = models::WorkoutSessionFinish {
+                                    id: session_id,
+                                    notes: Some((*notes.current()).clone()).filter(|notes| !notes.is_empty()),
+                                };
+
+                                let res = client.post(api::SESSION_FINISH.as_str())
+                                    .json(&finish).bearer_auth(auth_token.unwrap_or("".into()))
+                                    .send().await
+                                    .handle_result::<()>(UIMessage::error("Finishing workout failed.".to_string())).await;
+
+                                match res {
+                                    Ok(()) => {
+                                        display_message.send(UIMessage::info("Finished workout.".to_string()));
+                                        active_session.set(None);
+                                        notes.set("".to_string());
+                                    }
+                                    Err(e) => display_message.send(e)
+                                }
+                            }
+                        }),
+                        "Finish"
+                    }
+                }
+            }
+        }
+        None => {
+            rsx! {
+                div {
+                    class: "form-group col-12 col-sm-auto",
+                    label {
+                        r#for: "session-title",
+                        "Workout"
+                    }
+                    input {
+                        class: "form-control",
+                        id: "session-title",
+                        value: "{title}",
+                        placeholder: "title",
+                        autocomplete: "off",
+                        oninput: move |evt| title.set(evt.value.clone()),
+                    }
+                }
+                div { class: "w-100" }
+                div {
+                    button {
+                        r#type: "button",
+                        class: "col-3 col-sm-1 btn btn-sm btn-outline-success",
+                        onclick: move |_| cx.spawn({
+                            to_owned![auth_token, active_session, title];
+                            let display_message = cx.props.display_message.clone();
+
+                            async move {
+                                let client = reqwest::Client::new();
+
+                                let start = models::WorkoutSessionStart {
+                                    title: Some((*title.current()).clone()).filter(|title| !title.is_empty()),
+                                };
+
+                                let res = client.post(api::SESSION_START.as_str())
+                                    .json(&start).bearer_auth(auth_token.unwrap_or("".into()))
+                                    .send().await
+                                    .handle_result::<models::WorkoutSessionQuery>(UIMessage::error("Starting workout failed.".to_string())).await;
+
+                                match res {
+                                    Ok(session) => {
+                                        display_message.send(UIMessage::info("Started workout.".to_string()));
+                                        active_session.set(Some(session));
+                                        title.set("".to_string());
+                                    }
+                                    Err(e) => display_message.send(e)
+                                }
+                            }
+                        }),
+                        "Start"
+                    }
+                }
+            }
+        }
+    };
+
+    cx.render(rsx! {
+        div {
+            class: "bg-body-tertiary my-3 p-2",
+            form {
+                class: "row g-1 g-sm-2",
+                content
+            }
+        }
+    })
+}
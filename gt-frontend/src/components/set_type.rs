@@ -0,0 +1,79 @@
+#![allow(non_snake_case)]
+use dioxus::prelude::*;
+
+use gt_core::models;
+
+#[derive(Props)]
+pub struct SetTypeSelectProps<'a> {
+    id: &'a str,
+    set_type: &'a UseState<models::SetType>,
+}
+
+/// Selector for the set type that is shared by all add exercise set forms.
+pub fn SetTypeSelect<'a>(cx: Scope<'a, SetTypeSelectProps<'a>>) -> Element<'a> {
+    let set_type = cx.props.set_type;
+
+    let options = models::SetType::ALL.iter().map(|t| {
+        let value: i32 = (*t).into();
+        rsx! {
+            option {
+                value: "{value}",
+                selected: *t == *set_type.get(),
+                t.label()
+            }
+        }
+    });
+
+    cx.render(rsx! {
+        div {
+            class: "form-group col-12 col-sm-2",
+            label {
+                r#for: cx.props.id,
+                "Set type"
+            }
+            select {
+                class: "form-select",
+                id: cx.props.id,
+                onchange: move |evt| {
+                    if let Some(t) = evt
+                        .value
+                        .parse::<i32>()
+                        .ok()
+                        .and_then(|v| models::SetType::try_from(v).ok())
+                    {
+                        set_type.set(t)
+                    }
+                },
+                options
+            }
+        }
+    })
+}
+
+#[derive(Props)]
+pub struct ExcludeWarmupProps<'a> {
+    exclude_warmup: &'a UseState<bool>,
+}
+
+/// Checkbox to leave warm-up sets out of PRs, graphs and the running sum of reps.
+pub fn ExcludeWarmup<'a>(cx: Scope<'a, ExcludeWarmupProps<'a>>) -> Element<'a> {
+    let exclude_warmup = cx.props.exclude_warmup;
+
+    cx.render(rsx! {
+        div {
+            class: "form-check col-12 col-sm-auto d-flex align-items-center",
+            input {
+                class: "form-check-input",
+                id: "exclude-warmup",
+                r#type: "checkbox",
+                checked: "{exclude_warmup}",
+                onclick: move |_| exclude_warmup.set(!*exclude_warmup.get()),
+            }
+            label {
+                class: "form-check-label ms-1",
+                r#for: "exclude-warmup",
+                "Exclude warm-ups"
+            }
+        }
+    })
+}
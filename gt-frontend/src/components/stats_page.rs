@@ -1,10 +1,233 @@
 #![allow(non_snake_case)]
+use chrono::NaiveDate;
 use dioxus::prelude::*;
+use fermi::use_read;
+
+use crate::messages::{MessageProps, UIMessage};
+use crate::request_ext::RequestExt;
+use crate::{
+    api, auth::ACTIVE_AUTH_TOKEN, components as c, components::nav::WEIGHT_UNIT,
+    util::utc_offset_param,
+};
+use gt_core::models;
+
+fn parse_date(value: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d").ok()
+}
+
+fn format_date_input(date: Option<NaiveDate>) -> String {
+    date.map(|date| date.format("%Y-%m-%d").to_string())
+        .unwrap_or_default()
+}
+
+pub fn StatsPage<'a>(cx: Scope<'a, MessageProps<'a>>) -> Element<'a> {
+    let auth_token = use_read(&cx, ACTIVE_AUTH_TOKEN);
+    let unit = *use_read(&cx, WEIGHT_UNIT);
+    let from = use_state(&cx, || None::<NaiveDate>);
+    let to = use_state(&cx, || None::<NaiveDate>);
+    let exclude_warmup = use_state(&cx, || true);
+
+    let fetch = use_future(
+        &cx,
+        (*from.get(), *to.get(), *exclude_warmup.get()),
+        |(from, to, exclude_warmup)| {
+            to_owned![auth_token];
+            let display_message = cx.props.display_message.clone();
+
+            async move {
+                let client = reqwest::Client::new();
+                let res = client
+                    .get(api::STATS.as_str())
+                    .query(&models::StatsRange { from, to })
+                    .query(&utc_offset_param())
+                    .query(&models::SetTypeFilter { exclude_warmup })
+                    .bearer_auth(auth_token.unwrap_or("".into()))
+                    .send()
+                    .await
+                    .handle_result::<models::StatsQuery>(UIMessage::error(
+                        "Fetching stats failed.".to_string(),
+                    ))
+                    .await;
+
+                match res {
+                    Ok(stats) => Some(stats),
+                    Err(e) => {
+                        display_message.send(e);
+                        None
+                    }
+                }
+            }
+        },
+    );
+
+    let from_value = format_date_input(*from.get());
+    let to_value = format_date_input(*to.get());
+    let tonnage_label = format!("Tonnage ({})", unit.label());
+
+    let content = match fetch.value() {
+        Some(Some(stats)) => {
+            let tonnage = format!("{:.1}", stats.tonnage);
+            let average_session = stats
+                .average_session_minutes
+                .map(|minutes| format!("{:.0} min", minutes))
+                .unwrap_or("-".to_string());
+            let period_rows = |periods: &'a Vec<models::StatsPeriod>, format: &'static str| {
+                periods.iter().rev().map(move |period| {
+                    let start = period.start.format(format).to_string();
+                    let tonnage = format!("{:.1}", period.tonnage);
+                    rsx! {
+                        tr {
+                            key: "{start}",
+                            th { "{start}" }
+                            td { "{period.training_days}" }
+                            td { "{period.sets}" }
+                            td { "{period.reps}" }
+                            td { "{tonnage}" }
+                        }
+                    }
+                })
+            };
+            let week_rows = period_rows(&stats.per_week, "%d.%m.%y");
+            let month_rows = period_rows(&stats.per_month, "%m/%Y");
+            let exercise_rows = stats.top_exercises.iter().map(|exercise| {
+                let tonnage = format!("{:.1}", exercise.tonnage);
+                rsx! {
+                    tr {
+                        key: "{exercise.name}",
+                        th { "{exercise.name}" }
+                        td { "{exercise.sets}" }
+                        td { "{exercise.reps}" }
+                        td { "{tonnage}" }
+                    }
+                }
+            });
+
+            rsx! {
+                div {
+                    class: "bg-body-tertiary",
+                    p { "Total" }
+                    ul {
+                        li { "Training days: {stats.training_days}" }
+                        li { "Sets: {stats.sets}" }
+                        li { "Reps: {stats.reps}" }
+                        li { "{tonnage_label}: {tonnage}" }
+                        li { "Average session: {average_session}" }
+                    }
+                }
+                div {
+                    class: "bg-body-tertiary",
+                    p { "Most trained exercises" }
+                    div {
+                        class: "table-responsive",
+                        table {
+                            class: "table table-sm table-striped",
+                            thead {
+                                tr {
+                                    th { "Exercise" }
+                                    th { "Sets" }
+                                    th { "Reps" }
+                                    th { "{tonnage_label}" }
+                                }
+                            }
+                            tbody { exercise_rows }
+                        }
+                    }
+                }
+                div {
+                    class: "bg-body-tertiary",
+                    p { "Per week" }
+                    div {
+                        class: "table-responsive",
+                        table {
+                            class: "table table-sm table-striped",
+                            thead {
+                                tr {
+                                    th { "Week of" }
+                                    th { "Days" }
+                                    th { "Sets" }
+                                    th { "Reps" }
+                                    th { "{tonnage_label}" }
+                                }
+                            }
+                            tbody { week_rows }
+                        }
+                    }
+                }
+                div {
+                    class: "bg-body-tertiary",
+                    p { "Per month" }
+                    div {
+                        class: "table-responsive",
+                        table {
+                            class: "table table-sm table-striped",
+                            thead {
+                                tr {
+                                    th { "Month" }
+                                    th { "Days" }
+                                    th { "Sets" }
+                                    th { "Reps" }
+                                    th { "{tonnage_label}" }
+                                }
+                            }
+                            tbody { month_rows }
+                        }
+                    }
+                }
+            }
+        }
+        _ => {
+            rsx! {
+                p { "Loading" }
+            }
+        }
+    };
 
-pub fn StatsPage(cx: Scope) -> Element {
     cx.render(rsx! {
         div {
             p { "Stats page" }
+            c::TrainingCalendar { display_message: cx.props.display_message }
+            div {
+                class: "my-3 p-2",
+                form {
+                    class: "row g-1 g-sm-2",
+                    div {
+                        class: "form-group col-6 col-sm-auto",
+                        label { r#for: "stats-from", "From" }
+                        input {
+                            id: "stats-from",
+                            class: "form-control",
+                            r#type: "date",
+                            value: "{from_value}",
+                            oninput: move |evt| from.set(parse_date(&evt.value))
+                        }
+                    }
+                    div {
+                        class: "form-group col-6 col-sm-auto",
+                        label { r#for: "stats-to", "To" }
+                        input {
+                            id: "stats-to",
+                            class: "form-control",
+                            r#type: "date",
+                            value: "{to_value}",
+                            oninput: move |evt| to.set(parse_date(&evt.value))
+                        }
+                    }
+                    div {
+                        class: "form-group col-12 col-sm-auto align-self-end",
+                        button {
+                            class: "btn btn-outline-secondary",
+                            r#type: "button",
+                            onclick: move |_| {
+                                from.set(None);
+                                to.set(None);
+                            },
+                            "All time"
+                        }
+                    }
+                    c::ExcludeWarmup { exclude_warmup: exclude_warmup }
+                }
+                content
+            }
         }
     })
 }
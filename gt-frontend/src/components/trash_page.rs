@@ -0,0 +1,199 @@
+#![allow(non_snake_case)]
+use chrono::{Local, TimeZone};
+use dioxus::prelude::*;
+use fermi::use_read;
+
+use crate::messages::{MessageProps, UIMessage};
+use crate::request_ext::RequestExt;
+use crate::{
+    api,
+    auth::ACTIVE_AUTH_TOKEN,
+    components as c,
+    components::nav::WEIGHT_UNIT,
+    util::{format_date, format_weight},
+};
+use gt_core::models;
+
+fn format_user_info_ts(info: &models::TrashedUserInfoTs, unit: models::WeightUnit) -> String {
+    [
+        info.height.map(|height| format!("height {}cm", height)),
+        info.weight
+            .map(|weight| format!("weight {}", format_weight(weight, unit))),
+        info.muscle_mass
+            .map(|muscle_mass| format!("muscle mass {}", format_weight(muscle_mass, unit))),
+        info.body_fat
+            .map(|body_fat| format!("body fat {}%", body_fat)),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>()
+    .join(", ")
+}
+
+pub fn TrashPage<'a>(cx: Scope<'a, MessageProps<'a>>) -> Element<'a> {
+    let auth_token = use_read(&cx, ACTIVE_AUTH_TOKEN);
+    let unit = *use_read(&cx, WEIGHT_UNIT);
+    // Bumped after restoring an item to fetch the trash again.
+    let version = use_state(&cx, || 0);
+
+    let fetch = use_future(&cx, (*version.get(),), |_| {
+        to_owned![auth_token];
+        let display_message = cx.props.display_message.clone();
+
+        async move {
+            let client = reqwest::Client::new();
+            let res = client
+                .get(api::TRASH.as_str())
+                .bearer_auth(auth_token.unwrap_or("".into()))
+                .send()
+                .await
+                .handle_result::<models::TrashQuery>(UIMessage::error(
+                    "Fetching trash failed.".to_string(),
+                ))
+                .await;
+
+            match res {
+                Ok(trash) => Some(trash),
+                Err(e) => {
+                    display_message.send(e);
+                    None
+                }
+            }
+        }
+    });
+
+    let restore = move |item: models::TrashItem| {
+        cx.spawn({
+            to_owned![auth_token, version];
+            let display_message = cx.props.display_message.clone();
+
+            async move {
+                let client = reqwest::Client::new();
+                let res = client
+                    .post(api::TRASH_RESTORE.as_str())
+                    .json(&item)
+                    .bearer_auth(auth_token.unwrap_or("".into()))
+                    .send()
+                    .await
+                    .handle_result::<()>(UIMessage::error("Restoring item failed.".to_string()))
+                    .await;
+
+                match res {
+                    Ok(()) => version.modify(|version| version + 1),
+                    Err(e) => display_message.send(e),
+                }
+            }
+        })
+    };
+
+    let content = match fetch.value() {
+        Some(Some(trash)) => {
+            let set_list = trash.exercise_sets.iter().map(|trashed| {
+                let id = trashed.exercise_set.id();
+                let deleted_at = format_date(Local.from_utc_datetime(&trashed.deleted_at));
+                rsx! {
+                    li {
+                        key: "{id}",
+                        class: "list-group-item",
+                        div {
+                            class: "row",
+                            div {
+                                class: "col",
+                                c::ExerciseSetInfo { exs: &trashed.exercise_set }
+                                p { class: "fw-light small mb-0", "Deleted {deleted_at}" }
+                            }
+                            div {
+                                class: "col-auto d-flex align-items-center",
+                                button {
+                                    class: "btn btn-sm btn-outline-secondary",
+                                    onclick: move |_| restore(models::TrashItem::ExerciseSet(id)),
+                                    "Restore"
+                                }
+                            }
+                        }
+                    }
+                }
+            });
+            let info_list = trash.user_info_ts.iter().map(|info| {
+                let id = info.id;
+                let values = format_user_info_ts(info, unit);
+                let created_at = format_date(Local.from_utc_datetime(&info.created_at));
+                let deleted_at = format_date(Local.from_utc_datetime(&info.deleted_at));
+                rsx! {
+                    li {
+                        key: "{id}",
+                        class: "list-group-item",
+                        div {
+                            class: "row",
+                            div {
+                                class: "col",
+                                p { class: "mb-0", "{values}" }
+                                p { class: "fw-light small mb-0", "Recorded {created_at}, deleted {deleted_at}" }
+                            }
+                            div {
+                                class: "col-auto d-flex align-items-center",
+                                button {
+                                    class: "btn btn-sm btn-outline-secondary",
+                                    onclick: move |_| restore(models::TrashItem::UserInfoTs(id)),
+                                    "Restore"
+                                }
+                            }
+                        }
+                    }
+                }
+            });
+
+            rsx! {
+                div {
+                    class: "bg-body-tertiary my-3 p-2",
+                    p { "Exercise sets" }
+                    if trash.exercise_sets.is_empty() {
+                        rsx! { p { class: "fw-light", "No deleted exercise sets." } }
+                    } else {
+                        rsx! {
+                            ul {
+                                class: "list-group list-group-flush",
+                                set_list
+                            }
+                        }
+                    }
+                }
+                div {
+                    class: "bg-body-tertiary my-3 p-2",
+                    p { "Body measurements" }
+                    if trash.user_info_ts.is_empty() {
+                        rsx! { p { class: "fw-light", "No deleted body measurements." } }
+                    } else {
+                        rsx! {
+                            ul {
+                                class: "list-group list-group-flush",
+                                info_list
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        _ => {
+            rsx! {
+                p { "Loading" }
+            }
+        }
+    };
+
+    let retention = format!(
+        "Deleted items are removed for good after {} days.",
+        models::TRASH_RETENTION_DAYS
+    );
+
+    cx.render(rsx! {
+        div {
+            p { "Trash page" }
+            p {
+                class: "fw-light",
+                "{retention}"
+            }
+            content
+        }
+    })
+}
@@ -10,7 +10,10 @@ use std::io::Cursor;
 use crate::{
     api,
     auth::ACTIVE_AUTH_TOKEN,
-    components::nav::{self, WrapperUserPicture, USER_PICTURE},
+    components::{
+        export_data::ExportData,
+        nav::{self, WrapperUserPicture, USER_PICTURE, WEIGHT_UNIT},
+    },
     messages::{MessageProps, UIMessage},
     request_ext::RequestExt,
     to_dataurl,
@@ -39,6 +42,9 @@ fn downscale_image(bytes: Vec<u8>) -> Vec<u8> {
 pub fn UserPage<'a>(cx: Scope<'a, MessageProps<'a>>) -> Element<'a> {
     let auth_token = use_read(&cx, ACTIVE_AUTH_TOKEN);
     let display_name = use_state(&cx, || "".to_string());
+    let weight_unit = use_atom_state(&cx, WEIGHT_UNIT);
+    let selected_weight_unit = use_state(&cx, models::WeightUnit::default);
+    let e1rm_formula = use_state(&cx, models::E1rmFormula::default);
     let user_picture = use_atom_state(&cx, USER_PICTURE);
     let user_picture_bytes = use_state(&cx, || Vec::new());
     let body_height = use_state(&cx, || 0.0);
@@ -55,6 +61,8 @@ pub fn UserPage<'a>(cx: Scope<'a, MessageProps<'a>>) -> Element<'a> {
         to_owned![
             auth_token,
             display_name,
+            selected_weight_unit,
+            e1rm_formula,
             body_height_latest,
             body_weight_latest,
             muscle_mass_latest,
@@ -77,6 +85,8 @@ pub fn UserPage<'a>(cx: Scope<'a, MessageProps<'a>>) -> Element<'a> {
             match res {
                 Ok(user_info) => {
                     display_name.set(user_info.display_name);
+                    selected_weight_unit.set(user_info.weight_unit);
+                    e1rm_formula.set(user_info.e1rm_formula);
                     body_weight_latest.set(user_info.weight.unwrap_or(0.0));
                     body_height_latest.set(user_info.height.unwrap_or(0.0));
                     muscle_mass_latest.set(user_info.muscle_mass.unwrap_or(0.0));
@@ -93,6 +103,28 @@ pub fn UserPage<'a>(cx: Scope<'a, MessageProps<'a>>) -> Element<'a> {
         crate::attachToFile();
     });
 
+    let unit_label = weight_unit.current().label();
+    let weight_unit_options = models::WeightUnit::ALL.iter().map(|unit| {
+        let value: i32 = (*unit).into();
+        rsx! {
+            option {
+                value: "{value}",
+                selected: *unit == *selected_weight_unit.get(),
+                unit.label()
+            }
+        }
+    });
+    let e1rm_formula_options = models::E1rmFormula::ALL.iter().map(|formula| {
+        let value: i32 = (*formula).into();
+        rsx! {
+            option {
+                value: "{value}",
+                selected: *formula == *e1rm_formula.get(),
+                formula.label()
+            }
+        }
+    });
+
     let user_form = rsx! {
     div {
         class: "bg-body-tertiary my-3 p-2",
@@ -118,6 +150,50 @@ pub fn UserPage<'a>(cx: Scope<'a, MessageProps<'a>>) -> Element<'a> {
                     oninput: move |evt| display_name.set(evt.value.clone()),
                 }
             }
+            div {
+                class: "form-group col-12 col-sm-auto",
+                label {
+                    r#for: "weight-unit",
+                    "Weight Unit"
+                }
+                select {
+                    class: "form-select",
+                    id: "weight-unit",
+                    onchange: move |evt| {
+                        if let Some(unit) = evt
+                            .value
+                            .parse::<i32>()
+                            .ok()
+                            .and_then(|v| models::WeightUnit::try_from(v).ok())
+                        {
+                            selected_weight_unit.set(unit)
+                        }
+                    },
+                    weight_unit_options
+                }
+            }
+            div {
+                class: "form-group col-12 col-sm-auto",
+                label {
+                    r#for: "e1rm-formula",
+                    "e1RM Formula"
+                }
+                select {
+                    class: "form-select",
+                    id: "e1rm-formula",
+                    onchange: move |evt| {
+                        if let Some(formula) = evt
+                            .value
+                            .parse::<i32>()
+                            .ok()
+                            .and_then(|v| models::E1rmFormula::try_from(v).ok())
+                        {
+                            e1rm_formula.set(formula)
+                        }
+                    },
+                    e1rm_formula_options
+                }
+            }
             div {
                 class: "form-group col-12 col-sm-auto",
                 label {
@@ -169,11 +245,13 @@ pub fn UserPage<'a>(cx: Scope<'a, MessageProps<'a>>) -> Element<'a> {
                     onclick: move |_| {
                         let user_info = models::UserInfo {
                             display_name: (*display_name.current()).clone(),
+                            weight_unit: *selected_weight_unit.current(),
+                            e1rm_formula: *e1rm_formula.current(),
                         };
                         let bytes = (*user_picture_bytes.current()).clone();
 
                         cx.spawn({
-                            to_owned![auth_token];
+                            to_owned![auth_token, weight_unit, fetch];
                             let display_message = cx.props.display_message.clone();
 
                             async move {
@@ -188,6 +266,9 @@ pub fn UserPage<'a>(cx: Scope<'a, MessageProps<'a>>) -> Element<'a> {
                                 match res {
                                     Ok(()) => {
                                         display_message.send(UIMessage::info(format!("Updated user info.")));
+                                        // the body info is returned in the new unit
+                                        weight_unit.set(user_info.weight_unit);
+                                        fetch.restart();
                                     }
                                     Err(e) => display_message.send(e)
                                 }
@@ -264,9 +345,9 @@ pub fn UserPage<'a>(cx: Scope<'a, MessageProps<'a>>) -> Element<'a> {
                 class: "col-12",
                 p { "Current Body Info" }
                 ul {
-                    li { format!("Bodyweight: {} kg", body_weight_latest.current()) }
+                    li { format!("Bodyweight: {} {}", body_weight_latest.current(), unit_label) }
                     li { format!("Height: {} cm", body_height_latest.current()) }
-                    li { format!("Muscle Mass: {} kg", muscle_mass_latest.current()) }
+                    li { format!("Muscle Mass: {} {}", muscle_mass_latest.current(), unit_label) }
                     li { format!("Body Fat: {} %", body_fat_latest.current()) }
                 }
             }
@@ -274,7 +355,7 @@ pub fn UserPage<'a>(cx: Scope<'a, MessageProps<'a>>) -> Element<'a> {
                 class: "form-group col-12 col-sm-auto",
                 label {
                     r#for: "body-weight",
-                    "Bodyweight (kg)"
+                    format!("Bodyweight ({})", unit_label)
                 }
                 input {
                     class: "form-control",
@@ -313,7 +394,7 @@ pub fn UserPage<'a>(cx: Scope<'a, MessageProps<'a>>) -> Element<'a> {
                 class: "form-group col-12 col-sm-auto",
                 label {
                     r#for: "muscle-mass",
-                    "Muscle Mass (kg)"
+                    format!("Muscle Mass ({})", unit_label)
                 }
                 input {
                     class: "form-control",
@@ -425,6 +506,7 @@ pub fn UserPage<'a>(cx: Scope<'a, MessageProps<'a>>) -> Element<'a> {
                 div {
                     user_form
                     user_form_ts
+                    ExportData { display_message: cx.props.display_message }
                 }
             }
         }
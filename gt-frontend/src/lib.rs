@@ -4,6 +4,7 @@ mod api;
 mod auth;
 mod components;
 mod messages;
+mod offline_queue;
 mod request_ext;
 mod util;
 
@@ -95,6 +96,7 @@ pub fn app(cx: Scope) -> Element {
                 div {
                     p { BANNER.clone() }
                 }
+                c::PendingSets { display_message: display_message }
                 Route { to: "/login", c::LoggedOut{ c::LoginPage { display_message: display_message } }}
                 Route { to: "/register", c::LoggedOut {  c::RegisterPage { display_message: display_message }  }}
                 Route { to: "/admin", c::Superuser { c::AdminPage { display_message: display_message } }}
@@ -102,7 +104,11 @@ pub fn app(cx: Scope) -> Element {
                 Route { to: "/history", c::LoggedIn { c::HistoryPage { display_message: display_message } }}
                 Route { to: "/graph", c::LoggedIn { c::GraphPage { display_message: display_message } }}
                 Route { to: "/pr", c::LoggedIn { c::PRPage { display_message: display_message } }}
-                Route { to: "/stats", c::LoggedIn { c::StatsPage {} }}
+                Route { to: "/sessions", c::LoggedIn { c::SessionPage { display_message: display_message } }}
+                Route { to: "/routines", c::LoggedIn { c::RoutinePage { display_message: display_message } }}
+                Route { to: "/programs", c::LoggedIn { c::ProgramPage { display_message: display_message } }}
+                Route { to: "/stats", c::LoggedIn { c::StatsPage { display_message: display_message } }}
+                Route { to: "/trash", c::LoggedIn { c::TrashPage { display_message: display_message } }}
                 Route { to: "", c::MainPage { display_message: display_message }}
             }
         }
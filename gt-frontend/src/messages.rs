@@ -2,8 +2,11 @@ use std::fmt;
 
 use chrono::Duration;
 use dioxus::prelude::*;
+use itertools::join;
 
-use crate::MESSAGE_TIMEOUT;
+use gt_core::models;
+
+use crate::{offline_queue::Submission, MESSAGE_TIMEOUT};
 
 #[derive(Props)]
 pub struct MessageProps<'a> {
@@ -50,6 +53,31 @@ impl UIMessage {
         }
     }
 
+    /// The message after logging a set. If the set broke any records we celebrate them instead of
+    /// only confirming the set.
+    pub fn added_set(records: &models::NewRecords, name: &str, added: String) -> Self {
+        if records.is_empty() {
+            UIMessage::info(added)
+        } else {
+            UIMessage::info(format!(
+                "🎉 New {} for \"{}\"!",
+                join(records.labels(), ", "),
+                name
+            ))
+        }
+    }
+
+    /// Like [`UIMessage::added_set`], unless the set could only be queued because there is no connection.
+    pub fn submitted_set(submission: &Submission, name: &str, added: String) -> Self {
+        match submission {
+            Submission::Sent(records) => UIMessage::added_set(records, name, added),
+            Submission::Queued => UIMessage::info(format!(
+                "No connection. \"{}\" is saved and will be sent when you are back online.",
+                name
+            )),
+        }
+    }
+
     pub fn server_error() -> Self {
         UIMessage::error("Connection to server failed".to_string())
     }
@@ -0,0 +1,220 @@
+use log::{error, info};
+use reqwest::{Response, StatusCode};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use web_sys::{window, Storage};
+
+use crate::{api, messages::UIMessage, request_ext::RequestExt};
+use gt_core::models::{self, AuthToken};
+
+const QUEUE_KEY: &str = "exercise_set_queue";
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+enum Payload {
+    Set(models::ExerciseSet),
+    Group(models::ExerciseSetGroup),
+}
+
+/// A set or round of a group that could not be sent yet, together with the idempotency key it was first submitted with
+/// and the unit its weight was entered in, since the user might switch units before it is sent.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct QueuedSubmission {
+    key: String,
+    unit: models::WeightUnit,
+    payload: Payload,
+}
+
+impl QueuedSubmission {
+    fn sets(&self) -> &[models::ExerciseSet] {
+        match &self.payload {
+            Payload::Set(exs) => std::slice::from_ref(exs),
+            Payload::Group(group) => &group.sets,
+        }
+    }
+}
+
+/// The outcome of a submission. Without a connection to the server it is queued instead.
+pub enum Submission<T = models::NewRecords> {
+    Sent(T),
+    Queued,
+}
+
+fn storage() -> Option<Storage> {
+    window()?.local_storage().ok()?
+}
+
+fn get_queue() -> Vec<QueuedSubmission> {
+    storage()
+        .and_then(|storage| storage.get_item(QUEUE_KEY).ok()?)
+        .and_then(|queue| serde_json::from_str(&queue).ok())
+        .unwrap_or_default()
+}
+
+fn store_queue(queue: &[QueuedSubmission]) {
+    let storage = match storage() {
+        Some(storage) => storage,
+        None => return,
+    };
+
+    let res = if queue.is_empty() {
+        storage.remove_item(QUEUE_KEY)
+    } else {
+        match serde_json::to_string(queue) {
+            Ok(queue) => storage.set_item(QUEUE_KEY, &queue),
+            Err(e) => {
+                error!("{}", e);
+                return;
+            }
+        }
+    };
+    if res.is_err() {
+        error!("Storing the queued exercise sets failed.");
+    }
+}
+
+fn enqueue(queued: QueuedSubmission) {
+    let mut queue = get_queue();
+    queue.push(queued);
+    store_queue(&queue);
+}
+
+/// The queue is read again because sets might have been queued while another one was sent.
+fn dequeue(key: &str) {
+    let mut queue = get_queue();
+    queue.retain(|queued| queued.key != key);
+    store_queue(&queue);
+}
+
+/// The number of sets that still have to be sent.
+pub fn queued_count() -> usize {
+    get_queue().iter().map(|queued| queued.sets().len()).sum()
+}
+
+pub fn new_idempotency_key() -> String {
+    (0..4)
+        .map(|_| format!("{:08x}", (js_sys::Math::random() * u32::MAX as f64) as u32))
+        .collect()
+}
+
+/// Whether the server could not be reached because a proxy in front of it failed.
+fn is_gateway_error(status: StatusCode) -> bool {
+    status == StatusCode::BAD_GATEWAY
+        || status == StatusCode::SERVICE_UNAVAILABLE
+        || status == StatusCode::GATEWAY_TIMEOUT
+}
+
+async fn post_submission(
+    auth_token: Option<AuthToken>,
+    queued: &QueuedSubmission,
+) -> reqwest::Result<Response> {
+    let client = reqwest::Client::new();
+    let request = match &queued.payload {
+        Payload::Set(exs) => client.post(api::EXERCISE_SET.as_str()).json(exs),
+        Payload::Group(group) => client.post(api::EXERCISE_SET_GROUP.as_str()).json(group),
+    };
+    request
+        .query(&models::WeightUnitParam {
+            unit: Some(queued.unit),
+        })
+        .header(models::IDEMPOTENCY_KEY_HEADER, &queued.key)
+        .bearer_auth(auth_token.unwrap_or("".into()))
+        .send()
+        .await
+}
+
+async fn submit<T: DeserializeOwned>(
+    auth_token: Option<AuthToken>,
+    queued: QueuedSubmission,
+    error: &str,
+) -> Result<Submission<T>, UIMessage> {
+    let res = post_submission(auth_token, &queued).await;
+    let unreachable = match &res {
+        Ok(res) => is_gateway_error(res.status()),
+        Err(e) => {
+            info!("{}", e);
+            true
+        }
+    };
+    if unreachable {
+        enqueue(queued);
+        return Ok(Submission::Queued);
+    }
+
+    res.handle_result::<T>(UIMessage::error(error.to_string()))
+        .await
+        .map(Submission::Sent)
+}
+
+/// Submit a new set with its weight in `unit`. If the server cannot be reached, or only the proxy in front of it answers,
+/// the set is queued in the local storage and sent later by [`replay_queue`].
+pub async fn submit_exercise_set(
+    auth_token: Option<AuthToken>,
+    exs: models::ExerciseSet,
+    unit: models::WeightUnit,
+) -> Result<Submission, UIMessage> {
+    let queued = QueuedSubmission {
+        key: new_idempotency_key(),
+        unit,
+        payload: Payload::Set(exs),
+    };
+    submit(auth_token, queued, "Submitting exercise failed.").await
+}
+
+/// Submit a round of a group with the idempotency key `key`, which later rounds can use as their `group_key`
+/// as long as the id of the group is not known. Like [`submit_exercise_set`], the round is queued without a connection.
+/// A round whose group was started by a round that is still queued is queued behind it right away,
+/// so that the group exists when the round is sent.
+pub async fn submit_exercise_set_group(
+    auth_token: Option<AuthToken>,
+    key: String,
+    group: models::ExerciseSetGroup,
+    unit: models::WeightUnit,
+) -> Result<Submission<models::ExerciseSetGroupId>, UIMessage> {
+    let group_is_queued = match (&group.group_id, &group.group_key) {
+        (None, Some(group_key)) => get_queue().iter().any(|queued| &queued.key == group_key),
+        _ => false,
+    };
+    let queued = QueuedSubmission {
+        key,
+        unit,
+        payload: Payload::Group(group),
+    };
+    if group_is_queued {
+        enqueue(queued);
+        return Ok(Submission::Queued);
+    }
+
+    submit(auth_token, queued, "Submitting superset failed.").await
+}
+
+/// Send the queued sets and rounds in the order they were logged, until the server cannot be reached anymore.
+/// Submissions the server rejects are dropped since sending them again would not help, but errors of the server
+/// itself and an expired login keep them in the queue. Returns the number of sent sets and the names
+/// of the rejected ones.
+pub async fn replay_queue(auth_token: Option<AuthToken>) -> (usize, Vec<String>) {
+    let mut sent = 0;
+    let mut rejected = vec![];
+
+    for queued in get_queue() {
+        match post_submission(auth_token.clone(), &queued).await {
+            Ok(res) if res.status().is_success() => {
+                dequeue(&queued.key);
+                sent += queued.sets().len();
+            }
+            Ok(res)
+                if res.status().is_server_error() || res.status() == StatusCode::UNAUTHORIZED =>
+            {
+                break
+            }
+            Ok(_) => {
+                dequeue(&queued.key);
+                rejected.extend(queued.sets().iter().map(|exs| exs.name().to_string()));
+            }
+            Err(e) => {
+                info!("{}", e);
+                break;
+            }
+        }
+    }
+
+    (sent, rejected)
+}
@@ -1,4 +1,12 @@
-use chrono::{DateTime, Local};
+use chrono::{DateTime, Local, NaiveDateTime};
+use gt_core::models::{UtcOffsetParam, WeightUnit};
+
+/// The time zone of the browser, so that the server takes dates as the days of the user instead of UTC days.
+pub fn utc_offset_param() -> UtcOffsetParam {
+    UtcOffsetParam {
+        utc_offset: Local::now().offset().local_minus_utc(),
+    }
+}
 
 pub fn format_date(t: DateTime<Local>) -> String {
     if t.date_naive() == Local::now().date_naive() {
@@ -8,18 +16,75 @@ pub fn format_date(t: DateTime<Local>) -> String {
     }
 }
 
-pub fn format_weighted_reps(reps: i32, weight: f64) -> String {
-    format!("{} @ {}kg", reps, weight)
+pub fn format_weight(weight: f64, unit: WeightUnit) -> String {
+    format!("{}{}", weight, unit.label())
+}
+
+pub fn format_weighted_reps(reps: i32, weight: f64, unit: WeightUnit) -> String {
+    format!("{} @ {}", reps, format_weight(weight, unit))
 }
 
 pub fn format_bodyweight_reps(reps: i32) -> String {
     format!("{} @ 身", reps)
 }
 
+/// Format the added weight of a weighted bodyweight set with an explicit sign, e.g. "+10kg" or "-25kg".
+pub fn format_added_weight(weight: f64, unit: WeightUnit) -> String {
+    if weight < 0.0 {
+        format!("-{}", format_weight(-weight, unit))
+    } else {
+        format!("+{}", format_weight(weight, unit))
+    }
+}
+
+pub fn format_weighted_bodyweight_reps(reps: i32, weight: f64, unit: WeightUnit) -> String {
+    format!("{} @ 身{}", reps, format_added_weight(weight, unit))
+}
+
+/// Format the effort of a set, which is recorded either as RPE or as reps in reserve.
+pub fn format_effort(rpe: Option<f64>, rir: Option<i32>) -> String {
+    match (rpe, rir) {
+        (Some(rpe), _) => format!("RPE {}", rpe),
+        (None, Some(rir)) => format!("{} RIR", rir),
+        (None, None) => "".to_string(),
+    }
+}
+
+pub fn format_time(time: i32) -> String {
+    if time < 60 {
+        format!("{}s", time)
+    } else {
+        format!("{}:{:02}min", time / 60, time % 60)
+    }
+}
+
+pub fn format_distance(distance: f64) -> String {
+    if distance < 1000.0 {
+        format!("{}m", distance)
+    } else {
+        format!("{:.2}km", distance / 1000.0)
+    }
+}
+
+/// Format a pace given in seconds per kilometer.
+pub fn format_pace(pace: f64) -> String {
+    let pace = pace.round() as i32;
+    format!("{}:{:02}/km", pace / 60, pace % 60)
+}
+
 pub fn format_running_sum(reps_fmt: String, reps_sum: i64) -> String {
     format!("+ {} = {}", reps_fmt, reps_sum)
 }
 
+pub fn format_session_duration(start: NaiveDateTime, end: NaiveDateTime) -> String {
+    let minutes = (end - start).num_minutes();
+    if minutes < 60 {
+        format!("{}min", minutes)
+    } else {
+        format!("{}h {:02}min", minutes / 60, minutes % 60)
+    }
+}
+
 pub fn lerp(a: f64, b: f64, t: f64) -> f64 {
     (1.0 - t) * a + t * b
 }
@@ -3,6 +3,22 @@ pub use sea_orm_migration::prelude::*;
 mod m20230113_140607_create_user_table;
 mod m20230113_140612_create_exercise_table;
 mod m20230212_105435_alter_user_superuser;
+mod m20230304_101500_create_workout_session_table;
+mod m20230311_183000_alter_exercise_set_distance;
+mod m20230318_120000_alter_exercise_set_set_type;
+mod m20230325_090000_alter_exercise_set_rpe;
+mod m20230401_110000_alter_exercise_set_group;
+mod m20230408_100000_create_routine_table;
+mod m20230415_100000_create_program_table;
+mod m20230422_100000_alter_exercise_name_metadata;
+mod m20230429_100000_create_exercise_alias_table;
+mod m20230506_100000_alter_exercise_name_owner;
+mod m20230513_100000_alter_user_info_weight_unit;
+mod m20230520_100000_alter_user_info_e1rm_formula;
+mod m20230527_100000_alter_exercise_set_note;
+mod m20230603_100000_alter_deleted_at;
+mod m20230610_100000_create_exercise_rest_table;
+mod m20230617_100000_alter_exercise_set_idempotency_key;
 
 pub struct Migrator;
 
@@ -13,6 +29,22 @@ impl MigratorTrait for Migrator {
             Box::new(m20230113_140607_create_user_table::Migration),
             Box::new(m20230113_140612_create_exercise_table::Migration),
             Box::new(m20230212_105435_alter_user_superuser::Migration),
+            Box::new(m20230304_101500_create_workout_session_table::Migration),
+            Box::new(m20230311_183000_alter_exercise_set_distance::Migration),
+            Box::new(m20230318_120000_alter_exercise_set_set_type::Migration),
+            Box::new(m20230325_090000_alter_exercise_set_rpe::Migration),
+            Box::new(m20230401_110000_alter_exercise_set_group::Migration),
+            Box::new(m20230408_100000_create_routine_table::Migration),
+            Box::new(m20230415_100000_create_program_table::Migration),
+            Box::new(m20230422_100000_alter_exercise_name_metadata::Migration),
+            Box::new(m20230429_100000_create_exercise_alias_table::Migration),
+            Box::new(m20230506_100000_alter_exercise_name_owner::Migration),
+            Box::new(m20230513_100000_alter_user_info_weight_unit::Migration),
+            Box::new(m20230520_100000_alter_user_info_e1rm_formula::Migration),
+            Box::new(m20230527_100000_alter_exercise_set_note::Migration),
+            Box::new(m20230603_100000_alter_deleted_at::Migration),
+            Box::new(m20230610_100000_create_exercise_rest_table::Migration),
+            Box::new(m20230617_100000_alter_exercise_set_idempotency_key::Migration),
         ]
     }
 }
@@ -0,0 +1,107 @@
+use sea_orm_migration::prelude::*;
+
+use crate::m20230113_140607_create_user_table::UserLogin;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(WorkoutSession::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(WorkoutSession::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(WorkoutSession::UserId).integer().not_null())
+                    .col(ColumnDef::new(WorkoutSession::Title).string())
+                    .col(ColumnDef::new(WorkoutSession::Notes).text())
+                    .col(
+                        ColumnDef::new(WorkoutSession::StartTime)
+                            .timestamp()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(WorkoutSession::EndTime).timestamp())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-workoutsession-user_id")
+                            .from(WorkoutSession::Table, WorkoutSession::UserId)
+                            .to(UserLogin::Table, UserLogin::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(ExerciseSet::Table)
+                    .add_column(ColumnDef::new(ExerciseSet::SessionId).integer())
+                    .to_owned(),
+            )
+            .await?;
+
+        // Deleting a session only ungroups its sets, the sets themselves are kept.
+        manager
+            .create_foreign_key(
+                ForeignKey::create()
+                    .name("fk-exerciseset-session_id")
+                    .from(ExerciseSet::Table, ExerciseSet::SessionId)
+                    .to(WorkoutSession::Table, WorkoutSession::Id)
+                    .on_delete(ForeignKeyAction::SetNull)
+                    .on_update(ForeignKeyAction::Cascade)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_foreign_key(
+                ForeignKey::drop()
+                    .name("fk-exerciseset-session_id")
+                    .table(ExerciseSet::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(ExerciseSet::Table)
+                    .drop_column(ExerciseSet::SessionId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(WorkoutSession::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum WorkoutSession {
+    Table,
+    Id,
+    UserId,
+    Title,
+    Notes,
+    StartTime,
+    EndTime,
+}
+
+#[derive(Iden)]
+enum ExerciseSet {
+    Table,
+    SessionId,
+}
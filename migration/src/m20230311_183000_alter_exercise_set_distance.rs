@@ -0,0 +1,36 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Distance in meters. The duration of distance based sets is stored in the existing `time` column.
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(ExerciseSet::Table)
+                    .add_column(ColumnDef::new(ExerciseSet::Distance).double())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(ExerciseSet::Table)
+                    .drop_column(ExerciseSet::Distance)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum ExerciseSet {
+    Table,
+    Distance,
+}
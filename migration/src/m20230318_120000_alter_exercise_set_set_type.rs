@@ -0,0 +1,41 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // All existing sets are counted as working sets.
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(ExerciseSet::Table)
+                    .add_column(
+                        ColumnDef::new(ExerciseSet::SetType)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(ExerciseSet::Table)
+                    .drop_column(ExerciseSet::SetType)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum ExerciseSet {
+    Table,
+    SetType,
+}
@@ -0,0 +1,39 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Effort of a set, either as RPE (6-10 in half steps) or as reps in reserve.
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(ExerciseSet::Table)
+                    .add_column(ColumnDef::new(ExerciseSet::Rpe).double())
+                    .add_column(ColumnDef::new(ExerciseSet::Rir).integer())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(ExerciseSet::Table)
+                    .drop_column(ExerciseSet::Rpe)
+                    .drop_column(ExerciseSet::Rir)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum ExerciseSet {
+    Table,
+    Rpe,
+    Rir,
+}
@@ -0,0 +1,37 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Sets of a superset or circuit share a group id, which is the id of the first set of the group.
+        // There is no foreign key so that the group stays intact when its first set is deleted.
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(ExerciseSet::Table)
+                    .add_column(ColumnDef::new(ExerciseSet::GroupId).integer())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(ExerciseSet::Table)
+                    .drop_column(ExerciseSet::GroupId)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum ExerciseSet {
+    Table,
+    GroupId,
+}
@@ -0,0 +1,123 @@
+use sea_orm_migration::prelude::*;
+
+use crate::m20230113_140607_create_user_table::UserLogin;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Routine::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Routine::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Routine::UserId).integer().not_null())
+                    .col(ColumnDef::new(Routine::Name).string().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-routine-user_id")
+                            .from(Routine::Table, Routine::UserId)
+                            .to(UserLogin::Table, UserLogin::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(RoutineExercise::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(RoutineExercise::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(RoutineExercise::RoutineId)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(RoutineExercise::NameId).integer().not_null())
+                    .col(
+                        ColumnDef::new(RoutineExercise::Position)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(RoutineExercise::TargetSets)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(RoutineExercise::TargetReps).integer())
+                    .col(ColumnDef::new(RoutineExercise::TargetWeight).double())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-routineexercise-routine_id")
+                            .from(RoutineExercise::Table, RoutineExercise::RoutineId)
+                            .to(Routine::Table, Routine::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-routineexercise-name_id")
+                            .from(RoutineExercise::Table, RoutineExercise::NameId)
+                            .to(ExerciseName::Table, ExerciseName::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(RoutineExercise::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(Routine::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum Routine {
+    Table,
+    Id,
+    UserId,
+    Name,
+}
+
+#[derive(Iden)]
+enum RoutineExercise {
+    Table,
+    Id,
+    RoutineId,
+    NameId,
+    Position,
+    TargetSets,
+    TargetReps,
+    TargetWeight,
+}
+
+#[derive(Iden)]
+enum ExerciseName {
+    Table,
+    Id,
+}
@@ -0,0 +1,143 @@
+use sea_orm_migration::prelude::*;
+
+use crate::m20230113_140607_create_user_table::UserLogin;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Program::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Program::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Program::UserId).integer().not_null())
+                    .col(ColumnDef::new(Program::Name).string().not_null())
+                    .col(ColumnDef::new(Program::StartDate).date_time().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-program-user_id")
+                            .from(Program::Table, Program::UserId)
+                            .to(UserLogin::Table, UserLogin::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(ProgramExercise::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ProgramExercise::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(ProgramExercise::ProgramId)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(ProgramExercise::NameId).integer().not_null())
+                    .col(
+                        ColumnDef::new(ProgramExercise::Position)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(ProgramExercise::Rule).integer().not_null())
+                    .col(ColumnDef::new(ProgramExercise::Sets).integer().not_null())
+                    .col(
+                        ColumnDef::new(ProgramExercise::MinReps)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ProgramExercise::MaxReps)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ProgramExercise::StartWeight)
+                            .double()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ProgramExercise::Increment)
+                            .double()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-programexercise-program_id")
+                            .from(ProgramExercise::Table, ProgramExercise::ProgramId)
+                            .to(Program::Table, Program::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-programexercise-name_id")
+                            .from(ProgramExercise::Table, ProgramExercise::NameId)
+                            .to(ExerciseName::Table, ExerciseName::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ProgramExercise::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(Program::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum Program {
+    Table,
+    Id,
+    UserId,
+    Name,
+    StartDate,
+}
+
+#[derive(Iden)]
+enum ProgramExercise {
+    Table,
+    Id,
+    ProgramId,
+    NameId,
+    Position,
+    Rule,
+    Sets,
+    MinReps,
+    MaxReps,
+    StartWeight,
+    Increment,
+}
+
+#[derive(Iden)]
+enum ExerciseName {
+    Table,
+    Id,
+}
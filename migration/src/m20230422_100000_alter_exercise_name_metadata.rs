@@ -0,0 +1,45 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Catalog information about an exercise, which is edited by admins.
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(ExerciseName::Table)
+                    .add_column(ColumnDef::new(ExerciseName::PrimaryMuscle).integer())
+                    .add_column(ColumnDef::new(ExerciseName::SecondaryMuscle).integer())
+                    .add_column(ColumnDef::new(ExerciseName::Equipment).integer())
+                    .add_column(ColumnDef::new(ExerciseName::MovementPattern).integer())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(ExerciseName::Table)
+                    .drop_column(ExerciseName::PrimaryMuscle)
+                    .drop_column(ExerciseName::SecondaryMuscle)
+                    .drop_column(ExerciseName::Equipment)
+                    .drop_column(ExerciseName::MovementPattern)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum ExerciseName {
+    Table,
+    PrimaryMuscle,
+    SecondaryMuscle,
+    Equipment,
+    MovementPattern,
+}
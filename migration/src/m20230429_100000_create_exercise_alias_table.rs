@@ -0,0 +1,61 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Other names (e.g. translations) under which an exercise can be logged.
+        manager
+            .create_table(
+                Table::create()
+                    .table(ExerciseAlias::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ExerciseAlias::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(ExerciseAlias::Alias)
+                            .string()
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(ColumnDef::new(ExerciseAlias::NameId).integer().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-exercisealias-name_id")
+                            .from(ExerciseAlias::Table, ExerciseAlias::NameId)
+                            .to(ExerciseName::Table, ExerciseName::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ExerciseAlias::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum ExerciseAlias {
+    Table,
+    Id,
+    Alias,
+    NameId,
+}
+
+#[derive(Iden)]
+enum ExerciseName {
+    Table,
+    Id,
+}
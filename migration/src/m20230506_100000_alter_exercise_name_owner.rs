@@ -0,0 +1,123 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::Statement;
+
+use crate::m20230113_140607_create_user_table::UserLogin;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Exercises without an owner form the global catalog, all others are private to their owner.
+        // Existing exercises become part of the global catalog.
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(ExerciseName::Table)
+                    .add_column(ColumnDef::new(ExerciseName::OwnerId).integer())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_foreign_key(
+                ForeignKey::create()
+                    .name("fk-exercisename-owner_id")
+                    .from(ExerciseName::Table, ExerciseName::OwnerId)
+                    .to(UserLogin::Table, UserLogin::Id)
+                    .on_delete(ForeignKeyAction::Cascade)
+                    .on_update(ForeignKeyAction::Cascade)
+                    .to_owned(),
+            )
+            .await?;
+
+        // Names only need to be unique per owner. The backend makes sure that a private name does not shadow a global one:
+        // private names are only created if there is no global one, and creating a global name merges private ones into it.
+        manager
+            .get_connection()
+            .execute(Statement::from_string(
+                manager.get_database_backend(),
+                "ALTER TABLE exercise_name DROP CONSTRAINT exercise_name_name_key".to_owned(),
+            ))
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-exercisename-name-owner_id")
+                    .table(ExerciseName::Table)
+                    .col(ExerciseName::Name)
+                    .col(ExerciseName::OwnerId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        // Postgres treats NULLs as distinct, so the global names need their own index.
+        manager
+            .get_connection()
+            .execute(Statement::from_string(
+                manager.get_database_backend(),
+                r#"CREATE UNIQUE INDEX "idx-exercisename-name-global" ON exercise_name (name) WHERE owner_id IS NULL"#
+                    .to_owned(),
+            ))
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx-exercisename-name-global")
+                    .table(ExerciseName::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx-exercisename-name-owner_id")
+                    .table(ExerciseName::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute(Statement::from_string(
+                manager.get_database_backend(),
+                "ALTER TABLE exercise_name ADD CONSTRAINT exercise_name_name_key UNIQUE (name)"
+                    .to_owned(),
+            ))
+            .await?;
+
+        manager
+            .drop_foreign_key(
+                ForeignKey::drop()
+                    .name("fk-exercisename-owner_id")
+                    .table(ExerciseName::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(ExerciseName::Table)
+                    .drop_column(ExerciseName::OwnerId)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum ExerciseName {
+    Table,
+    Name,
+    OwnerId,
+}
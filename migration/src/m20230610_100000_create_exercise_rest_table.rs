@@ -0,0 +1,80 @@
+use sea_orm_migration::prelude::*;
+
+use crate::m20230113_140607_create_user_table::UserLogin;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // The rest in seconds a user wants to take after a set of an exercise.
+        manager
+            .create_table(
+                Table::create()
+                    .table(ExerciseRest::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ExerciseRest::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(ExerciseRest::UserId).integer().not_null())
+                    .col(ColumnDef::new(ExerciseRest::NameId).integer().not_null())
+                    .col(ColumnDef::new(ExerciseRest::Rest).integer().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-exerciserest-user_id")
+                            .from(ExerciseRest::Table, ExerciseRest::UserId)
+                            .to(UserLogin::Table, UserLogin::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-exerciserest-name_id")
+                            .from(ExerciseRest::Table, ExerciseRest::NameId)
+                            .to(ExerciseName::Table, ExerciseName::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-exerciserest-user_id-name_id")
+                    .table(ExerciseRest::Table)
+                    .col(ExerciseRest::UserId)
+                    .col(ExerciseRest::NameId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ExerciseRest::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum ExerciseRest {
+    Table,
+    Id,
+    UserId,
+    NameId,
+    Rest,
+}
+
+#[derive(Iden)]
+enum ExerciseName {
+    Table,
+    Id,
+}